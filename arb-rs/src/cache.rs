@@ -1,8 +1,10 @@
-use redis::{aio::MultiplexedConnection, Client};
-use std::fmt;
+use redis::{aio::MultiplexedConnection, Client, RedisResult};
+use std::{collections::HashMap, fmt, future::Future, time::Instant};
 
 use crate::config::CacheConfig;
 use crate::error::Result;
+use crate::metrics::Metrics;
+use crate::services::usage::record_cache_lookup;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Provider {
@@ -31,10 +33,39 @@ pub struct Cache {
     client: Client,
     connection: MultiplexedConnection,
     config: CacheConfig,
+    metrics: Metrics,
+}
+
+/// Run a Redis command, recording how long it took
+async fn timed<T>(
+    metrics: &Metrics,
+    command: &str,
+    call: impl Future<Output = RedisResult<T>>,
+) -> RedisResult<T> {
+    let started = Instant::now();
+    let result = call.await;
+    metrics.observe_redis(command, started.elapsed());
+    result
+}
+
+/// What a key caches, for metrics: the data type of provider keys like
+/// `sportradar:scores:mlb`, otherwise the key's prefix
+fn data_type_label(key: &str) -> &str {
+    let mut parts = key.split(':');
+    let first = parts.next().unwrap_or_default();
+    match parts.next() {
+        Some(data_type)
+            if first == Provider::Sportradar.as_str()
+                || first == Provider::RollingInsights.as_str() =>
+        {
+            data_type
+        }
+        _ => first,
+    }
 }
 
 impl Cache {
-    pub async fn new(config: CacheConfig) -> Result<Self> {
+    pub async fn new(config: CacheConfig, metrics: Metrics) -> Result<Self> {
         let client = Client::open(config.redis_url.as_str())?;
         let connection = client.get_multiplexed_async_connection().await?;
 
@@ -42,10 +73,27 @@ impl Cache {
             client,
             connection,
             config,
+            metrics,
         })
     }
 
+    /// This connection as a store for sessions, keys, counters and user data:
+    /// always on and always expiring, whatever `cache.enabled` and
+    /// `cache.mode` say about cached responses
+    pub fn store(&self) -> Self {
+        let mut store = self.clone();
+        store.config.enabled = true;
+        store.config.mode = crate::config::CacheMode::TtlBased;
+        store
+    }
+
     /// Basic Redis SETEX with expiry argument, or SET for infinite caching
+    #[tracing::instrument(
+        name = "cache.setx",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
     pub async fn setx(
         &mut self,
         key: impl AsRef<str>,
@@ -58,25 +106,63 @@ impl Cache {
 
         match self.config.mode {
             crate::config::CacheMode::Infinite => {
-                redis::cmd("SET")
-                    .arg(key.as_ref())
-                    .arg(value)
-                    .exec_async(&mut self.connection)
-                    .await?;
+                timed(
+                    &self.metrics,
+                    "SET",
+                    redis::cmd("SET")
+                        .arg(key.as_ref())
+                        .arg(value)
+                        .exec_async(&mut self.connection),
+                )
+                .await?;
             }
             crate::config::CacheMode::TtlBased => {
-                redis::cmd("SETEX")
-                    .arg(key.as_ref())
-                    .arg(expiry_seconds)
-                    .arg(value)
-                    .exec_async(&mut self.connection)
-                    .await?;
+                timed(
+                    &self.metrics,
+                    "SETEX",
+                    redis::cmd("SETEX")
+                        .arg(key.as_ref())
+                        .arg(expiry_seconds)
+                        .arg(value)
+                        .exec_async(&mut self.connection),
+                )
+                .await?;
             }
         }
         Ok(())
     }
 
+    /// Redis SET without an expiry, for records kept until they're deleted
+    #[tracing::instrument(
+        name = "cache.set",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
+    pub async fn set(&mut self, key: impl AsRef<str>, value: &str) -> Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        timed(
+            &self.metrics,
+            "SET",
+            redis::cmd("SET")
+                .arg(key.as_ref())
+                .arg(value)
+                .exec_async(&mut self.connection),
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Lua script that sets the key and sets the expiry per the config
+    #[tracing::instrument(
+        name = "cache.setx_slide",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
     pub async fn setx_slide(&mut self, key: impl AsRef<str>, value: &str) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
@@ -84,11 +170,15 @@ impl Cache {
 
         match self.config.mode {
             crate::config::CacheMode::Infinite => {
-                redis::cmd("SET")
-                    .arg(key.as_ref())
-                    .arg(value)
-                    .exec_async(&mut self.connection)
-                    .await?;
+                timed(
+                    &self.metrics,
+                    "SET",
+                    redis::cmd("SET")
+                        .arg(key.as_ref())
+                        .arg(value)
+                        .exec_async(&mut self.connection),
+                )
+                .await?;
             }
             crate::config::CacheMode::TtlBased => {
                 let script = r#"
@@ -101,14 +191,18 @@ impl Cache {
                 return val
                 "#;
 
-                redis::cmd("EVAL")
-                    .arg(script)
-                    .arg(1)
-                    .arg(key.as_ref())
-                    .arg(value)
-                    .arg(self.config.default_ttl)
-                    .exec_async(&mut self.connection)
-                    .await?;
+                timed(
+                    &self.metrics,
+                    "EVAL",
+                    redis::cmd("EVAL")
+                        .arg(script)
+                        .arg(1)
+                        .arg(key.as_ref())
+                        .arg(value)
+                        .arg(self.config.default_ttl)
+                        .exec_async(&mut self.connection),
+                )
+                .await?;
             }
         }
 
@@ -116,20 +210,40 @@ impl Cache {
     }
 
     /// Simple Redis GET
+    #[tracing::instrument(
+        name = "cache.get",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()), cache.hit = tracing::field::Empty)
+    )]
     pub async fn get(&mut self, key: impl AsRef<str>) -> Result<Option<String>> {
         if !self.config.enabled {
             return Ok(None);
         }
 
-        let result: Option<String> = redis::cmd("GET")
-            .arg(key.as_ref())
-            .query_async(&mut self.connection)
-            .await?;
+        let result: Option<String> = timed(
+            &self.metrics,
+            "GET",
+            redis::cmd("GET")
+                .arg(key.as_ref())
+                .query_async(&mut self.connection),
+        )
+        .await?;
 
+        record_cache_lookup(key.as_ref(), result.is_some());
+        tracing::Span::current().record("cache.hit", result.is_some());
+        self.metrics
+            .observe_cache_lookup(data_type_label(key.as_ref()), result.is_some());
         Ok(result)
     }
 
     /// Lua script that gets the key and sets the expiry
+    #[tracing::instrument(
+        name = "cache.get_sliding",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()), cache.hit = tracing::field::Empty)
+    )]
     pub async fn get_sliding(&mut self, key: impl AsRef<str>) -> Result<Option<String>> {
         if !self.config.enabled {
             return Ok(None);
@@ -143,23 +257,281 @@ impl Cache {
         return val
         "#;
 
-        let result: Option<String> = redis::cmd("EVAL")
-            .arg(script)
-            .arg(1)
-            .arg(key.as_ref())
-            .arg(self.config.default_ttl)
-            .query_async(&mut self.connection)
-            .await?;
+        let result: Option<String> = timed(
+            &self.metrics,
+            "EVAL",
+            redis::cmd("EVAL")
+                .arg(script)
+                .arg(1)
+                .arg(key.as_ref())
+                .arg(self.config.default_ttl)
+                .query_async(&mut self.connection),
+        )
+        .await?;
 
+        record_cache_lookup(key.as_ref(), result.is_some());
+        tracing::Span::current().record("cache.hit", result.is_some());
+        self.metrics
+            .observe_cache_lookup(data_type_label(key.as_ref()), result.is_some());
         Ok(result)
     }
 
+    /// Redis GETDEL: read a single-use value so it can't be read again
+    #[tracing::instrument(
+        name = "cache.take",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
+    pub async fn take(&mut self, key: impl AsRef<str>) -> Result<Option<String>> {
+        if !self.config.enabled {
+            return Ok(None);
+        }
+
+        let result: Option<String> = timed(
+            &self.metrics,
+            "GETDEL",
+            redis::cmd("GETDEL")
+                .arg(key.as_ref())
+                .query_async(&mut self.connection),
+        )
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Spend a single-use value: GETDEL `key` and keep it under `used_key` for
+    /// `expiry_seconds`, atomically, so two requests can't both spend it
+    ///
+    /// Returns the value and whether it had already been spent.
+    #[tracing::instrument(
+        name = "cache.spend",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
+    pub async fn spend(
+        &mut self,
+        key: impl AsRef<str>,
+        used_key: impl AsRef<str>,
+        expiry_seconds: u64,
+    ) -> Result<Option<(String, bool)>> {
+        if !self.config.enabled {
+            return Ok(None);
+        }
+
+        let script = r#"
+        local val = redis.call("GETDEL", KEYS[1])
+        if val then
+            redis.call("SETEX", KEYS[2], ARGV[1], val)
+            return {val, 0}
+        end
+        local used = redis.call("GET", KEYS[2])
+        if used then
+            return {used, 1}
+        end
+        return false
+        "#;
+
+        let result: Option<(String, u8)> = timed(
+            &self.metrics,
+            "EVAL",
+            redis::cmd("EVAL")
+                .arg(script)
+                .arg(2)
+                .arg(key.as_ref())
+                .arg(used_key.as_ref())
+                .arg(expiry_seconds)
+                .query_async(&mut self.connection),
+        )
+        .await?;
+
+        Ok(result.map(|(value, spent)| (value, spent == 1)))
+    }
+
+    /// Redis DEL
+    #[tracing::instrument(
+        name = "cache.delete",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
+    pub async fn delete(&mut self, key: impl AsRef<str>) -> Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        timed(
+            &self.metrics,
+            "DEL",
+            redis::cmd("DEL")
+                .arg(key.as_ref())
+                .exec_async(&mut self.connection),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Redis INCR, starting the key's expiry on its first increment
+    #[tracing::instrument(
+        name = "cache.incr",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
+    pub async fn incr(
+        &mut self,
+        key: impl AsRef<str>,
+        expiry_seconds: u64,
+    ) -> Result<u64> {
+        if !self.config.enabled {
+            return Ok(0);
+        }
+
+        let script = r#"
+        local count = redis.call("INCR", KEYS[1])
+        if count == 1 then
+            redis.call("EXPIRE", KEYS[1], ARGV[1])
+        end
+        return count
+        "#;
+
+        let count: u64 = timed(
+            &self.metrics,
+            "EVAL",
+            redis::cmd("EVAL")
+                .arg(script)
+                .arg(1)
+                .arg(key.as_ref())
+                .arg(expiry_seconds)
+                .query_async(&mut self.connection),
+        )
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Redis HINCRBY for each field in one transaction, refreshing the
+    /// hash's expiry; returns the new values
+    #[tracing::instrument(
+        name = "cache.increment_fields",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
+    pub async fn increment_fields(
+        &mut self,
+        key: impl AsRef<str>,
+        fields: &[(String, u64)],
+        expiry_seconds: u64,
+    ) -> Result<Vec<u64>> {
+        if !self.config.enabled || fields.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for (field, value) in fields {
+            pipe.cmd("HINCRBY").arg(key.as_ref()).arg(field).arg(*value);
+        }
+        pipe.cmd("EXPIRE")
+            .arg(key.as_ref())
+            .arg(expiry_seconds)
+            .ignore();
+
+        let values: Vec<u64> = timed(
+            &self.metrics,
+            "HINCRBY",
+            pipe.query_async(&mut self.connection),
+        )
+        .await?;
+        Ok(values)
+    }
+
+    /// Redis HINCRBY of one counter by 1, unless that would take it past
+    /// `limit` (0 for none); `None` when the limit is reached
+    #[tracing::instrument(
+        name = "cache.increment_field_within",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
+    pub async fn increment_field_within(
+        &mut self,
+        key: impl AsRef<str>,
+        field: &str,
+        limit: u64,
+        expiry_seconds: u64,
+    ) -> Result<Option<u64>> {
+        if !self.config.enabled {
+            return Ok(Some(0));
+        }
+
+        let script = r#"
+        local count = redis.call("HINCRBY", KEYS[1], ARGV[1], 1)
+        local limit = tonumber(ARGV[2])
+        if limit > 0 and count > limit then
+            redis.call("HINCRBY", KEYS[1], ARGV[1], -1)
+            return false
+        end
+        redis.call("EXPIRE", KEYS[1], ARGV[3])
+        return count
+        "#;
+
+        let count: Option<u64> = timed(
+            &self.metrics,
+            "EVAL",
+            redis::cmd("EVAL")
+                .arg(script)
+                .arg(1)
+                .arg(key.as_ref())
+                .arg(field)
+                .arg(limit)
+                .arg(expiry_seconds)
+                .query_async(&mut self.connection),
+        )
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Redis HGETALL of a hash of counters
+    #[tracing::instrument(
+        name = "cache.get_fields",
+        level = "debug",
+        skip_all,
+        fields(data_type = data_type_label(key.as_ref()))
+    )]
+    pub async fn get_fields(
+        &mut self,
+        key: impl AsRef<str>,
+    ) -> Result<HashMap<String, u64>> {
+        if !self.config.enabled {
+            return Ok(HashMap::new());
+        }
+
+        let fields: HashMap<String, u64> = timed(
+            &self.metrics,
+            "HGETALL",
+            redis::cmd("HGETALL")
+                .arg(key.as_ref())
+                .query_async(&mut self.connection),
+        )
+        .await?;
+        Ok(fields)
+    }
+
     pub async fn ping(&mut self) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
         }
 
-        redis::cmd("PING").exec_async(&mut self.connection).await?;
+        timed(
+            &self.metrics,
+            "PING",
+            redis::cmd("PING").exec_async(&mut self.connection),
+        )
+        .await?;
         Ok(())
     }
 
@@ -168,9 +540,12 @@ impl Cache {
             return Ok(0);
         }
 
-        let size: usize = redis::cmd("DBSIZE")
-            .query_async(&mut self.connection)
-            .await?;
+        let size: usize = timed(
+            &self.metrics,
+            "DBSIZE",
+            redis::cmd("DBSIZE").query_async(&mut self.connection),
+        )
+        .await?;
         Ok(size)
     }
 }
@@ -272,6 +647,174 @@ impl CacheKey {
         CacheKey::with_provider(provider, format!("standings:{}:{}", league, season))
     }
 
+    /// Generate a cache key for standings analytics (magic numbers, clinch flags)
+    pub fn standings_analytics(
+        provider: Provider,
+        league: impl fmt::Display,
+        season: i32,
+    ) -> Self {
+        CacheKey::with_provider(
+            provider,
+            format!("standings_analytics:{}:{}", league, season),
+        )
+    }
+
+    /// Generate a cache key for simulated playoff odds
+    pub fn playoff_odds(
+        provider: Provider,
+        league: impl fmt::Display,
+        season: i32,
+        model: &str,
+        iterations: u32,
+    ) -> Self {
+        CacheKey::with_provider(
+            provider,
+            format!(
+                "playoff_odds:{}:{}:{}:{}",
+                league, season, model, iterations
+            ),
+        )
+    }
+
+    /// Generate a cache key for a league season's rolling Elo ratings
+    pub fn ratings(provider: Provider, league: impl fmt::Display, season: i32) -> Self {
+        CacheKey::with_provider(provider, format!("ratings:{}:{}", league, season))
+    }
+
+    /// Generate a cache key for final scores on a date
+    pub fn final_scores(
+        provider: Provider,
+        league: impl fmt::Display,
+        date: &str,
+    ) -> Self {
+        CacheKey::with_provider(provider, format!("final_scores:{}:{}", league, date))
+    }
+
+    /// Generate a cache key for player or team season stats
+    pub fn season_stats(
+        provider: Provider,
+        league: impl fmt::Display,
+        season: &str,
+        kind: &str,
+    ) -> Self {
+        CacheKey::with_provider(
+            provider,
+            format!("season_stats:{}:{}:{}", league, kind, season),
+        )
+    }
+
+    /// Generate a cache key for a player resource (profile, game log, season stats)
+    pub fn player(
+        provider: Provider,
+        league: impl fmt::Display,
+        player_id: i32,
+        resource: &str,
+    ) -> Self {
+        CacheKey::with_provider(
+            provider,
+            format!("player:{}:{}:{}", league, player_id, resource),
+        )
+    }
+
+    /// Generate a cache key for a league's injury report
+    pub fn injuries(provider: Provider, league: impl fmt::Display) -> Self {
+        CacheKey::with_provider(provider, format!("injuries:{}", league))
+    }
+
+    /// Generate a cache key for the last injury statuses seen, used for change detection
+    pub fn injury_snapshot(provider: Provider, league: impl fmt::Display) -> Self {
+        CacheKey::with_provider(provider, format!("injuries:snapshot:{}", league))
+    }
+
+    /// Generate a cache key for recent ruled-out starter alerts
+    pub fn starter_alerts(provider: Provider, league: impl fmt::Display) -> Self {
+        CacheKey::with_provider(provider, format!("injuries:starter_alerts:{}", league))
+    }
+
+    /// Generate a cache key for a league's depth charts
+    pub fn depth_charts(provider: Provider, league: impl fmt::Display) -> Self {
+        CacheKey::with_provider(provider, format!("depth_charts:{}", league))
+    }
+
+    /// Generate a cache key for starting lineups on a date
+    pub fn lineups(provider: Provider, league: impl fmt::Display, date: &str) -> Self {
+        CacheKey::with_provider(provider, format!("lineups:{}:{}", league, date))
+    }
+
+    /// Generate a cache key for a league's provider news
+    pub fn news(provider: Provider, league: impl fmt::Display) -> Self {
+        CacheKey::with_provider(provider, format!("news:{}", league))
+    }
+
+    /// Generate a cache key for a team's aggregated, deduplicated news feed
+    pub fn team_news(provider: Provider, league: impl fmt::Display, team: &str) -> Self {
+        CacheKey::with_provider(provider, format!("news:{}:{}", league, team))
+    }
+
+    /// Generate a cache key for a team's active roster
+    pub fn team_players(
+        provider: Provider,
+        league: impl fmt::Display,
+        team: &str,
+    ) -> Self {
+        CacheKey::with_provider(provider, format!("team_players:{}:{}", league, team))
+    }
+
+    /// Generate a cache key for one page of Twitter search results
+    pub fn twitter_search(query: &str, cursor: Option<&str>) -> Self {
+        match cursor {
+            Some(cursor) => CacheKey::new(format!("twitter_search:{}:{}", query, cursor)),
+            None => CacheKey::new(format!("twitter_search:{}", query)),
+        }
+    }
+
+    /// Generate a cache key for the long-lived copy of a Twitter search page
+    pub fn twitter_search_stale(query: &str, cursor: Option<&str>) -> Self {
+        CacheKey::new(format!(
+            "twitter_search:stale:{}:{}",
+            query,
+            cursor.unwrap_or_default()
+        ))
+    }
+
+    /// Generate a cache key for when a game's tweets were last collected
+    pub fn social_refresh(league: impl fmt::Display, game_id: &str) -> Self {
+        CacheKey::new(format!("social_refresh:{}:{}", league, game_id))
+    }
+
+    /// Generate a cache key for a league's video catalog
+    pub fn video_catalog(league: impl fmt::Display) -> Self {
+        CacheKey::new(format!("video_catalog:{}", league))
+    }
+
+    /// Generate a cache key for when a league's stored videos were last refreshed
+    pub fn video_refresh(league: impl fmt::Display) -> Self {
+        CacheKey::new(format!("video_refresh:{}", league))
+    }
+
+    /// Generate a cache key for the Reddit thread matched to a game
+    pub fn reddit_game_thread(league: impl fmt::Display, game_id: &str) -> Self {
+        CacheKey::new(format!("reddit:thread:{}:{}", league, game_id))
+    }
+
+    /// Generate a cache key for the first comments of a game's Reddit thread
+    pub fn reddit_thread_comments(
+        league: impl fmt::Display,
+        game_id: &str,
+        subreddit: Option<&str>,
+        sort: &str,
+        limit: u32,
+    ) -> Self {
+        CacheKey::new(format!(
+            "reddit:thread_comments:{}:{}:{}:{}:{}",
+            league,
+            game_id,
+            subreddit.unwrap_or_default().to_lowercase(),
+            sort,
+            limit
+        ))
+    }
+
     /// Generate a cache key for stadiums data
     pub fn stadiums(provider: Provider, league: impl fmt::Display) -> Self {
         CacheKey::with_provider(provider, format!("stadiums:{}", league))
@@ -296,6 +839,71 @@ impl CacheKey {
         CacheKey::with_provider(provider, format!("user:{}", email))
     }
 
+    /// Generate a cache key for a sign-in in progress, by its `state`
+    pub fn oauth_session(state: &str) -> Self {
+        CacheKey::new(format!("oauth_session:{}", state))
+    }
+
+    /// Generate a cache key for a one-time code a sign-in callback hands the client
+    pub fn auth_code(code: &str) -> Self {
+        CacheKey::new(format!("auth_code:{}", code))
+    }
+
+    /// Generate a cache key for a signed-in session
+    pub fn auth_session(session_id: &str) -> Self {
+        CacheKey::new(format!("auth_session:{}", session_id))
+    }
+
+    /// Generate a cache key for a live refresh token, by hash
+    pub fn refresh_token(hash: &str) -> Self {
+        CacheKey::new(format!("refresh_token:{}", hash))
+    }
+
+    /// Generate a cache key for a refresh token that's been rotated out, by hash
+    pub fn refresh_token_used(hash: &str) -> Self {
+        CacheKey::new(format!("refresh_token_used:{}", hash))
+    }
+
+    /// Generate a cache key for a user's preferences
+    pub fn user_preferences(user_id: &str) -> Self {
+        CacheKey::new(format!("user_preferences:{}", user_id))
+    }
+
+    /// Generate a cache key for an issued API key, by the key's hash
+    pub fn api_key(hash: &str) -> Self {
+        CacheKey::new(format!("api_key:{}", hash))
+    }
+
+    /// Generate a cache key mapping an API key's public id to its hash
+    pub fn api_key_id(key_id: &str) -> Self {
+        CacheKey::new(format!("api_key_id:{}", key_id))
+    }
+
+    /// Generate a cache key for a client's request count in one quota window
+    pub fn rate_limit(client: &str, window_start: u64) -> Self {
+        CacheKey::new(format!("rate_limit:{}:{}", client, window_start))
+    }
+
+    /// Generate a cache key for one day's upstream usage counters
+    pub fn usage_day(date: &str) -> Self {
+        CacheKey::new(format!("usage:day:{}", date))
+    }
+
+    /// Generate a cache key for one month's upstream call totals
+    pub fn usage_month(month: &str) -> Self {
+        CacheKey::new(format!("usage:month:{}", month))
+    }
+
+    /// Generate a cache key for the last readiness probe of an upstream
+    pub fn health_probe(upstream: impl fmt::Display) -> Self {
+        CacheKey::new(format!("health_probe:{}", upstream))
+    }
+
+    /// Generate a cache key for a user's favorite teams
+    pub fn user_favorites(user_id: &str) -> Self {
+        CacheKey::new(format!("user_favorites:{}", user_id))
+    }
+
     /// Generate a cache key for game by date data
     pub fn game_by_date(
         provider: Provider,
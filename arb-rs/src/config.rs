@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 
+use crate::services::{moderation::Strictness, usage::Upstream, videos::VideoType};
+
 /// Configuration for the Arbitration API server
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArbConfig {
@@ -13,6 +15,36 @@ pub struct ArbConfig {
     pub seasons: SeasonsConfig,
     /// API configuration
     pub api: ApiConfig,
+    /// Playoff odds simulation configuration
+    #[serde(default)]
+    pub simulation: SimulationConfig,
+    /// Team news feed configuration
+    #[serde(default)]
+    pub news: NewsConfig,
+    /// Per-game Twitter search configuration
+    #[serde(default)]
+    pub social: SocialConfig,
+    /// Social feed moderation
+    #[serde(default)]
+    pub moderation: ModerationConfig,
+    /// Video highlight sources
+    #[serde(default)]
+    pub videos: VideoConfig,
+    /// Access and refresh token lifetimes
+    #[serde(default)]
+    pub auth: AuthConfig,
+    /// API keys and per-client request quotas
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    /// Upstream call accounting and monthly caps
+    #[serde(default)]
+    pub usage: UsageConfig,
+    /// OpenTelemetry trace export
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    /// Postgres holding the tables in etc/schema
+    #[serde(default)]
+    pub database: DatabaseConfig,
 }
 
 /// Server configuration
@@ -26,6 +58,10 @@ pub struct ServerConfig {
     pub cors_origins: String,
     /// Client URL for redirects
     pub client_url: String,
+    /// Other places sign-in may return to, such as iOS deep links
+    /// (`arb://auth`); matched as prefixes
+    #[serde(default)]
+    pub return_urls: Vec<String>,
 }
 
 /// Cache mode configuration
@@ -53,8 +89,10 @@ pub struct CacheConfig {
     pub ttl: CacheTtlConfig,
 }
 
-/// Cache TTL configuration for different data types
+/// Cache TTL configuration for different data types; TTLs missing from the
+/// file keep their defaults
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CacheTtlConfig {
     /// TTL for team profiles (in seconds)
     pub team_profiles: u64,
@@ -74,6 +112,8 @@ pub struct CacheTtlConfig {
     pub standings: u64,
     /// TTL for Twitter search results (in seconds)
     pub twitter_search: u64,
+    /// TTL for the fallback copy of Twitter search results served while rate limited (in seconds)
+    pub twitter_search_stale: u64,
     /// TTL for Reddit game thread IDs (in seconds)
     pub reddit_thread: u64,
     /// TTL for Reddit game thread comments (in seconds)
@@ -82,6 +122,72 @@ pub struct CacheTtlConfig {
     pub odds: u64,
     /// TTL for user authentication data (in seconds)
     pub user_auth: u64,
+    /// TTL for a sign-in's state, nonce and PKCE verifier (in seconds)
+    pub oauth_state: u64,
+    /// TTL for user preferences, refreshed on every update (in seconds)
+    pub user_preferences: u64,
+    /// TTL for a user's favorite teams, refreshed on every update (in seconds)
+    pub user_favorites: u64,
+    /// TTL for simulated playoff odds (in seconds)
+    pub playoff_odds: u64,
+    /// TTL for persisted team ratings (in seconds)
+    pub ratings: u64,
+    /// TTL for player and team season stats (in seconds)
+    pub season_stats: u64,
+    /// TTL for player profiles, game logs and career stats (in seconds)
+    pub players: u64,
+    /// TTL for a player's stats in a finished season, which no longer change (in seconds)
+    pub career_seasons: u64,
+    /// TTL for injury reports (in seconds)
+    pub injuries: u64,
+    /// TTL for team depth charts (in seconds)
+    pub depth_charts: u64,
+    /// TTL for starting lineups and probable pitchers (in seconds)
+    pub lineups: u64,
+    /// TTL for ruled-out starter alerts and the injury snapshot they diff against (in seconds)
+    pub starter_alerts: u64,
+    /// TTL for provider news and aggregated team news feeds (in seconds)
+    pub news: u64,
+    /// TTL for a league's video catalog (in seconds)
+    pub videos: u64,
+    /// TTL for the result of a readiness probe of an upstream (in seconds)
+    pub health_probe: u64,
+}
+
+impl Default for CacheTtlConfig {
+    fn default() -> Self {
+        Self {
+            team_profiles: 3600,         // 1 hour
+            schedule: 3600,              // 1 hour
+            postseason_schedule: 3600,   // 1 hour
+            scores: 60,                  // 1 minute
+            play_by_play: 60,            // 1 minute
+            box_scores: 60,              // 1 minute
+            stadiums: 21600,             // 6 hours
+            standings: 21600,            // 6 hours
+            twitter_search: 60,          // 1 minute
+            twitter_search_stale: 86400, // 1 day
+            reddit_thread: 600,          // 10 minutes
+            reddit_thread_comments: 60,  // 1 minute
+            odds: 3600,                  // 1 hour
+            user_auth: 604800,           // 1 week
+            oauth_state: 600,            // 10 minutes
+            user_preferences: 31536000,  // 1 year
+            user_favorites: 63072000,    // 2 years
+            playoff_odds: 21600,         // 6 hours
+            ratings: 31536000,           // 1 year
+            season_stats: 3600,          // 1 hour
+            players: 3600,               // 1 hour
+            career_seasons: 31536000,    // 1 year
+            injuries: 300,               // 5 minutes
+            depth_charts: 3600,          // 1 hour
+            lineups: 300,                // 5 minutes
+            starter_alerts: 86400,       // 1 day
+            news: 900,                   // 15 minutes
+            videos: 604800,              // 1 week
+            health_probe: 300,           // 5 minutes
+        }
+    }
 }
 
 /// Sports seasons configuration
@@ -117,6 +223,12 @@ pub struct ApiConfig {
     pub rsc_token: String,
     /// Twitter API base URL
     pub twitter_base_url: String,
+    /// Twitter API key (loaded from environment)
+    #[serde(default)]
+    pub twitter_api_key: String,
+    /// Twitter API request budget
+    #[serde(default)]
+    pub twitter_api: TwitterApiConfig,
     /// Request timeout in seconds
     pub request_timeout: u64,
     /// JWT secret for signing tokens (loaded from environment)
@@ -135,6 +247,314 @@ pub struct ApiConfig {
     pub reddit_api: RedditApiConfig,
 }
 
+/// Rating model used to price each simulated game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RatingModel {
+    /// Elo ratings replayed from the season's final scores
+    #[default]
+    Elo,
+    /// Pythagorean expectation from points scored and allowed
+    PointDifferential,
+}
+
+impl RatingModel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RatingModel::Elo => "elo",
+            RatingModel::PointDifferential => "point_differential",
+        }
+    }
+}
+
+/// Monte Carlo playoff odds configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationConfig {
+    /// Number of simulated seasons per run
+    pub iterations: u32,
+    /// Rating model used to price remaining games
+    pub model: RatingModel,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 5000,
+            model: RatingModel::Elo,
+        }
+    }
+}
+
+/// RSS or Atom feed ingested into one team's news
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewsFeedConfig {
+    /// League the team plays in (e.g. "mlb")
+    pub league: String,
+    /// Team abbreviation (e.g. "NYY")
+    pub team: String,
+    /// Feed URL (RSS 2.0 or Atom)
+    pub url: String,
+    /// Source name shown on stories; defaults to the feed title
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Team news feed configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NewsConfig {
+    /// Stories per page when the request doesn't ask for a page size
+    pub page_size: usize,
+    /// Most stories a request can ask for per page
+    pub max_page_size: usize,
+    /// RSS and Atom feeds merged with the provider news
+    #[serde(default)]
+    pub feeds: Vec<NewsFeedConfig>,
+}
+
+impl Default for NewsConfig {
+    fn default() -> Self {
+        Self {
+            page_size: 20,
+            max_page_size: 100,
+            feeds: Vec::new(),
+        }
+    }
+}
+
+/// Twitter search terms for one team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamSocialConfig {
+    /// League the team plays in (e.g. "mlb")
+    pub league: String,
+    /// Team abbreviation (e.g. "NYY")
+    pub team: String,
+    /// Hashtags without the leading `#`
+    #[serde(default)]
+    pub hashtags: Vec<String>,
+    /// Official team accounts without the leading `@`
+    #[serde(default)]
+    pub handles: Vec<String>,
+    /// Beat reporter accounts without the leading `@`
+    #[serde(default)]
+    pub beat_reporters: Vec<String>,
+}
+
+/// Per-game Twitter search configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SocialConfig {
+    /// Minutes before the scheduled start included in a game's search window
+    pub window_before_minutes: i64,
+    /// Minutes after the scheduled start included in a game's search window
+    pub window_after_minutes: i64,
+    /// Most result pages followed per refresh
+    pub max_pages: u32,
+    #[serde(default)]
+    pub teams: Vec<TeamSocialConfig>,
+}
+
+impl Default for SocialConfig {
+    fn default() -> Self {
+        Self {
+            window_before_minutes: 60,
+            window_after_minutes: 300,
+            max_pages: 5,
+            teams: Vec::new(),
+        }
+    }
+}
+
+/// Access and refresh token lifetimes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// How long an access token is accepted
+    pub access_token_seconds: u64,
+    /// How long a session survives without its refresh token being used
+    pub refresh_token_seconds: u64,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            access_token_seconds: 900,
+            refresh_token_seconds: 2592000,
+        }
+    }
+}
+
+/// API keys and per-client request quotas, counted in Redis per fixed window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Whether quotas are enforced; API keys are checked either way
+    pub enabled: bool,
+    /// Length of a quota window
+    pub window_seconds: u64,
+    /// Requests per window for each IP without an API key
+    pub anonymous_requests: u64,
+    /// Requests per window for each app key
+    pub app_requests: u64,
+    /// Requests per window for each admin key
+    pub admin_requests: u64,
+    /// Take the client IP from the last `X-Forwarded-For` hop; only behind a
+    /// proxy that appends it
+    #[serde(default)]
+    pub trust_forwarded_for: bool,
+    /// Admin key that can issue other keys (loaded from environment)
+    #[serde(default)]
+    pub admin_key: String,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_seconds: 60,
+            anonymous_requests: 60,
+            app_requests: 600,
+            admin_requests: 6000,
+            trust_forwarded_for: false,
+            admin_key: String::new(),
+        }
+    }
+}
+
+/// Upstream call accounting, rolled up per day in Redis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageConfig {
+    /// How often each instance writes its counters to Redis
+    pub flush_seconds: u64,
+    /// How long daily rollups are kept
+    pub retention_days: u64,
+    /// Calls allowed per calendar month (UTC) by upstream: `sports_data`,
+    /// `rolling_insights`, `twitter_api` or `reddit`; missing or 0 means
+    /// no cap
+    #[serde(default)]
+    pub monthly_caps: HashMap<String, u64>,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            flush_seconds: 30,
+            retention_days: 90,
+            monthly_caps: HashMap::new(),
+        }
+    }
+}
+
+/// OpenTelemetry traces, exported over OTLP/HTTP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    /// Whether spans are exported; logs are unaffected
+    pub enabled: bool,
+    /// OTLP/HTTP traces endpoint of the collector
+    pub otlp_endpoint: String,
+    /// `service.name` on exported spans
+    pub service_name: String,
+    /// Fraction of traces to sample, by trace id; a caller's sampled flag
+    /// is ignored
+    pub sample_ratio: f64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            otlp_endpoint: "http://localhost:4318/v1/traces".to_string(),
+            service_name: "arb-rs".to_string(),
+            sample_ratio: 1.0,
+        }
+    }
+}
+
+/// Postgres connection
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// Connection URL (loaded from environment); unset leaves collected social
+    /// posts unstored
+    pub url: String,
+}
+
+/// Social feed moderation
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModerationConfig {
+    /// Strictness for users who haven't picked one
+    #[serde(default)]
+    pub default_strictness: Strictness,
+    /// Words masked at moderate strictness and dropped at strict
+    #[serde(default)]
+    pub blocked_words: Vec<String>,
+    /// Reddit users and Twitter handles whose posts are always dropped
+    #[serde(default)]
+    pub blocked_authors: Vec<String>,
+}
+
+/// Where a video source's items come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoSourceKind {
+    /// RSS 2.0 or Atom feed, such as a team's video feed
+    Feed,
+    /// YouTube channel, read through its public Atom feed
+    Youtube,
+    /// JSON highlight endpoint
+    Provider,
+}
+
+/// A source of video metadata for one league or team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoSourceConfig {
+    /// League the source covers (e.g. "mlb")
+    pub league: String,
+    /// Team abbreviation (e.g. "PHI"); unset for league-wide sources
+    #[serde(default)]
+    pub team: Option<String>,
+    pub kind: VideoSourceKind,
+    /// Feed or endpoint URL; for `youtube`, the channel id
+    pub url: String,
+    /// Source name shown on videos; defaults to the feed title
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Type for videos whose title doesn't say
+    #[serde(default)]
+    pub video_type: VideoType,
+}
+
+/// Video highlights configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoConfig {
+    /// Videos per page when the request doesn't ask for a page size
+    pub page_size: usize,
+    /// Most videos a request can ask for per page
+    pub max_page_size: usize,
+    /// How long a league's catalog is served before its sources are read again
+    pub refresh_seconds: u64,
+    /// Most videos kept in a league's catalog; the oldest are dropped past it
+    pub max_videos: usize,
+    #[serde(default)]
+    pub sources: Vec<VideoSourceConfig>,
+}
+
+impl Default for VideoConfig {
+    fn default() -> Self {
+        Self {
+            page_size: 20,
+            max_page_size: 100,
+            refresh_seconds: 900,
+            max_videos: 500,
+            sources: Vec::new(),
+        }
+    }
+}
+
 /// Google OAuth configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GoogleOAuthConfig {
@@ -185,6 +605,28 @@ pub struct RedditOAuthConfig {
     pub redirect_uri: String,
 }
 
+/// Twitter API request budget, shared by every endpoint that searches Twitter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TwitterApiConfig {
+    /// Sustained requests per minute
+    pub requests_per_minute: u32,
+    /// Requests that can be sent back to back before the rate applies
+    pub burst: u32,
+    /// Longest `Retry-After` honored after a 429, in seconds
+    pub max_retry_after_seconds: u64,
+}
+
+impl Default for TwitterApiConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: 60,
+            burst: 10,
+            max_retry_after_seconds: 900,
+        }
+    }
+}
+
 /// Reddit API configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RedditApiConfig {
@@ -196,6 +638,23 @@ pub struct RedditApiConfig {
     pub default_sort: String,
     /// User agent string for Reddit API requests
     pub user_agent: String,
+    /// Team subreddits searched for game threads
+    #[serde(default)]
+    pub teams: Vec<RedditTeamConfig>,
+}
+
+/// Where a team's game threads are posted and what their titles call it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedditTeamConfig {
+    /// League the team plays in (e.g. "mlb")
+    pub league: String,
+    /// Team abbreviation (e.g. "PHI")
+    pub team: String,
+    /// Subreddit without the leading `r/`
+    pub subreddit: String,
+    /// Names thread titles use for the team (e.g. "Phillies")
+    #[serde(default)]
+    pub names: Vec<String>,
 }
 
 impl Default for ArbConfig {
@@ -244,27 +703,14 @@ impl Default for ArbConfig {
                 port: 3000,
                 cors_origins: "http://localhost:5173,http://localhost:3000,https://444037a67f75.ngrok.app".to_string(),
                 client_url: "https://444037a67f75.ngrok.app".to_string(),
+                return_urls: Vec::new(),
             },
             cache: CacheConfig {
                 enabled: true,
                 mode: CacheMode::TtlBased,
                 redis_url: "redis://localhost:6379".to_string(),
                 default_ttl: 60, // ✅ Match TOML: 1 minute
-                ttl: CacheTtlConfig {
-                    team_profiles: 3600,          // 1 hour
-                    schedule: 3600,               // 1 hour
-                    postseason_schedule: 3600,    // 1 hour
-                    scores: 60,                   // 1 minute
-                    play_by_play: 60,             // 1 minute
-                    box_scores: 60,               // 1 minute
-                    stadiums: 21600,              // 6 hours
-                    standings: 21600,             // 6 hours
-                    twitter_search: 60,           // 1 minute
-                    reddit_thread: 600,           // 10 minutes
-                    reddit_thread_comments: 60,   // 1 minute
-                    odds: 3600,                   // 1 hour
-                    user_auth: 604800,            // 1 week
-                },
+                ttl: CacheTtlConfig::default(),
             },
             seasons: SeasonsConfig { current_seasons },
             api: ApiConfig {
@@ -273,6 +719,8 @@ impl Default for ArbConfig {
                 rolling_insights_base_url: "http://rest.datafeeds.rolling-insights.com/api/v1/".to_string(),
                 rsc_token: "".to_string(),
                 twitter_base_url: "https://api.twitterapi.io".to_string(),
+                twitter_api_key: "".to_string(),
+                twitter_api: TwitterApiConfig::default(),
                 request_timeout: 30,
                 jwt_secret: "".to_string(),
                 google_oauth: GoogleOAuthConfig {
@@ -298,8 +746,19 @@ impl Default for ArbConfig {
                     max_comment_limit: 100,
                     default_sort: "new".to_string(),
                     user_agent: "arbitration-app/1.0 by u/arbitration_dev".to_string(),
+                    teams: Vec::new(),
                 },
             },
+            simulation: SimulationConfig::default(),
+            news: NewsConfig::default(),
+            social: SocialConfig::default(),
+            moderation: ModerationConfig::default(),
+            videos: VideoConfig::default(),
+            auth: AuthConfig::default(),
+            rate_limit: RateLimitConfig::default(),
+            usage: UsageConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            database: DatabaseConfig::default(),
         }
     }
 }
@@ -435,6 +894,22 @@ impl ArbConfig {
             config.api.sportsdata_api_key = api_key;
         }
 
+        if let Ok(admin_key) = std::env::var("ARB_ADMIN_API_KEY") {
+            config.rate_limit.admin_key = admin_key;
+        }
+
+        if let Ok(url) = std::env::var("DATABASE_URL") {
+            config.database.url = url;
+        }
+
+        if let Ok(endpoint) = std::env::var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT") {
+            config.telemetry.otlp_endpoint = endpoint;
+        }
+
+        if let Ok(api_key) = std::env::var("TWITTERAPIIO_API_KEY") {
+            config.api.twitter_api_key = api_key;
+        }
+
         if let Ok(rsc_token) = std::env::var("RSC_TOKEN") {
             config.api.rsc_token = rsc_token;
         }
@@ -469,6 +944,77 @@ impl ArbConfig {
         }
     }
 
+    /// Settings that parse but can't work, as readable messages
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if url::Url::parse(&self.server.client_url).is_err() {
+            problems.push(format!(
+                "server.client_url \"{}\" is not a URL",
+                self.server.client_url
+            ));
+        }
+        if self.api.request_timeout == 0 {
+            problems.push("api.request_timeout must be positive".to_string());
+        }
+        if self.rate_limit.window_seconds == 0 {
+            problems.push("rate_limit.window_seconds must be positive".to_string());
+        }
+        if self.usage.retention_days == 0 {
+            problems.push("usage.retention_days must be positive".to_string());
+        }
+        for upstream in self.usage.monthly_caps.keys() {
+            if !Upstream::ALL.iter().any(|known| known.as_str() == upstream) {
+                problems.push(format!(
+                    "usage.monthly_caps has unknown upstream \"{}\"",
+                    upstream
+                ));
+            }
+        }
+        if !self.database.url.is_empty()
+            && self.database.url.parse::<tokio_postgres::Config>().is_err()
+        {
+            problems.push("database.url is not a Postgres connection URL".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.telemetry.sample_ratio) {
+            problems.push("telemetry.sample_ratio must be between 0 and 1".to_string());
+        }
+        let mut sports: Vec<_> = self.seasons.current_seasons.iter().collect();
+        sports.sort_by_key(|(sport, _)| *sport);
+        for (sport, season) in sports {
+            if chrono::NaiveDate::parse_from_str(&season.postseason_start, "%m-%d-%Y")
+                .is_err()
+            {
+                problems.push(format!(
+                    "seasons.{}.postseason_start \"{}\" is not MM-DD-YYYY",
+                    sport, season.postseason_start
+                ));
+            }
+        }
+        problems
+    }
+
+    /// Environment variables for upstream and sign-in credentials that
+    /// aren't set
+    pub fn missing_credentials(&self) -> Vec<&'static str> {
+        [
+            ("SPORTSDATAIO_API_KEY", &self.api.sportsdata_api_key),
+            ("RSC_TOKEN", &self.api.rsc_token),
+            ("TWITTERAPIIO_API_KEY", &self.api.twitter_api_key),
+            ("REDDIT_CLIENT_ID", &self.api.reddit_oauth.client_id),
+            ("REDDIT_CLIENT_SECRET", &self.api.reddit_oauth.client_secret),
+            ("JWT_SECRET", &self.api.jwt_secret),
+            ("GOOGLE_CLIENT_ID", &self.api.google_oauth.client_id),
+            ("GOOGLE_CLIENT_SECRET", &self.api.google_oauth.client_secret),
+            ("APPLE_CLIENT_ID", &self.api.apple_oauth.client_id),
+            ("APPLE_TEAM_ID", &self.api.apple_oauth.team_id),
+            ("APPLE_KEY_ID", &self.api.apple_oauth.key_id),
+        ]
+        .into_iter()
+        .filter(|(_, value)| value.is_empty())
+        .map(|(name, _)| name)
+        .collect()
+    }
+
     /// Get season information for a specific sport
     pub fn get_season_info(&self, sport: &str) -> Option<&SeasonInfo> {
         self.seasons.current_seasons.get(sport)
@@ -516,16 +1062,29 @@ impl fmt::Display for ArbConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "ArbConfig {{ server: {}, cache: {}, seasons: {}, api: {} }}",
-            self.server, self.cache, self.seasons, self.api
+            "ArbConfig {{ server: {}, cache: {}, seasons: {}, api: {}, simulation: {}, news: {}, social: {}, moderation: {}, videos: {}, auth: {}, rate_limit: {}, usage: {}, telemetry: {}, database: {} }}",
+            self.server,
+            self.cache,
+            self.seasons,
+            self.api,
+            self.simulation,
+            self.news,
+            self.social,
+            self.moderation,
+            self.videos,
+            self.auth,
+            self.rate_limit,
+            self.usage,
+            self.telemetry,
+            self.database
         )
     }
 }
 
 impl fmt::Display for ServerConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "ServerConfig {{ host: \"{}\", port: {}, cors_origins: \"{}\", client_url: \"{}\" }}", 
-               self.host, self.port, self.cors_origins, self.client_url)
+        write!(f, "ServerConfig {{ host: \"{}\", port: {}, cors_origins: \"{}\", client_url: \"{}\", return_urls: {:?} }}", 
+               self.host, self.port, self.cors_origins, self.client_url, self.return_urls)
     }
 }
 
@@ -546,14 +1105,136 @@ impl fmt::Display for SeasonsConfig {
     }
 }
 
+impl fmt::Display for SimulationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SimulationConfig {{ iterations: {}, model: \"{}\" }}",
+            self.iterations,
+            self.model.as_str()
+        )
+    }
+}
+
+impl fmt::Display for NewsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "NewsConfig {{ page_size: {}, max_page_size: {}, feeds: {} }}",
+            self.page_size,
+            self.max_page_size,
+            self.feeds.len()
+        )
+    }
+}
+
+impl fmt::Display for AuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AuthConfig {{ access_token_seconds: {}, refresh_token_seconds: {} }}",
+            self.access_token_seconds, self.refresh_token_seconds
+        )
+    }
+}
+
+impl fmt::Display for RateLimitConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RateLimitConfig {{ enabled: {}, window_seconds: {}, anonymous_requests: {}, app_requests: {}, admin_requests: {}, trust_forwarded_for: {}, admin_key: \"{}\" }}",
+            self.enabled,
+            self.window_seconds,
+            self.anonymous_requests,
+            self.app_requests,
+            self.admin_requests,
+            self.trust_forwarded_for,
+            mask_secret(&self.admin_key)
+        )
+    }
+}
+
+impl fmt::Display for UsageConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut caps: Vec<_> = self.monthly_caps.iter().collect();
+        caps.sort();
+        write!(
+            f,
+            "UsageConfig {{ flush_seconds: {}, retention_days: {}, monthly_caps: {:?} }}",
+            self.flush_seconds, self.retention_days, caps
+        )
+    }
+}
+
+impl fmt::Display for TelemetryConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TelemetryConfig {{ enabled: {}, otlp_endpoint: \"{}\", service_name: \"{}\", sample_ratio: {} }}",
+            self.enabled, self.otlp_endpoint, self.service_name, self.sample_ratio
+        )
+    }
+}
+
+impl fmt::Display for DatabaseConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DatabaseConfig {{ url: \"{}\" }}",
+            mask_secret(&self.url)
+        )
+    }
+}
+
+impl fmt::Display for ModerationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ModerationConfig {{ default_strictness: {}, blocked_words: {}, blocked_authors: {} }}",
+            self.default_strictness,
+            self.blocked_words.len(),
+            self.blocked_authors.len()
+        )
+    }
+}
+
+impl fmt::Display for VideoConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "VideoConfig {{ page_size: {}, max_page_size: {}, refresh_seconds: {}, max_videos: {}, sources: {} }}",
+            self.page_size,
+            self.max_page_size,
+            self.refresh_seconds,
+            self.max_videos,
+            self.sources.len()
+        )
+    }
+}
+
+impl fmt::Display for SocialConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SocialConfig {{ window_before_minutes: {}, window_after_minutes: {}, max_pages: {}, teams: {} }}",
+            self.window_before_minutes,
+            self.window_after_minutes,
+            self.max_pages,
+            self.teams.len()
+        )
+    }
+}
+
 impl fmt::Display for ApiConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "ApiConfig {{ sportsdata_base_url: \"{}\", sportsdata_api_key: \"{}\", rolling_insights_base_url: \"{}\", rsc_token: \"{}\", twitter_base_url: \"{}\", request_timeout: {}, jwt_secret: \"{}\", google_oauth: {}, apple_oauth: {}, reddit_oauth: {} }}", 
+        write!(f, "ApiConfig {{ sportsdata_base_url: \"{}\", sportsdata_api_key: \"{}\", rolling_insights_base_url: \"{}\", rsc_token: \"{}\", twitter_base_url: \"{}\", twitter_api_key: \"{}\", twitter_api: {:?}, request_timeout: {}, jwt_secret: \"{}\", google_oauth: {}, apple_oauth: {}, reddit_oauth: {} }}", 
                self.sportsdata_base_url,
                mask_secret(&self.sportsdata_api_key),
                self.rolling_insights_base_url,
                mask_secret(&self.rsc_token),
                self.twitter_base_url,
+               mask_secret(&self.twitter_api_key),
+               self.twitter_api,
                self.request_timeout,
                mask_secret(&self.jwt_secret),
                self.google_oauth,
@@ -582,3 +1263,59 @@ impl fmt::Display for RedditOAuthConfig {
                mask_secret(&self.client_id), mask_secret(&self.client_secret), self.redirect_uri)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_from_before_new_sections_still_loads() {
+        let config: ArbConfig = toml::from_str(
+            r#"
+            [server]
+            host = "0.0.0.0"
+            port = 3000
+            cors_origins = "http://localhost:5173"
+            client_url = "http://localhost:5173"
+
+            [cache]
+            enabled = true
+            mode = "TtlBased"
+            redis_url = "redis://localhost:6379"
+            default_ttl = 60
+
+            [cache.ttl]
+            scores = 30
+            user_auth = 604800
+
+            [seasons.current_seasons.mlb]
+            regular = "2025"
+            postseason = "2025POST"
+            postseason_start = "10-01-2025"
+
+            [api]
+            sportsdata_base_url = "https://api.sportsdata.io/v3"
+            rolling_insights_base_url = "http://rest.datafeeds.rolling-insights.com/api/v1/"
+            twitter_base_url = "https://api.twitterapi.io"
+            request_timeout = 30
+
+            [api.reddit_api]
+            default_comment_limit = 20
+            max_comment_limit = 100
+            default_sort = "new"
+            user_agent = "arbitration-app/1.0"
+
+            [news]
+            page_size = 10
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.cache.ttl.scores, 30);
+        assert_eq!(config.cache.ttl.oauth_state, 600);
+        assert_eq!(config.cache.ttl.health_probe, 300);
+        assert_eq!(config.news.page_size, 10);
+        assert!(config.news.feeds.is_empty());
+        assert_eq!(config.rate_limit.window_seconds, 60);
+    }
+}
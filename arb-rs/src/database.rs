@@ -0,0 +1,39 @@
+use deadpool_postgres::{Config, Object, Pool, Runtime};
+use tokio_postgres::NoTls;
+
+use crate::config::DatabaseConfig;
+use crate::error::Result;
+
+/// Postgres pool for the tables in etc/schema
+///
+/// Connections are opened on first use and replaced when they drop, so a
+/// database that's down at startup only fails the requests that need it.
+#[derive(Clone)]
+pub struct Database {
+    pool: Pool,
+}
+
+impl Database {
+    /// A pool for `config.url`, or `None` when no database is configured
+    pub fn new(config: &DatabaseConfig) -> Result<Option<Self>> {
+        if config.url.is_empty() {
+            return Ok(None);
+        }
+
+        let pool = Config {
+            url: Some(config.url.clone()),
+            ..Default::default()
+        }
+        .create_pool(Some(Runtime::Tokio1), NoTls)?;
+        Ok(Some(Self { pool }))
+    }
+
+    pub async fn client(&self) -> Result<Object> {
+        Ok(self.pool.get().await?)
+    }
+
+    pub async fn ping(&self) -> Result<()> {
+        self.client().await?.simple_query("SELECT 1").await?;
+        Ok(())
+    }
+}
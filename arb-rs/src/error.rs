@@ -28,6 +28,21 @@ pub enum Error {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("Metrics error: {0}")]
+    Metrics(#[from] prometheus::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] tokio_postgres::Error),
+
+    #[error("Database pool error: {0}")]
+    DatabasePool(#[from] deadpool_postgres::PoolError),
+
+    #[error("Database config error: {0}")]
+    DatabaseConfig(#[from] deadpool_postgres::CreatePoolError),
+
+    #[error("Telemetry error: {0}")]
+    Telemetry(#[from] opentelemetry_otlp::ExporterBuildError),
+
     #[error("Invalid league: {0}")]
     InvalidLeague(String),
 
@@ -1,9 +1,12 @@
 pub mod cache;
 pub mod config;
+pub mod database;
 pub mod error;
+pub mod metrics;
 pub mod path;
 pub mod schema;
 pub mod server;
 pub mod services;
+pub mod telemetry;
 pub mod uses;
 pub mod zero_copy;
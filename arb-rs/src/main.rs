@@ -1,19 +1,25 @@
 use async_std::sync::{Arc, Mutex};
 use clap::Parser;
+use opentelemetry::trace::TracerProvider as _;
 use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 pub mod cache;
 pub mod config;
+pub mod database;
 pub mod error;
+pub mod metrics;
 pub mod path;
 pub mod schema;
 pub mod server;
 pub mod services;
+pub mod telemetry;
 pub mod uses;
 pub mod zero_copy;
 
-use crate::{cache::Cache, config::ArbConfig, error::Result, server::Server};
+use crate::{
+    cache::Cache, config::ArbConfig, error::Result, metrics::Metrics, server::Server,
+};
 
 /// Arbitration API Server - A proxy server for sports data with Redis caching
 #[derive(Parser, Debug)]
@@ -24,32 +30,53 @@ struct Args {
     config: String,
 }
 
+fn env_filter() -> EnvFilter {
+    EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "arb=debug,tower_http=debug".into())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
     dotenv::dotenv().ok();
 
+    // Log to stdout while loading the config, which says where spans go
+    let config = tracing::subscriber::with_default(
+        tracing_subscriber::registry()
+            .with(env_filter())
+            .with(tracing_subscriber::fmt::layer()),
+        || {
+            info!("Starting arbitration proxy server...");
+            ArbConfig::from_file(&args.config)
+        },
+    );
+
+    let tracer_provider = telemetry::tracer_provider(&config.telemetry)?;
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "arb=debug,tower_http=debug".into()),
-        )
+        .with(env_filter())
         .with(tracing_subscriber::fmt::layer())
+        .with(tracer_provider.as_ref().map(|provider| {
+            tracing_opentelemetry::layer()
+                .with_tracer(provider.tracer(config.telemetry.service_name.clone()))
+        }))
         .init();
 
-    info!("Starting arbitration proxy server...");
-
-    let config = ArbConfig::from_file(&args.config);
-
-    let cache = Arc::new(Mutex::new(Cache::new(config.cache.clone()).await?));
+    let metrics = Metrics::new()?;
+    let cache = Cache::new(config.cache.clone(), metrics.clone()).await?;
+    let store = Arc::new(Mutex::new(cache.store()));
+    let cache = Arc::new(Mutex::new(cache));
 
     info!("Server config: {}", config);
 
-    let server = Server::new(cache, config);
-    server
-        .run()
-        .await
-        .map_err(|e| crate::error::Error::Server(e.to_string()))?;
+    let server = Server::new(cache, store, config, metrics);
+    let served = server.run().await;
+
+    // Runs after a graceful shutdown too, so buffered spans aren't lost
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            tracing::warn!("Failed to flush spans on shutdown: {}", e);
+        }
+    }
 
-    Ok(())
+    served.map_err(|e| crate::error::Error::Server(e.to_string()))
 }
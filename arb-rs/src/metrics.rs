@@ -0,0 +1,203 @@
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts,
+    Registry, TextEncoder,
+};
+use std::time::Duration;
+
+use crate::error::Result;
+
+/// Buckets for Redis commands and lock waits, which should be sub-millisecond
+const FAST_BUCKETS: &[f64] = &[
+    0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0,
+];
+
+/// Prometheus metrics for requests, upstream calls and the Redis cache,
+/// exported at `/metrics`
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_duration: HistogramVec,
+    http_in_flight: IntGauge,
+    upstream_requests: IntCounterVec,
+    upstream_duration: HistogramVec,
+    cache_lookups: IntCounterVec,
+    redis_duration: HistogramVec,
+    cache_lock_wait: Histogram,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new_custom(Some("arb".to_string()), None)?;
+
+        let http_requests = IntCounterVec::new(
+            Opts::new(
+                "http_requests_total",
+                "Requests served, by route and status",
+            ),
+            &["method", "route", "status"],
+        )?;
+        let http_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Time to serve a request, by route",
+            ),
+            &["method", "route"],
+        )?;
+        let http_in_flight =
+            IntGauge::new("http_requests_in_flight", "Requests being served right now")?;
+        let upstream_requests = IntCounterVec::new(
+            Opts::new(
+                "upstream_requests_total",
+                "Calls to upstream APIs, by provider and HTTP status",
+            ),
+            &["provider", "status"],
+        )?;
+        let upstream_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "upstream_request_duration_seconds",
+                "Time until an upstream API responds, by provider",
+            ),
+            &["provider"],
+        )?;
+        let cache_lookups = IntCounterVec::new(
+            Opts::new(
+                "cache_lookups_total",
+                "Cache reads, by data type and hit or miss",
+            ),
+            &["data_type", "result"],
+        )?;
+        let redis_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "redis_command_duration_seconds",
+                "Time for a Redis command to complete, by command",
+            )
+            .buckets(FAST_BUCKETS.to_vec()),
+            &["command"],
+        )?;
+        let cache_lock_wait = Histogram::with_opts(
+            HistogramOpts::new(
+                "cache_lock_wait_seconds",
+                "Time spent waiting for the shared cache connection",
+            )
+            .buckets(FAST_BUCKETS.to_vec()),
+        )?;
+
+        registry.register(Box::new(http_requests.clone()))?;
+        registry.register(Box::new(http_duration.clone()))?;
+        registry.register(Box::new(http_in_flight.clone()))?;
+        registry.register(Box::new(upstream_requests.clone()))?;
+        registry.register(Box::new(upstream_duration.clone()))?;
+        registry.register(Box::new(cache_lookups.clone()))?;
+        registry.register(Box::new(redis_duration.clone()))?;
+        registry.register(Box::new(cache_lock_wait.clone()))?;
+
+        Ok(Self {
+            registry,
+            http_requests,
+            http_duration,
+            http_in_flight,
+            upstream_requests,
+            upstream_duration,
+            cache_lookups,
+            redis_duration,
+            cache_lock_wait,
+        })
+    }
+
+    /// Everything gathered, in the Prometheus text format
+    pub fn render(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+
+    /// Count a request as in flight until the returned guard is dropped,
+    /// which also covers handlers that panic or are cancelled
+    pub fn start_request(&self) -> InFlight {
+        self.http_in_flight.inc();
+        InFlight(self.http_in_flight.clone())
+    }
+
+    pub fn observe_request(
+        &self,
+        method: &str,
+        route: &str,
+        status: u16,
+        elapsed: Duration,
+    ) {
+        self.http_requests
+            .with_label_values(&[method, route, &status.to_string()])
+            .inc();
+        self.http_duration
+            .with_label_values(&[method, route])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// `status` is the HTTP status, or "error" when no response came back
+    pub fn observe_upstream(&self, provider: &str, status: &str, elapsed: Duration) {
+        self.upstream_requests
+            .with_label_values(&[provider, status])
+            .inc();
+        self.upstream_duration
+            .with_label_values(&[provider])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn observe_cache_lookup(&self, data_type: &str, hit: bool) {
+        let result = if hit { "hit" } else { "miss" };
+        self.cache_lookups
+            .with_label_values(&[data_type, result])
+            .inc();
+    }
+
+    pub fn observe_redis(&self, command: &str, elapsed: Duration) {
+        self.redis_duration
+            .with_label_values(&[command])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn observe_cache_lock_wait(&self, elapsed: Duration) {
+        self.cache_lock_wait.observe(elapsed.as_secs_f64());
+    }
+}
+
+/// A request counted in `http_requests_in_flight`; see [`Metrics::start_request`]
+#[must_use = "the request stops counting as in flight when this is dropped"]
+pub struct InFlight(IntGauge);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_exposes_observations() {
+        let metrics = Metrics::new().unwrap();
+        let in_flight = metrics.start_request();
+        assert!(metrics
+            .render()
+            .unwrap()
+            .contains("arb_http_requests_in_flight 1"));
+        metrics.observe_request("GET", "/api/v1/scores", 200, Duration::from_millis(12));
+        drop(in_flight);
+        metrics.observe_upstream("sports_data", "429", Duration::from_millis(300));
+        metrics.observe_cache_lookup("scores", true);
+
+        let text = metrics.render().unwrap();
+        assert!(text.contains(
+            r#"arb_http_requests_total{method="GET",route="/api/v1/scores",status="200"} 1"#
+        ));
+        assert!(text.contains("arb_http_requests_in_flight 0"));
+        assert!(text.contains(
+            r#"arb_upstream_requests_total{provider="sports_data",status="429"} 1"#
+        ));
+        assert!(text
+            .contains(r#"arb_cache_lookups_total{data_type="scores",result="hit"} 1"#));
+    }
+}
@@ -103,12 +103,77 @@ pub struct StadiumsPath {
     league: League,
 }
 
+#[derive(Debug, Clone)]
+pub struct GamesInProgressPath {
+    league: League,
+}
+
 #[derive(Debug, Clone)]
 pub struct StandingsPath {
     league: League,
     season: i32,
 }
 
+#[derive(Debug, Clone)]
+pub struct PlayerSeasonStatsPath {
+    league: League,
+    season: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TeamSeasonStatsPath {
+    league: League,
+    season: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayerPath {
+    league: League,
+    player_id: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayerGameLogPath {
+    league: League,
+    season: String,
+    player_id: i32,
+    games: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayerSeasonStatsByPlayerPath {
+    league: League,
+    season: String,
+    player_id: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct InjuredPlayersPath {
+    league: League,
+}
+
+#[derive(Debug, Clone)]
+pub struct DepthChartsPath {
+    league: League,
+}
+
+#[derive(Debug, Clone)]
+pub struct StartingLineupsPath {
+    league: League,
+    date: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewsPath {
+    league: League,
+}
+
+#[derive(Debug, Clone)]
+pub struct TeamPlayersPath {
+    league: League,
+    team: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct OddsByDatePath {
     league: League,
@@ -519,6 +584,17 @@ impl std::fmt::Display for StadiumsPath {
     }
 }
 
+impl std::fmt::Display for GamesInProgressPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/scores/json/AreAnyGamesInProgress",
+            BASE_URL,
+            self.league.to_string().to_lowercase()
+        )
+    }
+}
+
 impl std::fmt::Display for StandingsPath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -531,6 +607,143 @@ impl std::fmt::Display for StandingsPath {
     }
 }
 
+impl std::fmt::Display for PlayerSeasonStatsPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/stats/json/PlayerSeasonStats/{}",
+            BASE_URL,
+            self.league.to_string().to_lowercase(),
+            self.season
+        )
+    }
+}
+
+impl std::fmt::Display for TeamSeasonStatsPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/scores/json/TeamSeasonStats/{}",
+            BASE_URL,
+            self.league.to_string().to_lowercase(),
+            self.season
+        )
+    }
+}
+
+impl std::fmt::Display for PlayerPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/scores/json/Player/{}",
+            BASE_URL,
+            self.league.to_string().to_lowercase(),
+            self.player_id
+        )
+    }
+}
+
+impl std::fmt::Display for PlayerGameLogPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.games {
+            Some(games) => write!(
+                f,
+                "{}/{}/stats/json/PlayerGameStatsBySeason/{}/{}/{}",
+                BASE_URL,
+                self.league.to_string().to_lowercase(),
+                self.season,
+                self.player_id,
+                games
+            ),
+            None => write!(
+                f,
+                "{}/{}/stats/json/PlayerGameStatsBySeason/{}/{}/all",
+                BASE_URL,
+                self.league.to_string().to_lowercase(),
+                self.season,
+                self.player_id
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for PlayerSeasonStatsByPlayerPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.league {
+            League::Nfl => write!(
+                f,
+                "{}/nfl/stats/json/PlayerSeasonStatsByPlayerID/{}/{}",
+                BASE_URL, self.season, self.player_id
+            ),
+            _ => write!(
+                f,
+                "{}/{}/stats/json/PlayerSeasonStatsByPlayer/{}/{}",
+                BASE_URL,
+                self.league.to_string().to_lowercase(),
+                self.season,
+                self.player_id
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for InjuredPlayersPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/projections/json/InjuredPlayers",
+            BASE_URL,
+            self.league.to_string().to_lowercase()
+        )
+    }
+}
+
+impl std::fmt::Display for DepthChartsPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/scores/json/DepthCharts",
+            BASE_URL,
+            self.league.to_string().to_lowercase()
+        )
+    }
+}
+
+impl std::fmt::Display for StartingLineupsPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/projections/json/StartingLineupsByDate/{}",
+            BASE_URL,
+            self.league.to_string().to_lowercase(),
+            self.date
+        )
+    }
+}
+
+impl std::fmt::Display for NewsPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/scores/json/News",
+            BASE_URL,
+            self.league.to_string().to_lowercase()
+        )
+    }
+}
+
+impl std::fmt::Display for TeamPlayersPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/scores/json/Players/{}",
+            BASE_URL,
+            self.league.to_string().to_lowercase(),
+            self.team
+        )
+    }
+}
+
 impl std::fmt::Display for OddsByDatePath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.date {
@@ -640,10 +853,72 @@ pub fn stadiums_path(league: League) -> StadiumsPath {
     StadiumsPath { league }
 }
 
+pub fn games_in_progress_path(league: League) -> GamesInProgressPath {
+    GamesInProgressPath { league }
+}
+
 pub fn standings_path(league: League, season: i32) -> StandingsPath {
     StandingsPath { league, season }
 }
 
+pub fn player_season_stats_path(league: League, season: String) -> PlayerSeasonStatsPath {
+    PlayerSeasonStatsPath { league, season }
+}
+
+pub fn team_season_stats_path(league: League, season: String) -> TeamSeasonStatsPath {
+    TeamSeasonStatsPath { league, season }
+}
+
+pub fn player_path(league: League, player_id: i32) -> PlayerPath {
+    PlayerPath { league, player_id }
+}
+
+pub fn player_game_log_path(
+    league: League,
+    season: String,
+    player_id: i32,
+    games: Option<u32>,
+) -> PlayerGameLogPath {
+    PlayerGameLogPath {
+        league,
+        season,
+        player_id,
+        games,
+    }
+}
+
+pub fn player_season_stats_by_player_path(
+    league: League,
+    season: String,
+    player_id: i32,
+) -> PlayerSeasonStatsByPlayerPath {
+    PlayerSeasonStatsByPlayerPath {
+        league,
+        season,
+        player_id,
+    }
+}
+
+pub fn injured_players_path(league: League) -> InjuredPlayersPath {
+    InjuredPlayersPath { league }
+}
+
+pub fn depth_charts_path(league: League) -> DepthChartsPath {
+    DepthChartsPath { league }
+}
+
+pub fn starting_lineups_path(league: League, date: String) -> StartingLineupsPath {
+    StartingLineupsPath { league, date }
+}
+
+pub fn news_path(league: League) -> NewsPath {
+    NewsPath { league }
+}
+
+pub fn team_players_path(league: League, team: String) -> TeamPlayersPath {
+    TeamPlayersPath { league, team }
+}
+
 pub fn odds_by_date_path(league: League, date: Option<String>) -> OddsByDatePath {
     OddsByDatePath { league, date }
 }
@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
 pub struct RedditListing {
@@ -37,33 +38,194 @@ pub struct RedditPost {
     pub over_18: Option<bool>,
     pub is_video: Option<bool>,
     pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub link_flair_text: Option<String>,
 }
 
-/// Find a live game thread from a Reddit listing
-pub fn find_live_game_thread(listing: &RedditListing) -> Option<&RedditPost> {
-    let game_thread_identifiers = [
-        "game thread",
-        "game chat",
-        "gdt:",
-        "gdt ",
-        "game day thread",
-        "live thread",
-        "game discussion",
-    ];
+const GAME_THREAD_IDENTIFIERS: [&str; 7] = [
+    "game thread",
+    "game chat",
+    "gdt:",
+    "gdt ",
+    "game day thread",
+    "live thread",
+    "game discussion",
+];
 
-    let exclude_identifiers = ["post game", "pre game", "post-game", "pre-game"];
+const EXCLUDE_IDENTIFIERS: [&str; 6] = [
+    "post game",
+    "pre game",
+    "post-game",
+    "pre-game",
+    "postgame",
+    "pregame",
+];
 
-    listing.data.children.iter().map(|c| &c.data).find(|post| {
-        let title = post.title.to_lowercase();
+/// Hours before the scheduled start a game thread may be posted
+const POSTED_BEFORE_HOURS: i64 = 12;
+/// Hours after the scheduled start a game thread may still be posted
+const POSTED_AFTER_HOURS: i64 = 3;
 
-        let is_game_thread = game_thread_identifiers
+/// Whether a post's title or flair marks it as a live game thread
+fn is_game_thread(post: &RedditPost) -> bool {
+    let title = post.title.to_lowercase();
+    let flair = post
+        .link_flair_text
+        .as_deref()
+        .unwrap_or_default()
+        .to_lowercase();
+    let has = |text: &str, identifiers: &[&str]| {
+        identifiers
             .iter()
-            .any(|identifier| title.contains(identifier));
+            .any(|identifier| text.contains(identifier))
+    };
 
-        let is_excluded = exclude_identifiers
-            .iter()
-            .any(|identifier| title.contains(identifier));
+    (has(&title, &GAME_THREAD_IDENTIFIERS) || has(&flair, &GAME_THREAD_IDENTIFIERS))
+        && !has(&title, &EXCLUDE_IDENTIFIERS)
+        && !has(&flair, &EXCLUDE_IDENTIFIERS)
+}
+
+/// Lowercase alphanumeric words, so "Phillies" doesn't match "Phil" and
+/// "D-backs" matches "d backs"
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
 
-        is_game_thread && !is_excluded
+fn mentions(title: &[String], names: &[String]) -> bool {
+    names.iter().map(|name| words(name)).any(|name| {
+        !name.is_empty() && title.windows(name.len()).any(|window| window == name)
     })
 }
+
+/// The game a thread is searched for
+#[derive(Debug, Clone)]
+pub struct GameThreadTarget {
+    /// Names a title may use for the home team, abbreviation included
+    pub home_names: Vec<String>,
+    /// Names a title may use for the away team, abbreviation included
+    pub away_names: Vec<String>,
+    pub start: DateTime<Utc>,
+}
+
+/// Find the game thread for one game in a Reddit listing
+///
+/// Candidates are game threads (by title or flair) posted around the scheduled
+/// start that name at least one of the teams. Threads naming both teams win,
+/// then the one posted closest to the start, which separates the two games of
+/// a doubleheader.
+pub fn find_game_thread<'a>(
+    listing: &'a RedditListing,
+    target: &GameThreadTarget,
+) -> Option<&'a RedditPost> {
+    let earliest = target.start - Duration::hours(POSTED_BEFORE_HOURS);
+    let latest = target.start + Duration::hours(POSTED_AFTER_HOURS);
+
+    listing
+        .data
+        .children
+        .iter()
+        .map(|c| &c.data)
+        .filter(|post| is_game_thread(post))
+        .filter_map(|post| {
+            let posted = DateTime::from_timestamp(post.created_utc as i64, 0)?;
+            if posted < earliest || posted > latest {
+                return None;
+            }
+            let title = words(&post.title);
+            let teams = mentions(&title, &target.home_names) as u8
+                + mentions(&title, &target.away_names) as u8;
+            let distance = (posted - target.start).num_seconds().abs();
+            (teams > 0).then_some((post, teams, distance))
+        })
+        .min_by_key(|&(_, teams, distance)| (std::cmp::Reverse(teams), distance))
+        .map(|(post, _, _)| post)
+}
+
+/// A game thread matched to a game, cached by game id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameThread {
+    pub league: String,
+    pub game_id: String,
+    pub subreddit: String,
+    pub thread_id: String,
+    pub title: String,
+    pub permalink: String,
+    pub created_utc: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(posts: &[(&str, &str, Option<&str>, i64)]) -> RedditListing {
+        let children: Vec<serde_json::Value> = posts
+            .iter()
+            .map(|(id, title, flair, created)| {
+                serde_json::json!({"kind": "t3", "data": {
+                    "id": id, "title": title, "selftext": "", "url": "",
+                    "permalink": format!("/r/phillies/comments/{}/", id),
+                    "author": "PhilliesBot", "subreddit": "phillies",
+                    "created_utc": *created as f64, "link_flair_text": flair
+                }})
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "kind": "Listing",
+            "data": {"dist": children.len(), "after": null, "children": children}
+        }))
+        .unwrap()
+    }
+
+    fn target(start: &str) -> GameThreadTarget {
+        GameThreadTarget {
+            home_names: vec!["PHI".to_string(), "Phillies".to_string()],
+            away_names: vec!["NYM".to_string(), "Mets".to_string()],
+            start: start.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_doubleheader_threads_match_by_start() {
+        // Game 1 at 17:05 UTC, game 2 at 23:05 UTC
+        let listing = listing(&[
+            (
+                "post",
+                "Postgame Thread: Phillies 5, Mets 2",
+                None,
+                1751662800,
+            ),
+            (
+                "g2",
+                "Game Thread: Mets @ Phillies (Game 2)",
+                None,
+                1751666400,
+            ),
+            (
+                "g1",
+                "Mets @ Phillies - 7/4",
+                Some("Game Thread"),
+                1751644800,
+            ),
+            ("old", "Game Thread: Mets @ Phillies", None, 1751558400),
+        ]);
+
+        let game_1 = find_game_thread(&listing, &target("2025-07-04T17:05:00Z"));
+        assert_eq!(game_1.map(|post| post.id.as_str()), Some("g1"));
+        let game_2 = find_game_thread(&listing, &target("2025-07-04T23:05:00Z"));
+        assert_eq!(game_2.map(|post| post.id.as_str()), Some("g2"));
+    }
+
+    #[test]
+    fn test_thread_naming_both_teams_wins() {
+        let listing = listing(&[
+            ("other", "Game Thread: Braves @ Mets", None, 1751666400),
+            ("ours", "Game Thread: Mets @ Phillies", None, 1751662800),
+        ]);
+
+        let thread = find_game_thread(&listing, &target("2025-07-04T23:05:00Z"));
+        assert_eq!(thread.map(|post| post.id.as_str()), Some("ours"));
+    }
+}
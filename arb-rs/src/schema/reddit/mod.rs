@@ -40,6 +40,12 @@ pub struct RedditPostData {
     pub downs: Option<i64>,
     pub body: Option<String>,
     pub replies: Option<RedditReplies>, // nested comment threads
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub children: Option<Vec<String>>, // comment ids behind a "more" stub
+    #[serde(default)]
+    pub over_18: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,6 +82,8 @@ pub struct RedditPost {
     pub subreddit: String,
     pub url: Option<String>,
     pub is_self: bool,
+    #[serde(default)]
+    pub over_18: bool,
     pub team: Option<String>, // 'away' or 'home' for game threads
     pub comments: Vec<RedditComment>,
 }
@@ -91,6 +99,7 @@ pub struct RedditSearchResponse {
 
 #[derive(Debug, Deserialize)]
 pub struct RedditSearchQuery {
+    pub league: Option<String>,
     pub subreddit: Option<String>,
     pub game_id: Option<String>,
     pub kind: Option<String>,
@@ -112,6 +121,7 @@ impl RedditPostData {
             subreddit: self.subreddit.clone().unwrap_or_default(),
             url: None, // Will be set if needed
             is_self: self.selftext.is_some(),
+            over_18: self.over_18.unwrap_or(false),
             team,
             comments: vec![], // Will be populated separately
         }
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// One player slot on a team depth chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepthChartEntry {
+    #[serde(rename = "DepthChartID", default)]
+    pub depth_chart_id: Option<i32>,
+
+    #[serde(rename = "TeamID", default)]
+    pub team_id: Option<i32>,
+
+    #[serde(rename = "PlayerID")]
+    pub player_id: i32,
+
+    #[serde(rename = "Name", default)]
+    pub name: Option<String>,
+
+    #[serde(rename = "PositionCategory", default)]
+    pub position_category: Option<String>,
+
+    #[serde(rename = "Position", default)]
+    pub position: Option<String>,
+
+    /// 1 is the starter
+    #[serde(rename = "DepthOrder", default)]
+    pub depth_order: Option<i32>,
+
+    #[serde(rename = "Updated", default)]
+    pub updated: Option<String>,
+}
+
+/// A team's depth chart from the SportsData `DepthCharts` endpoint
+///
+/// MLB and NBA send a single `DepthCharts` list; NFL splits it into
+/// `Offense`, `Defense` and `SpecialTeams`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamDepthChart {
+    #[serde(rename = "TeamID")]
+    pub team_id: i32,
+
+    #[serde(rename = "DepthCharts", default)]
+    pub depth_charts: Vec<DepthChartEntry>,
+
+    #[serde(rename = "Offense", default)]
+    pub offense: Vec<DepthChartEntry>,
+
+    #[serde(rename = "Defense", default)]
+    pub defense: Vec<DepthChartEntry>,
+
+    #[serde(rename = "SpecialTeams", default)]
+    pub special_teams: Vec<DepthChartEntry>,
+}
+
+impl TeamDepthChart {
+    /// Every slot regardless of which list the league uses
+    pub fn entries(&self) -> impl Iterator<Item = &DepthChartEntry> {
+        self.depth_charts
+            .iter()
+            .chain(&self.offense)
+            .chain(&self.defense)
+            .chain(&self.special_teams)
+    }
+}
+
+pub type TeamDepthCharts = Vec<TeamDepthChart>;
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Player on the SportsData `InjuredPlayers` report (MLB, NFL and NBA share this shape)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjuredPlayer {
+    #[serde(rename = "PlayerID")]
+    pub player_id: i32,
+
+    #[serde(rename = "FirstName", default)]
+    pub first_name: Option<String>,
+
+    #[serde(rename = "LastName", default)]
+    pub last_name: Option<String>,
+
+    #[serde(rename = "Team", default)]
+    pub team: Option<String>,
+
+    #[serde(rename = "TeamID", default)]
+    pub team_id: Option<i32>,
+
+    #[serde(rename = "Position", default)]
+    pub position: Option<String>,
+
+    #[serde(rename = "Status", default)]
+    pub status: Option<String>,
+
+    /// e.g. "Out", "Questionable", "Day-To-Day", "10-Day IL"
+    #[serde(rename = "InjuryStatus", default)]
+    pub injury_status: Option<String>,
+
+    #[serde(rename = "InjuryBodyPart", default)]
+    pub injury_body_part: Option<String>,
+
+    #[serde(rename = "InjuryStartDate", default)]
+    pub injury_start_date: Option<String>,
+
+    #[serde(rename = "InjuryNotes", default)]
+    pub injury_notes: Option<String>,
+}
+
+impl InjuredPlayer {
+    pub fn full_name(&self) -> String {
+        match (&self.first_name, &self.last_name) {
+            (Some(first), Some(last)) => format!("{} {}", first, last),
+            (Some(name), None) | (None, Some(name)) => name.clone(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+pub type InjuredPlayers = Vec<InjuredPlayer>;
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// Player in an MLB starting lineup or listed as a probable pitcher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineupPlayer {
+    #[serde(rename = "PlayerID")]
+    pub player_id: i32,
+
+    #[serde(rename = "TeamID", default)]
+    pub team_id: Option<i32>,
+
+    #[serde(rename = "Team", default)]
+    pub team: Option<String>,
+
+    #[serde(rename = "FirstName", default)]
+    pub first_name: Option<String>,
+
+    #[serde(rename = "LastName", default)]
+    pub last_name: Option<String>,
+
+    #[serde(rename = "Position", default)]
+    pub position: Option<String>,
+
+    #[serde(rename = "BattingOrder", default)]
+    pub batting_order: Option<i32>,
+
+    /// `false` while the lineup is projected rather than posted by the team
+    #[serde(rename = "Confirmed", default)]
+    pub confirmed: Option<bool>,
+}
+
+/// One game from the SportsData MLB `StartingLineupsByDate` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MLBStartingLineup {
+    #[serde(rename = "GameID")]
+    pub game_id: i32,
+
+    #[serde(rename = "Day", default)]
+    pub day: Option<String>,
+
+    #[serde(rename = "DateTime", default)]
+    pub date_time: Option<String>,
+
+    #[serde(rename = "Status", default)]
+    pub status: Option<String>,
+
+    #[serde(rename = "HomeTeam")]
+    pub home_team: String,
+
+    #[serde(rename = "AwayTeam")]
+    pub away_team: String,
+
+    #[serde(rename = "HomeStartingPitcher", default)]
+    pub home_starting_pitcher: Option<LineupPlayer>,
+
+    #[serde(rename = "AwayStartingPitcher", default)]
+    pub away_starting_pitcher: Option<LineupPlayer>,
+
+    #[serde(rename = "HomeBattingLineup", default)]
+    pub home_batting_lineup: Vec<LineupPlayer>,
+
+    #[serde(rename = "AwayBattingLineup", default)]
+    pub away_batting_lineup: Vec<LineupPlayer>,
+}
+
+pub type MLBStartingLineups = Vec<MLBStartingLineup>;
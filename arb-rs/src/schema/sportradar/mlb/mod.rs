@@ -1,6 +1,7 @@
 pub mod box_score;
 pub mod game_by_date;
 pub mod headshots;
+pub mod lineups;
 pub mod odds;
 pub mod play_by_play;
 pub mod schedule;
@@ -1,8 +1,12 @@
 pub mod data_type;
+pub mod depth_chart;
 pub mod golf;
+pub mod injuries;
 pub mod league_response;
 pub mod mlb;
 pub mod nba;
+pub mod news;
 pub mod nfl;
 pub mod nhl;
+pub mod player;
 pub mod soccer;
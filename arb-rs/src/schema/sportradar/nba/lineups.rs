@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Player in an NBA starting lineup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NBALineupPlayer {
+    #[serde(rename = "PlayerID")]
+    pub player_id: i32,
+
+    #[serde(rename = "TeamID", default)]
+    pub team_id: Option<i32>,
+
+    #[serde(rename = "Team", default)]
+    pub team: Option<String>,
+
+    #[serde(rename = "Name", default)]
+    pub name: Option<String>,
+
+    #[serde(rename = "Position", default)]
+    pub position: Option<String>,
+
+    #[serde(rename = "Starting", default)]
+    pub starting: Option<bool>,
+
+    /// `false` while the lineup is projected rather than announced
+    #[serde(rename = "Confirmed", default)]
+    pub confirmed: Option<bool>,
+}
+
+/// One game from the SportsData NBA `StartingLineupsByDate` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NBAStartingLineup {
+    #[serde(rename = "GameID")]
+    pub game_id: i32,
+
+    #[serde(rename = "Day", default)]
+    pub day: Option<String>,
+
+    #[serde(rename = "DateTime", default)]
+    pub date_time: Option<String>,
+
+    #[serde(rename = "Status", default)]
+    pub status: Option<String>,
+
+    #[serde(rename = "HomeTeam")]
+    pub home_team: String,
+
+    #[serde(rename = "AwayTeam")]
+    pub away_team: String,
+
+    #[serde(rename = "HomeLineup", default)]
+    pub home_lineup: Vec<NBALineupPlayer>,
+
+    #[serde(rename = "AwayLineup", default)]
+    pub away_lineup: Vec<NBALineupPlayer>,
+}
+
+pub type NBAStartingLineups = Vec<NBAStartingLineup>;
@@ -1,5 +1,6 @@
 pub mod box_score;
 pub mod headshots;
+pub mod lineups;
 pub mod play_by_play;
 pub mod schedule;
 pub mod stadiums;
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// Story from the SportsData `News` endpoint (MLB, NFL and NBA share this shape)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SportsDataNews {
+    #[serde(rename = "NewsID")]
+    pub news_id: i64,
+
+    #[serde(rename = "Source", default)]
+    pub source: Option<String>,
+
+    #[serde(rename = "Updated", default)]
+    pub updated: Option<String>,
+
+    #[serde(rename = "Title", default)]
+    pub title: String,
+
+    #[serde(rename = "Content", default)]
+    pub content: Option<String>,
+
+    #[serde(rename = "Url", default)]
+    pub url: Option<String>,
+
+    #[serde(rename = "Author", default)]
+    pub author: Option<String>,
+
+    /// Comma separated, e.g. "Injuries, Transactions"
+    #[serde(rename = "Categories", default)]
+    pub categories: Option<String>,
+
+    #[serde(rename = "PlayerID", default)]
+    pub player_id: Option<i32>,
+
+    #[serde(rename = "TeamID", default)]
+    pub team_id: Option<i32>,
+
+    #[serde(rename = "Team", default)]
+    pub team: Option<String>,
+
+    #[serde(rename = "PlayerID2", default)]
+    pub player_id2: Option<i32>,
+
+    #[serde(rename = "TeamID2", default)]
+    pub team_id2: Option<i32>,
+
+    #[serde(rename = "Team2", default)]
+    pub team2: Option<String>,
+
+    #[serde(rename = "OriginalSource", default)]
+    pub original_source: Option<String>,
+
+    #[serde(rename = "OriginalSourceUrl", default)]
+    pub original_source_url: Option<String>,
+}
+
+pub type SportsDataNewsFeed = Vec<SportsDataNews>;
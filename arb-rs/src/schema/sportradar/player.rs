@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+/// Player profile from the SportsData `Player/{playerid}` endpoint
+///
+/// The fields below are shared by MLB, NFL and NBA; league-specific fields
+/// (e.g. `BatHand`, `ByeWeek`, `DraftKingsPosition`) are kept in `extra`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SportsDataPlayer {
+    #[serde(rename = "PlayerID")]
+    pub player_id: i32,
+
+    #[serde(rename = "FirstName", default)]
+    pub first_name: Option<String>,
+
+    #[serde(rename = "LastName", default)]
+    pub last_name: Option<String>,
+
+    #[serde(rename = "Status", default)]
+    pub status: Option<String>,
+
+    #[serde(rename = "TeamID", default)]
+    pub team_id: Option<i32>,
+
+    #[serde(rename = "Team", default)]
+    pub team: Option<String>,
+
+    #[serde(rename = "Jersey", default)]
+    pub jersey: Option<i32>,
+
+    #[serde(rename = "Position", default)]
+    pub position: Option<String>,
+
+    #[serde(rename = "PositionCategory", default)]
+    pub position_category: Option<String>,
+
+    #[serde(rename = "Height", default)]
+    pub height: Option<serde_json::Value>,
+
+    #[serde(rename = "Weight", default)]
+    pub weight: Option<i32>,
+
+    #[serde(rename = "BirthDate", default)]
+    pub birth_date: Option<String>,
+
+    #[serde(rename = "BirthCity", default)]
+    pub birth_city: Option<String>,
+
+    #[serde(rename = "BirthState", default)]
+    pub birth_state: Option<String>,
+
+    #[serde(rename = "BirthCountry", default)]
+    pub birth_country: Option<String>,
+
+    #[serde(rename = "College", default)]
+    pub college: Option<String>,
+
+    #[serde(rename = "Experience", default)]
+    pub experience: Option<serde_json::Value>,
+
+    #[serde(rename = "ProDebut", default)]
+    pub pro_debut: Option<String>,
+
+    #[serde(rename = "InjuryStatus", default)]
+    pub injury_status: Option<String>,
+
+    #[serde(rename = "PhotoUrl", default)]
+    pub photo_url: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl SportsDataPlayer {
+    pub fn full_name(&self) -> String {
+        match (&self.first_name, &self.last_name) {
+            (Some(first), Some(last)) => format!("{} {}", first, last),
+            (Some(name), None) | (None, Some(name)) => name.clone(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// One game from the SportsData `PlayerGameStatsBySeason` game log
+///
+/// Every stat column stays in `stats`; only the fields needed to place the game
+/// (date, opponent, venue) are typed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerGameStat {
+    #[serde(rename = "GameID", default)]
+    pub game_id: Option<i64>,
+
+    #[serde(rename = "GameKey", default)]
+    pub game_key: Option<String>,
+
+    #[serde(rename = "Day", default)]
+    pub day: Option<String>,
+
+    #[serde(rename = "GameDate", default)]
+    pub game_date: Option<String>,
+
+    #[serde(rename = "DateTime", default)]
+    pub date_time: Option<String>,
+
+    #[serde(rename = "Team", default)]
+    pub team: Option<String>,
+
+    #[serde(rename = "Opponent", default)]
+    pub opponent: Option<String>,
+
+    #[serde(rename = "HomeOrAway", default)]
+    pub home_or_away: Option<String>,
+
+    #[serde(rename = "Started", default)]
+    pub started: Option<i32>,
+
+    #[serde(flatten)]
+    pub stats: serde_json::Map<String, serde_json::Value>,
+}
+
+pub type PlayerGameStats = Vec<PlayerGameStat>;
@@ -1,39 +1,110 @@
 use async_std::sync::{Arc, Mutex};
 use axum::{
+    http::HeaderValue,
+    middleware,
     routing::{get, post},
     Router,
 };
+use std::net::SocketAddr;
 use tower::ServiceBuilder;
 use tower_http::{
-    cors::{Any, CorsLayer},
+    cors::{AllowOrigin, Any, CorsLayer},
     trace::TraceLayer,
 };
 use tracing::info;
 
-use crate::{cache::Cache, config::ArbConfig};
+use crate::{cache::Cache, config::ArbConfig, metrics::Metrics};
+
+/// CORS for the comma-separated `origins`, or any origin for `*`
+fn cors_layer(origins: &str) -> CorsLayer {
+    let allowed = if origins.trim() == "*" {
+        AllowOrigin::any()
+    } else {
+        let origins: Vec<HeaderValue> = origins
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .filter_map(|origin| match origin.parse() {
+                Ok(origin) => Some(origin),
+                Err(_) => {
+                    tracing::warn!("Ignoring invalid CORS origin: {}", origin);
+                    None
+                }
+            })
+            .collect();
+        AllowOrigin::list(origins)
+    };
+    CorsLayer::new()
+        .allow_origin(allowed)
+        .allow_methods(Any)
+        .allow_headers(Any)
+}
 
 pub struct Server {
     cache: Arc<Mutex<Cache>>,
+    store: Arc<Mutex<Cache>>,
     config: ArbConfig,
+    metrics: Metrics,
 }
 
 impl Server {
-    pub fn new(cache: Arc<Mutex<Cache>>, config: ArbConfig) -> Self {
-        Self { cache, config }
+    pub fn new(
+        cache: Arc<Mutex<Cache>>,
+        store: Arc<Mutex<Cache>>,
+        config: ArbConfig,
+        metrics: Metrics,
+    ) -> Self {
+        Self {
+            cache,
+            store,
+            config,
+            metrics,
+        }
     }
 
     pub fn build(self) -> Router {
         let sportradar_state = crate::uses::sportradar::UseCaseState::new(
             self.cache.clone(),
+            self.store.clone(),
             self.config.clone(),
+            self.metrics.clone(),
         );
         let rolling_insights_state = crate::uses::rolling_insights::UseCaseState::new(
             self.cache.clone(),
             self.config.clone(),
+            self.metrics.clone(),
+            sportradar_state.usage.clone(),
         );
 
+        // Routes for signed-in users only
+        let authenticated_router = Router::new()
+            .route("/api/v1/me", get(crate::uses::sportradar::me))
+            .route("/api/v1/me/feed", get(crate::uses::sportradar::me_feed))
+            .route("/api/v1/auth/logout", post(crate::uses::sportradar::logout))
+            .route(
+                "/api/v1/preferences",
+                get(crate::uses::sportradar::get_preferences)
+                    .put(crate::uses::sportradar::put_preferences)
+                    .patch(crate::uses::sportradar::patch_preferences)
+                    .delete(crate::uses::sportradar::delete_preferences),
+            )
+            .route(
+                "/api/v1/favorites",
+                get(crate::uses::sportradar::get_favorites)
+                    .put(crate::uses::sportradar::put_favorites)
+                    .post(crate::uses::sportradar::add_favorite)
+                    .delete(crate::uses::sportradar::remove_favorite),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                sportradar_state.clone(),
+                crate::uses::sportradar::require_auth,
+            ));
+
         let sportradar_router = Router::new()
-            .route("/health", get(crate::uses::sportradar::health_check))
+            .route("/health", get(crate::uses::health::health_check))
+            .route("/health/live", get(crate::uses::health::liveness))
+            .route("/health/ready", get(crate::uses::health::readiness_check))
+            .route("/metrics", get(crate::uses::sportradar::metrics))
             .route(
                 "/api/v1/team-profile",
                 get(crate::uses::sportradar::team_profile),
@@ -56,6 +127,71 @@ impl Server {
             )
             .route("/api/v1/stadiums", get(crate::uses::sportradar::stadiums))
             .route("/api/v1/standings", get(crate::uses::sportradar::standings))
+            .route(
+                "/api/v1/standings-analytics",
+                get(crate::uses::sportradar::standings_analytics),
+            )
+            .route(
+                "/api/v1/playoff-odds",
+                get(crate::uses::sportradar::playoff_odds),
+            )
+            .route("/api/v1/ratings", get(crate::uses::sportradar::ratings))
+            .route(
+                "/api/v1/ratings/history",
+                get(crate::uses::sportradar::rating_history),
+            )
+            .route(
+                "/api/v1/win-probability",
+                get(crate::uses::sportradar::win_probability),
+            )
+            .route(
+                "/api/v1/season-stats",
+                get(crate::uses::sportradar::season_stats),
+            )
+            .route(
+                "/api/v1/leaderboard",
+                get(crate::uses::sportradar::leaderboard),
+            )
+            .route(
+                "/api/v1/player",
+                get(crate::uses::sportradar::player_profile),
+            )
+            .route(
+                "/api/v1/player/game-log",
+                get(crate::uses::sportradar::player_game_log),
+            )
+            .route(
+                "/api/v1/player/splits",
+                get(crate::uses::sportradar::player_splits),
+            )
+            .route(
+                "/api/v1/player/career",
+                get(crate::uses::sportradar::player_career),
+            )
+            .route("/api/v1/injuries", get(crate::uses::sportradar::injuries))
+            .route(
+                "/api/v1/depth-charts",
+                get(crate::uses::sportradar::depth_charts),
+            )
+            .route("/api/v1/lineups", get(crate::uses::sportradar::lineups))
+            .route(
+                "/api/v1/probable-pitchers",
+                get(crate::uses::sportradar::probable_pitchers),
+            )
+            .route("/api/v1/news", get(crate::uses::sportradar::news))
+            .route(
+                "/api/v1/game-social",
+                get(crate::uses::sportradar::game_social),
+            )
+            .route(
+                "/api/v1/game-sentiment",
+                get(crate::uses::sportradar::game_sentiment),
+            )
+            .route("/api/v1/videos", get(crate::uses::sportradar::videos))
+            .route(
+                "/api/v1/game-timeline",
+                get(crate::uses::sportradar::game_timeline),
+            )
             .route(
                 "/api/v1/odds-by-date",
                 get(crate::uses::sportradar::odds_by_date),
@@ -88,7 +224,29 @@ impl Server {
                 "/api/v1/signin/apple/callback",
                 post(crate::uses::sportradar::handle_apple_auth_callback),
             )
-            .with_state(sportradar_state);
+            .route(
+                "/api/v1/auth/token",
+                post(crate::uses::sportradar::auth_token),
+            )
+            .route(
+                "/api/v1/auth/refresh",
+                post(crate::uses::sportradar::auth_refresh),
+            )
+            .route(
+                "/api/v1/auth/guest",
+                post(crate::uses::sportradar::guest_signup),
+            )
+            .route(
+                "/api/v1/admin/api-keys",
+                post(crate::uses::sportradar::issue_api_key)
+                    .delete(crate::uses::sportradar::revoke_api_key),
+            )
+            .route(
+                "/api/v1/admin/usage",
+                get(crate::uses::sportradar::usage_report),
+            )
+            .merge(authenticated_router)
+            .with_state(sportradar_state.clone());
 
         let rolling_insights_router = Router::new()
             .route(
@@ -113,16 +271,29 @@ impl Server {
             )
             .with_state(rolling_insights_state);
 
-        sportradar_router.merge(rolling_insights_router).layer(
-            ServiceBuilder::new()
-                .layer(TraceLayer::new_for_http())
-                .layer(
-                    CorsLayer::new()
-                        .allow_origin(Any)
-                        .allow_methods(Any)
-                        .allow_headers(Any),
-                ),
-        )
+        sportradar_router
+            .merge(rolling_insights_router)
+            .route_layer(middleware::from_fn_with_state(
+                sportradar_state.clone(),
+                crate::uses::sportradar::track_usage,
+            ))
+            .route_layer(middleware::from_fn_with_state(
+                sportradar_state.clone(),
+                crate::uses::sportradar::track_metrics,
+            ))
+            .route_layer(middleware::from_fn(crate::uses::sportradar::trace_handler))
+            .layer(
+                ServiceBuilder::new()
+                    .layer(
+                        TraceLayer::new_for_http()
+                            .make_span_with(crate::telemetry::request_span),
+                    )
+                    .layer(cors_layer(&self.config.server.cors_origins))
+                    .layer(middleware::from_fn_with_state(
+                        sportradar_state,
+                        crate::uses::sportradar::rate_limit,
+                    )),
+            )
     }
 
     pub async fn run(self) -> Result<(), Box<dyn std::error::Error>> {
@@ -131,8 +302,47 @@ impl Server {
         let listener = tokio::net::TcpListener::bind("0.0.0.0:8000").await?;
         info!("Server starting on http://0.0.0.0:8000");
 
-        axum::serve(listener, app).await?;
+        // Client addresses feed per-IP rate limits
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
 
+        info!("Server stopped");
         Ok(())
     }
 }
+
+/// Resolves on Ctrl-C or SIGTERM, after which in-flight requests finish and
+/// `run` returns
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    info!("Shutting down, finishing in-flight requests");
+}
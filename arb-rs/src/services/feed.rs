@@ -0,0 +1,153 @@
+use chrono::{DateTime, NaiveDate, TimeZone};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::services::news::eastern_datetime;
+
+/// A favorite team's game, with its start in the user's time zone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedGame {
+    pub league: String,
+    pub game_id: String,
+    pub home: String,
+    pub away: String,
+    pub status: String,
+    /// RFC 3339 in the user's time zone; midnight of the game day, in that
+    /// time zone, when the start time isn't set yet
+    pub starts_at: String,
+    /// Whether `starts_at` is a real start time
+    pub start_time_known: bool,
+    pub home_score: Option<i32>,
+    pub away_score: Option<i32>,
+    /// The user's favorites playing in this game
+    pub favorites: Vec<String>,
+}
+
+/// Games in `games` involving one of `favorites` whose start, seen from
+/// `tz`, falls on `from` through `to`; sorted by start
+///
+/// Games without a start time stay on their scheduled date: there's no time
+/// to convert, and moving Eastern midnight would put them a day early west of
+/// New York.
+pub fn feed_games(
+    league: &str,
+    games: &[serde_json::Value],
+    favorites: &[String],
+    tz: Tz,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<FeedGame> {
+    let mut feed: Vec<(DateTime<Tz>, FeedGame)> = games
+        .iter()
+        .filter_map(|game| {
+            let text = |key: &str| game.get(key).and_then(|v| v.as_str());
+            let home = text("HomeTeam")?;
+            let away = text("AwayTeam")?;
+            let playing: Vec<String> = favorites
+                .iter()
+                .filter(|team| {
+                    team.eq_ignore_ascii_case(home) || team.eq_ignore_ascii_case(away)
+                })
+                .cloned()
+                .collect();
+            if playing.is_empty() {
+                return None;
+            }
+
+            let date_time = text("DateTime").and_then(eastern_datetime);
+            let start = match date_time {
+                Some(start) => start.with_timezone(&tz),
+                None => {
+                    let day = eastern_datetime(text("Day")?)?.date_naive();
+                    tz.from_local_datetime(&day.and_time(chrono::NaiveTime::MIN))
+                        .earliest()?
+                }
+            };
+            if start.date_naive() < from || start.date_naive() > to {
+                return None;
+            }
+
+            let score = |keys: &[&str]| {
+                keys.iter()
+                    .find_map(|key| game.get(*key).and_then(|v| v.as_i64()))
+                    .map(|score| score as i32)
+            };
+            let game_id = ["GameID", "GameId", "GlobalGameID", "ScoreID", "GameKey"]
+                .iter()
+                .find_map(|key| match game.get(*key)? {
+                    serde_json::Value::String(value) => Some(value.clone()),
+                    serde_json::Value::Number(value) => Some(value.to_string()),
+                    _ => None,
+                })
+                .unwrap_or_default();
+
+            Some((
+                start,
+                FeedGame {
+                    league: league.to_string(),
+                    game_id,
+                    home: home.to_string(),
+                    away: away.to_string(),
+                    status: text("Status").unwrap_or("Scheduled").to_string(),
+                    starts_at: start.to_rfc3339(),
+                    start_time_known: date_time.is_some(),
+                    home_score: score(&["HomeTeamRuns", "HomeTeamScore", "HomeScore"]),
+                    away_score: score(&["AwayTeamRuns", "AwayTeamScore", "AwayScore"]),
+                    favorites: playing,
+                },
+            ))
+        })
+        .collect();
+
+    feed.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.game_id.cmp(&b.1.game_id)));
+    feed.into_iter().map(|(_, game)| game).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::{Los_Angeles, New_York};
+    use serde_json::json;
+
+    #[test]
+    fn test_feed_uses_the_users_calendar_day() {
+        let games = vec![
+            json!({"GameID": 2, "HomeTeam": "CIN", "AwayTeam": "CHC",
+                   "DateTime": "2025-07-04T22:10:00", "Status": "Scheduled"}),
+            json!({"GameID": 1, "HomeTeam": "CIN", "AwayTeam": "CHC",
+                   "DateTime": "2025-07-04T13:10:00", "Status": "Final",
+                   "HomeTeamRuns": 5, "AwayTeamRuns": 3}),
+            json!({"GameID": 3, "HomeTeam": "NYY", "AwayTeam": "BOS",
+                   "DateTime": "2025-07-04T19:05:00"}),
+        ];
+        let favorites = vec!["CIN".to_string()];
+        let day = NaiveDate::from_ymd_opt(2025, 7, 4).unwrap();
+
+        let eastern = feed_games("mlb", &games, &favorites, New_York, day, day);
+        assert_eq!(eastern.len(), 2);
+        assert_eq!(eastern[0].game_id, "1");
+        assert_eq!(eastern[0].starts_at, "2025-07-04T13:10:00-04:00");
+        assert_eq!(eastern[0].home_score, Some(5));
+
+        // Both games are already July 5th in Tokyo
+        let tokyo = chrono_tz::Asia::Tokyo;
+        assert!(feed_games("mlb", &games, &favorites, tokyo, day, day).is_empty());
+        let next_day = day.succ_opt().unwrap();
+        let tokyo = feed_games("mlb", &games, &favorites, tokyo, next_day, next_day);
+        assert_eq!(tokyo.len(), 2);
+        assert_eq!(tokyo[1].starts_at, "2025-07-05T11:10:00+09:00");
+    }
+
+    #[test]
+    fn test_games_without_a_time_keep_their_date() {
+        let games = vec![json!({"GameID": 4, "HomeTeam": "CIN", "AwayTeam": "CHC",
+                                "Day": "2025-07-04T00:00:00", "DateTime": null})];
+        let favorites = vec!["CIN".to_string()];
+        let day = NaiveDate::from_ymd_opt(2025, 7, 4).unwrap();
+
+        let pacific = feed_games("mlb", &games, &favorites, Los_Angeles, day, day);
+        assert_eq!(pacific.len(), 1);
+        assert_eq!(pacific[0].starts_at, "2025-07-04T00:00:00-07:00");
+        assert!(!pacific[0].start_time_known);
+    }
+}
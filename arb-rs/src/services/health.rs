@@ -0,0 +1,193 @@
+use async_std::sync::{Arc, Mutex, MutexGuard};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::services::usage::Upstream;
+
+/// Redis round trips slower than this mark Redis as degraded
+pub const SLOW_REDIS: Duration = Duration::from_millis(100);
+
+/// Credentials the server can't serve scores or sessions without; the rest
+/// only turn off one feature
+pub const REQUIRED_CREDENTIALS: &[&str] = &["SPORTSDATAIO_API_KEY", "JWT_SECRET"];
+
+/// Checks that take the instance out of rotation when down
+pub const CRITICAL_CHECKS: &[&str] = &["redis", "config", "credentials"];
+
+/// What readiness probes share: one HTTP client, and a lock per upstream so
+/// polls arriving together wait for a single probe instead of each sending
+/// their own
+#[derive(Debug, Clone)]
+pub struct UpstreamProbes {
+    pub http: reqwest::Client,
+    locks: Arc<BTreeMap<Upstream, Mutex<()>>>,
+}
+
+impl UpstreamProbes {
+    pub fn new(timeout: Duration) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|e| {
+                tracing::error!(
+                    "Failed to build probe HTTP client, using defaults: {}",
+                    e
+                );
+                reqwest::Client::new()
+            });
+        let locks = Upstream::ALL
+            .into_iter()
+            .map(|upstream| (upstream, Mutex::new(())))
+            .collect();
+        Self {
+            http,
+            locks: Arc::new(locks),
+        }
+    }
+
+    /// Held while `upstream` is probed
+    pub async fn lock(&self, upstream: Upstream) -> Option<MutexGuard<'_, ()>> {
+        Some(self.locks.get(&upstream)?.lock().await)
+    }
+}
+
+/// State of one dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Up,
+    Degraded,
+    Down,
+    /// Not checked, e.g. no database is configured or credentials are missing
+    Skipped,
+}
+
+/// Result of checking one dependency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Check {
+    pub status: CheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// When an upstream was last probed, as a Unix timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<i64>,
+}
+
+impl Check {
+    fn new(status: CheckStatus, detail: Option<String>) -> Self {
+        Self {
+            status,
+            latency_ms: None,
+            detail,
+            checked_at: None,
+        }
+    }
+
+    pub fn up() -> Self {
+        Self::new(CheckStatus::Up, None)
+    }
+
+    pub fn degraded(detail: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Degraded, Some(detail.into()))
+    }
+
+    pub fn down(detail: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Down, Some(detail.into()))
+    }
+
+    pub fn skipped(detail: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Skipped, Some(detail.into()))
+    }
+
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency_ms = Some(latency.as_secs_f64() * 1000.0);
+        self
+    }
+
+    pub fn checked_at(mut self, timestamp: i64) -> Self {
+        self.checked_at = Some(timestamp);
+        self
+    }
+}
+
+/// Whether an instance should get traffic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Readiness {
+    Ready,
+    /// Serving, but a dependency is slow, down or unconfigured
+    Degraded,
+    NotReady,
+}
+
+/// Check for credentials: down without a required one, degraded without
+/// any other
+pub fn credentials_check(missing: &[&str]) -> Check {
+    let required: Vec<&str> = missing
+        .iter()
+        .copied()
+        .filter(|name| REQUIRED_CREDENTIALS.contains(name))
+        .collect();
+    if !required.is_empty() {
+        Check::down(format!("missing {}", required.join(", ")))
+    } else if !missing.is_empty() {
+        Check::degraded(format!("missing {}", missing.join(", ")))
+    } else {
+        Check::up()
+    }
+}
+
+/// Not ready when a critical check is down, degraded when any check is
+/// degraded or down; skipped checks don't count
+pub fn readiness(checks: &BTreeMap<String, Check>) -> Readiness {
+    let critical_down = checks.iter().any(|(name, check)| {
+        check.status == CheckStatus::Down && CRITICAL_CHECKS.contains(&name.as_str())
+    });
+    if critical_down {
+        Readiness::NotReady
+    } else if checks
+        .values()
+        .any(|check| matches!(check.status, CheckStatus::Degraded | CheckStatus::Down))
+    {
+        Readiness::Degraded
+    } else {
+        Readiness::Ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upstream_outage_degrades_but_redis_outage_is_not_ready() {
+        let mut checks = BTreeMap::from([
+            ("redis".to_string(), Check::up()),
+            ("config".to_string(), Check::up()),
+            ("credentials".to_string(), credentials_check(&[])),
+            (
+                "reddit".to_string(),
+                Check::skipped("credentials not configured"),
+            ),
+        ]);
+        assert_eq!(readiness(&checks), Readiness::Ready);
+
+        checks.insert("sports_data".to_string(), Check::down("Status error: 502"));
+        assert_eq!(readiness(&checks), Readiness::Degraded);
+        checks.insert("database".to_string(), Check::down("connection refused"));
+        assert_eq!(readiness(&checks), Readiness::Degraded);
+
+        checks.insert("redis".to_string(), Check::down("connection refused"));
+        assert_eq!(readiness(&checks), Readiness::NotReady);
+
+        assert_eq!(
+            credentials_check(&["RSC_TOKEN"]).status,
+            CheckStatus::Degraded
+        );
+        let check = credentials_check(&["RSC_TOKEN", "JWT_SECRET"]);
+        assert_eq!(check.status, CheckStatus::Down);
+        assert_eq!(check.detail.as_deref(), Some("missing JWT_SECRET"));
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::schema::sportradar::{depth_chart::TeamDepthChart, injuries::InjuredPlayer};
+
+/// Most recent starter alerts kept per league
+pub const MAX_STARTER_ALERTS: usize = 50;
+
+/// Whether an injury status keeps the player out of the next game
+///
+/// Covers "Out", "Suspended", NFL injured reserve and MLB injured list stints
+/// ("10-Day IL", "60-Day IL"); "Questionable" or "Day-To-Day" don't count.
+pub fn is_ruled_out(status: &str) -> bool {
+    let status = status.trim().to_lowercase();
+    status == "out"
+        || status == "ir"
+        || status == "suspended"
+        || status.contains("injured reserve")
+        || status.ends_with(" il")
+        || status.ends_with("-il")
+}
+
+/// Player ids listed first on their team's depth chart at any position
+pub fn depth_chart_starters(charts: &[TeamDepthChart]) -> HashSet<i32> {
+    charts
+        .iter()
+        .flat_map(|chart| chart.entries())
+        .filter(|entry| entry.depth_order == Some(1))
+        .map(|entry| entry.player_id)
+        .collect()
+}
+
+/// Injury report row annotated for game previews
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjuryReportEntry {
+    #[serde(flatten)]
+    pub player: InjuredPlayer,
+    pub starter: bool,
+    pub ruled_out: bool,
+}
+
+pub fn annotate(
+    injuries: Vec<InjuredPlayer>,
+    starters: &HashSet<i32>,
+) -> Vec<InjuryReportEntry> {
+    injuries
+        .into_iter()
+        .map(|player| InjuryReportEntry {
+            starter: starters.contains(&player.player_id),
+            ruled_out: player.injury_status.as_deref().is_some_and(is_ruled_out),
+            player,
+        })
+        .collect()
+}
+
+/// A starter who was just ruled out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarterAlert {
+    pub player_id: i32,
+    pub name: String,
+    pub team: Option<String>,
+    pub position: Option<String>,
+    pub previous_status: Option<String>,
+    pub status: String,
+    pub detected_at: String,
+}
+
+/// Injury status per player id as of the last report we saw
+pub type InjurySnapshot = HashMap<i32, String>;
+
+pub fn snapshot(injuries: &[InjuredPlayer]) -> InjurySnapshot {
+    injuries
+        .iter()
+        .filter_map(|player| Some((player.player_id, player.injury_status.clone()?)))
+        .collect()
+}
+
+/// Starters whose status moved to ruled out since the previous report
+///
+/// An empty previous snapshot (first report seen) produces no alerts, so a cold
+/// start doesn't flag every starter already on the injured list.
+pub fn detect_ruled_out_starters(
+    previous: &InjurySnapshot,
+    current: &[InjuredPlayer],
+    starters: &HashSet<i32>,
+) -> Vec<StarterAlert> {
+    if previous.is_empty() {
+        return Vec::new();
+    }
+
+    let detected_at = chrono::Utc::now().to_rfc3339();
+    current
+        .iter()
+        .filter(|player| starters.contains(&player.player_id))
+        .filter_map(|player| {
+            let status = player.injury_status.as_deref()?;
+            let previous_status = previous.get(&player.player_id);
+            let was_out = previous_status.is_some_and(|status| is_ruled_out(status));
+            (is_ruled_out(status) && !was_out).then(|| StarterAlert {
+                player_id: player.player_id,
+                name: player.full_name(),
+                team: player.team.clone(),
+                position: player.position.clone(),
+                previous_status: previous_status.cloned(),
+                status: status.to_string(),
+                detected_at: detected_at.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn injured(player_id: i32, status: &str) -> InjuredPlayer {
+        serde_json::from_value(serde_json::json!({
+            "PlayerID": player_id,
+            "FirstName": "Test",
+            "LastName": format!("Player{}", player_id),
+            "Team": "NYY",
+            "InjuryStatus": status
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ruled_out_statuses() {
+        assert!(is_ruled_out("Out"));
+        assert!(is_ruled_out("10-Day IL"));
+        assert!(is_ruled_out("Injured Reserve"));
+        assert!(!is_ruled_out("Questionable"));
+        assert!(!is_ruled_out("Day-To-Day"));
+    }
+
+    #[test]
+    fn test_only_newly_ruled_out_starters_are_flagged() {
+        let starters: HashSet<i32> = [1, 2, 3].into_iter().collect();
+        let previous = snapshot(&[injured(1, "Questionable"), injured(2, "Out")]);
+        let current = vec![
+            injured(1, "Out"),
+            injured(2, "Out"),
+            injured(3, "15-Day IL"),
+            injured(4, "Out"),
+        ];
+
+        let alerts = detect_ruled_out_starters(&previous, &current, &starters);
+        let flagged: Vec<i32> = alerts.iter().map(|alert| alert.player_id).collect();
+        assert_eq!(flagged, [1, 3]);
+        assert_eq!(alerts[0].previous_status.as_deref(), Some("Questionable"));
+
+        assert!(
+            detect_ruled_out_starters(&InjurySnapshot::new(), &current, &starters)
+                .is_empty()
+        );
+    }
+}
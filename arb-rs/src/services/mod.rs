@@ -1 +1,21 @@
 pub mod auth;
+pub mod feed;
+pub mod health;
+pub mod injuries;
+pub mod moderation;
+pub mod news;
+pub mod pagination;
+pub mod players;
+pub mod preferences;
+pub mod rate_limit;
+pub mod ratings;
+pub mod reddit;
+pub mod sentiment;
+pub mod simulation;
+pub mod social;
+pub mod standings;
+pub mod stats;
+pub mod timeline;
+pub mod twitter;
+pub mod usage;
+pub mod videos;
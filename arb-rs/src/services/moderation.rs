@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt, str::FromStr};
+
+use crate::{
+    config::ModerationConfig,
+    schema::{
+        reddit::{RedditComment, RedditSearchResponse},
+        twitterapi::tweet::TwitterSearchResponse,
+    },
+};
+
+/// How much of a social feed a user wants filtered
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Strictness {
+    /// Everything, as posted
+    Off,
+    /// Drop NSFW posts, spam and blocked authors; mask listed words
+    #[default]
+    Moderate,
+    /// As moderate, but drop anything containing a listed word
+    Strict,
+}
+
+impl fmt::Display for Strictness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strictness::Off => write!(f, "off"),
+            Strictness::Moderate => write!(f, "moderate"),
+            Strictness::Strict => write!(f, "strict"),
+        }
+    }
+}
+
+impl FromStr for Strictness {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "off" => Ok(Strictness::Off),
+            "moderate" => Ok(Strictness::Moderate),
+            "strict" => Ok(Strictness::Strict),
+            other => Err(format!("Unknown moderation strictness: {}", other)),
+        }
+    }
+}
+
+/// Posts with fewer letters than this besides their links count as link-only
+const LINK_ONLY_MAX_LETTERS: usize = 3;
+/// Texts with at least this many words are checked for repetition
+const REPETITION_MIN_WORDS: usize = 6;
+/// Share of distinct words below which a text counts as repetitive
+const REPETITION_MAX_DISTINCT: f64 = 0.3;
+/// Texts with at least this many words count as copies when anyone reposts
+/// them; shorter ones only when their author does, since fans say "what a
+/// catch" together
+const COPY_MIN_WORDS: usize = 8;
+
+/// Links and nothing else worth reading
+fn is_link_only(text: &str) -> bool {
+    let mut has_link = false;
+    let mut letters = 0;
+    for word in text.split_whitespace() {
+        if word.starts_with("http://")
+            || word.starts_with("https://")
+            || word.starts_with("www.")
+        {
+            has_link = true;
+        } else {
+            letters += word.chars().filter(|c| c.is_alphabetic()).count();
+        }
+    }
+    has_link && letters < LINK_ONLY_MAX_LETTERS
+}
+
+/// The same few words over and over
+fn is_repetitive(words: &[String]) -> bool {
+    if words.len() < REPETITION_MIN_WORDS {
+        return false;
+    }
+    let distinct: HashSet<&String> = words.iter().collect();
+    (distinct.len() as f64) / (words.len() as f64) < REPETITION_MAX_DISTINCT
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// What happened to a feed under moderation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModerationSummary {
+    pub removed: usize,
+    pub masked: usize,
+}
+
+/// Applies one user's strictness to a feed
+///
+/// Remembers texts already seen so an author's repeats, and copies of long
+/// texts by anyone, posted again later in the same feed are dropped as spam.
+pub struct Moderator<'a> {
+    config: &'a ModerationConfig,
+    strictness: Strictness,
+    seen: HashSet<String>,
+    seen_by_author: HashSet<(String, String)>,
+    pub summary: ModerationSummary,
+}
+
+impl<'a> Moderator<'a> {
+    pub fn new(config: &'a ModerationConfig, strictness: Strictness) -> Self {
+        Self {
+            config,
+            strictness,
+            seen: HashSet::new(),
+            seen_by_author: HashSet::new(),
+            summary: ModerationSummary::default(),
+        }
+    }
+
+    fn is_blocked_word(&self, word: &str) -> bool {
+        self.config
+            .blocked_words
+            .iter()
+            .any(|blocked| blocked.eq_ignore_ascii_case(word))
+    }
+
+    /// The text to show, masked if needed, or `None` to drop the post
+    pub fn review(&mut self, author: &str, text: &str, nsfw: bool) -> Option<String> {
+        if self.strictness == Strictness::Off {
+            return Some(text.to_string());
+        }
+
+        let handle = |name: &str| {
+            let name = name.trim();
+            name.strip_prefix("u/")
+                .unwrap_or(name)
+                .trim_start_matches('@')
+                .to_lowercase()
+        };
+        let blocked_author = self
+            .config
+            .blocked_authors
+            .iter()
+            .any(|blocked| handle(blocked) == handle(author));
+        let words = words(text);
+        let normalized = words.join(" ");
+        let repeated = !words.is_empty()
+            && !self
+                .seen_by_author
+                .insert((handle(author), normalized.clone()));
+        let copied = words.len() >= COPY_MIN_WORDS && !self.seen.insert(normalized);
+        let duplicate = repeated || copied;
+        let spam = is_link_only(text) || is_repetitive(&words) || duplicate;
+        let profane = words.iter().any(|word| self.is_blocked_word(word));
+
+        if blocked_author
+            || nsfw
+            || spam
+            || (profane && self.strictness == Strictness::Strict)
+        {
+            self.summary.removed += 1;
+            return None;
+        }
+        if !profane {
+            return Some(text.to_string());
+        }
+
+        self.summary.masked += 1;
+        let masked = text
+            .split_inclusive(|c: char| !c.is_alphanumeric() && c != '\'')
+            .map(|piece| {
+                let word =
+                    piece.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '\'');
+                if self.is_blocked_word(word) {
+                    format!(
+                        "{}{}",
+                        "*".repeat(word.chars().count()),
+                        &piece[word.len()..]
+                    )
+                } else {
+                    piece.to_string()
+                }
+            })
+            .collect();
+        Some(masked)
+    }
+
+    /// Review comments and their replies; dropping a comment drops its replies
+    fn comments(&mut self, comments: Vec<RedditComment>) -> Vec<RedditComment> {
+        comments
+            .into_iter()
+            .filter_map(|mut comment| {
+                comment.content =
+                    self.review(&comment.author, &comment.content, false)?;
+                comment.replies = self.comments(std::mem::take(&mut comment.replies));
+                Some(comment)
+            })
+            .collect()
+    }
+
+    pub fn reddit(&mut self, response: &mut RedditSearchResponse) {
+        let posts = std::mem::take(&mut response.posts);
+        response.posts = posts
+            .into_iter()
+            .filter_map(|mut post| {
+                post.title = self.review(&post.author, &post.title, post.over_18)?;
+                post.comments = self.comments(std::mem::take(&mut post.comments));
+                Some(post)
+            })
+            .collect();
+        response.total_posts = response.posts.len() as u32;
+    }
+
+    pub fn tweets(&mut self, response: &mut TwitterSearchResponse) {
+        let tweets = std::mem::take(&mut response.tweets);
+        response.tweets = tweets
+            .into_iter()
+            .filter_map(|mut tweet| {
+                let author = tweet
+                    .additional_fields
+                    .pointer("/author/userName")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                let nsfw =
+                    ["possiblySensitive", "possibly_sensitive"]
+                        .iter()
+                        .any(|key| {
+                            tweet
+                                .additional_fields
+                                .get(*key)
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false)
+                        });
+                tweet.text = self.review(&author, &tweet.text, nsfw)?;
+                Some(tweet)
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ModerationConfig {
+        ModerationConfig {
+            default_strictness: Strictness::Moderate,
+            blocked_words: vec!["darn".to_string()],
+            blocked_authors: vec!["u/SpamBot".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_moderate_masks_and_strict_drops() {
+        let config = config();
+        let mut moderate = Moderator::new(&config, Strictness::Moderate);
+        assert_eq!(
+            moderate.review("fan", "Darn, that ump!", false).as_deref(),
+            Some("****, that ump!")
+        );
+        assert_eq!(moderate.review("SpamBot", "Great game", false), None);
+        assert_eq!(moderate.review("fan", "Nice swing", true), None);
+
+        let mut strict = Moderator::new(&config, Strictness::Strict);
+        assert_eq!(strict.review("fan", "Darn, that ump!", false), None);
+        let mut off = Moderator::new(&config, Strictness::Off);
+        assert!(off.review("SpamBot", "darn", true).is_some());
+    }
+
+    #[test]
+    fn test_spam_heuristics() {
+        let config = config();
+        let mut moderator = Moderator::new(&config, Strictness::Moderate);
+        assert_eq!(
+            moderator.review("a", "https://spam.example/x 🔥", false),
+            None
+        );
+        assert_eq!(
+            moderator.review("b", "go go go go go go go go", false),
+            None
+        );
+        assert!(moderator.review("c", "What a catch!", false).is_some());
+        assert!(moderator.review("d", "what a CATCH", false).is_some());
+        assert_eq!(moderator.review("c", "what a catch", false), None);
+
+        let copypasta = "Follow my page for the best picks every single night";
+        assert!(moderator.review("e", copypasta, false).is_some());
+        assert_eq!(moderator.review("f", copypasta, false), None);
+        assert_eq!(moderator.summary.removed, 4);
+    }
+}
@@ -0,0 +1,403 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::{America::New_York, Tz};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::{
+    schema::sportradar::{news::SportsDataNews, player::SportsDataPlayer},
+    services::pagination::Paging,
+};
+
+/// What a story is about, for filtering transactions out of general news
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NewsKind {
+    Trade,
+    Signing,
+    /// Injured list / injured reserve placements and activations
+    InjuredList,
+    #[default]
+    News,
+}
+
+const INJURED_LIST_PHRASES: [&str; 8] = [
+    " injured list ",
+    " il ",
+    " injured reserve ",
+    " ir ",
+    " placed on ",
+    " activated from ",
+    " reinstated from ",
+    " designated to return ",
+];
+const TRADE_PHRASES: [&str; 4] = [" trade ", " traded ", " trades ", " acquire"];
+const SIGNING_PHRASES: [&str; 8] = [
+    " sign ",
+    " signs ",
+    " signed ",
+    " signing ",
+    " agree to ",
+    " agrees to ",
+    " contract ",
+    " extension ",
+];
+
+/// Classify a story from its headline and provider categories
+pub fn classify(title: &str, categories: Option<&str>) -> NewsKind {
+    let text: String = format!("{} {}", title, categories.unwrap_or_default())
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let text = format!(
+        " {} ",
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+    let mentions = |phrases: &[&str]| phrases.iter().any(|phrase| text.contains(phrase));
+
+    if mentions(&INJURED_LIST_PHRASES) {
+        NewsKind::InjuredList
+    } else if mentions(&TRADE_PHRASES) {
+        NewsKind::Trade
+    } else if mentions(&SIGNING_PHRASES) {
+        NewsKind::Signing
+    } else {
+        NewsKind::News
+    }
+}
+
+/// A news story from the provider or a team feed, tagged with teams and players
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewsStory {
+    pub id: String,
+    pub kind: NewsKind,
+    pub title: String,
+    pub summary: Option<String>,
+    pub url: Option<String>,
+    pub source: String,
+    pub author: Option<String>,
+    /// RFC 3339, UTC
+    pub published: Option<String>,
+    pub teams: Vec<String>,
+    pub team_ids: Vec<i32>,
+    pub player_ids: Vec<i32>,
+}
+
+impl NewsStory {
+    /// Whether the story is tagged with a team, by abbreviation
+    pub fn mentions_team(&self, team: &str) -> bool {
+        self.teams.iter().any(|t| t.eq_ignore_ascii_case(team))
+    }
+}
+
+/// SportsData timestamps are US Eastern wall-clock time without an offset
+///
+/// Read in `America/New_York`, so daylight saving time is followed; the
+/// repeated hour when clocks fall back resolves to its first occurrence.
+pub fn eastern_datetime(value: &str) -> Option<DateTime<Tz>> {
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").ok()?;
+    New_York.from_local_datetime(&naive).earliest()
+}
+
+/// A SportsData timestamp as RFC 3339 in UTC
+pub fn provider_timestamp(value: &str) -> Option<String> {
+    Some(eastern_datetime(value)?.with_timezone(&Utc).to_rfc3339())
+}
+
+impl From<&SportsDataNews> for NewsStory {
+    fn from(news: &SportsDataNews) -> Self {
+        let teams: Vec<String> = [&news.team, &news.team2]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect();
+
+        Self {
+            id: format!("sportsdata:{}", news.news_id),
+            kind: classify(&news.title, news.categories.as_deref()),
+            title: news.title.clone(),
+            summary: news.content.clone(),
+            url: news
+                .original_source_url
+                .clone()
+                .or_else(|| news.url.clone()),
+            source: news
+                .original_source
+                .clone()
+                .or_else(|| news.source.clone())
+                .unwrap_or_else(|| "SportsData".to_string()),
+            author: news.author.clone(),
+            published: news.updated.as_deref().and_then(provider_timestamp),
+            teams,
+            team_ids: [news.team_id, news.team_id2]
+                .into_iter()
+                .flatten()
+                .collect(),
+            player_ids: [news.player_id, news.player_id2]
+                .into_iter()
+                .flatten()
+                .collect(),
+        }
+    }
+}
+
+/// Drop markup from feed summaries, which are often HTML fragments
+fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Stories from a parsed RSS or Atom feed configured for `team`
+pub fn feed_stories(
+    feed: &feed_rs::model::Feed,
+    source: Option<&str>,
+    team: &str,
+) -> Vec<NewsStory> {
+    let source = source
+        .map(String::from)
+        .or_else(|| feed.title.as_ref().map(|title| title.content.clone()))
+        .unwrap_or_else(|| team.to_string());
+
+    feed.entries
+        .iter()
+        .filter_map(|entry| {
+            let title = entry.title.as_ref()?.content.trim().to_string();
+            let categories: Vec<&str> = entry
+                .categories
+                .iter()
+                .map(|category| category.term.as_str())
+                .collect();
+            let url = entry.links.first().map(|link| link.href.clone());
+            let summary = entry
+                .summary
+                .as_ref()
+                .map(|summary| strip_html(&summary.content))
+                .filter(|summary| !summary.is_empty());
+
+            Some(NewsStory {
+                id: if entry.id.is_empty() {
+                    url.clone().unwrap_or_else(|| title.clone())
+                } else {
+                    entry.id.clone()
+                },
+                kind: classify(&title, Some(&categories.join(" "))),
+                summary,
+                url,
+                source: source.clone(),
+                author: entry.authors.first().map(|author| author.name.clone()),
+                published: entry
+                    .published
+                    .or(entry.updated)
+                    .map(|published: DateTime<Utc>| published.to_rfc3339()),
+                teams: vec![team.to_uppercase()],
+                team_ids: Vec::new(),
+                player_ids: Vec::new(),
+                title,
+            })
+        })
+        .collect()
+}
+
+/// Tag stories with the team id and any rostered player named in them
+pub fn tag_roster(stories: &mut [NewsStory], team: &str, roster: &[SportsDataPlayer]) {
+    let team_id = roster.iter().find_map(|player| player.team_id);
+    let names: Vec<(i32, String)> = roster
+        .iter()
+        .filter(|player| player.first_name.is_some() && player.last_name.is_some())
+        .map(|player| (player.player_id, player.full_name().to_lowercase()))
+        .collect();
+
+    for story in stories.iter_mut() {
+        if let Some(team_id) = team_id {
+            if story.mentions_team(team) && !story.team_ids.contains(&team_id) {
+                story.team_ids.push(team_id);
+            }
+        }
+
+        let text = format!(
+            "{} {}",
+            story.title,
+            story.summary.as_deref().unwrap_or_default()
+        )
+        .to_lowercase();
+        for (player_id, name) in &names {
+            if text.contains(name.as_str()) && !story.player_ids.contains(player_id) {
+                story.player_ids.push(*player_id);
+            }
+        }
+    }
+}
+
+/// Canonical form of a story link: no scheme, `www.`, query or trailing slash
+fn url_key(url: &str) -> String {
+    let url = url.trim().to_lowercase();
+    let url = url
+        .split_once("://")
+        .map(|(_, rest)| rest.to_string())
+        .unwrap_or(url);
+    let url = url.strip_prefix("www.").unwrap_or(&url);
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    url.trim_end_matches('/').to_string()
+}
+
+fn title_key(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Merge duplicate stories and order newest first
+///
+/// Two stories are the same if they link to the same page or share a headline;
+/// the first one seen is kept and picks up the other's team and player tags.
+pub fn dedupe(stories: Vec<NewsStory>) -> Vec<NewsStory> {
+    let mut unique: Vec<NewsStory> = Vec::new();
+    let mut seen: Vec<(Option<String>, String)> = Vec::new();
+
+    for story in stories {
+        let url = story.url.as_deref().map(url_key);
+        let title = title_key(&story.title);
+        let duplicate_of = seen.iter().position(|(seen_url, seen_title)| {
+            (url.is_some() && *seen_url == url)
+                || (!title.is_empty() && *seen_title == title)
+        });
+
+        match duplicate_of {
+            Some(index) => {
+                let kept = &mut unique[index];
+                for team in story.teams {
+                    if !kept.mentions_team(&team) {
+                        kept.teams.push(team);
+                    }
+                }
+                let mut team_ids: HashSet<i32> = kept.team_ids.iter().copied().collect();
+                kept.team_ids
+                    .extend(story.team_ids.into_iter().filter(|id| team_ids.insert(*id)));
+                let mut player_ids: HashSet<i32> =
+                    kept.player_ids.iter().copied().collect();
+                kept.player_ids.extend(
+                    story
+                        .player_ids
+                        .into_iter()
+                        .filter(|id| player_ids.insert(*id)),
+                );
+                if kept.kind == NewsKind::News {
+                    kept.kind = story.kind;
+                }
+            }
+            None => {
+                seen.push((url, title));
+                unique.push(story);
+            }
+        }
+    }
+
+    // RFC 3339 UTC strings sort chronologically; undated stories go last
+    unique.sort_by(|a, b| b.published.cmp(&a.published));
+    unique
+}
+
+/// One page of a team's news feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewsPage {
+    #[serde(flatten)]
+    pub paging: Paging,
+    pub stories: Vec<NewsStory>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::pagination::paginate;
+
+    fn story(id: &str, title: &str, url: &str, published: &str) -> NewsStory {
+        NewsStory {
+            id: id.to_string(),
+            kind: classify(title, None),
+            title: title.to_string(),
+            summary: None,
+            url: Some(url.to_string()),
+            source: "test".to_string(),
+            author: None,
+            published: Some(published.to_string()),
+            teams: vec!["NYY".to_string()],
+            team_ids: Vec::new(),
+            player_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_classify_transactions() {
+        assert_eq!(
+            classify("Yankees place Cole on 15-day IL", None),
+            NewsKind::InjuredList
+        );
+        assert_eq!(
+            classify("Phillies acquire reliever from Marlins", None),
+            NewsKind::Trade
+        );
+        assert_eq!(
+            classify("Judge, Yankees agree to extension", None),
+            NewsKind::Signing
+        );
+        assert_eq!(classify("Designated hitter debate", None), NewsKind::News);
+        assert_eq!(
+            provider_timestamp("2025-07-04T12:30:00").as_deref(),
+            Some("2025-07-04T16:30:00+00:00")
+        );
+        assert_eq!(
+            provider_timestamp("2025-01-04T12:30:00").as_deref(),
+            Some("2025-01-04T17:30:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_dedupe_merges_tags_and_orders_newest_first() {
+        let mut duplicate = story(
+            "b",
+            "Yankees acquire reliever",
+            "http://mlb.com/news/reliever/?utm=rss",
+            "2025-07-02T00:00:00+00:00",
+        );
+        duplicate.player_ids.push(7);
+        let stories = vec![
+            story(
+                "a",
+                "Yankees acquire reliever",
+                "https://www.mlb.com/news/reliever",
+                "2025-07-01T00:00:00+00:00",
+            ),
+            duplicate,
+            story(
+                "c",
+                "Game recap",
+                "https://mlb.com/news/recap",
+                "2025-07-03T00:00:00+00:00",
+            ),
+        ];
+
+        let unique = dedupe(stories);
+        assert_eq!(unique.len(), 2);
+        assert_eq!(unique[0].id, "c");
+        assert_eq!(unique[1].player_ids, [7]);
+
+        let (paging, page) = paginate(&unique, 2, 1, 20);
+        assert_eq!((paging.total, paging.has_more), (2, false));
+        assert_eq!(page[0].id, "a");
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a page sits in its list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Paging {
+    pub page: usize,
+    pub page_size: usize,
+    pub total: usize,
+    pub has_more: bool,
+}
+
+/// Slice out a 1-based page, with the page size clamped to `1..=max_page_size`
+pub fn paginate<T: Clone>(
+    items: &[T],
+    page: usize,
+    page_size: usize,
+    max_page_size: usize,
+) -> (Paging, Vec<T>) {
+    let page = page.max(1);
+    let page_size = page_size.clamp(1, max_page_size.max(1));
+    let start = (page - 1).saturating_mul(page_size).min(items.len());
+    let end = start.saturating_add(page_size).min(items.len());
+
+    let paging = Paging {
+        page,
+        page_size,
+        total: items.len(),
+        has_more: end < items.len(),
+    };
+    (paging, items[start..end].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_size_is_clamped() {
+        let items: Vec<usize> = (0..250).collect();
+        let (paging, page) = paginate(&items, 1, 10_000, 100);
+        assert_eq!(
+            (paging.page_size, page.len(), paging.has_more),
+            (100, 100, true)
+        );
+
+        let (paging, page) = paginate(&items, 3, 0, 100);
+        assert_eq!((paging.page_size, page), (1, vec![2]));
+
+        let (paging, page) = paginate(&items, 9, 100, 100);
+        assert!(page.is_empty() && !paging.has_more);
+    }
+}
@@ -0,0 +1,350 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{
+    schema::sportradar::player::{PlayerGameStat, SportsDataPlayer},
+    services::stats::{numeric_stats, StatLine},
+};
+
+/// Oldest season a career lookup will reach back to
+const MAX_CAREER_SEASONS: i32 = 25;
+
+/// Season stat lookups a career request has in flight at once
+pub const CAREER_SEASON_FETCHES: usize = 4;
+
+/// Provider stats that add up across games and seasons, for MLB, NFL and NBA
+///
+/// Anything else (rates, averages, ids, jersey numbers) can't be summed.
+const COUNTING_STATS: &[&str] = &[
+    // Shared
+    "Games",
+    "Started",
+    "Minutes",
+    "FantasyPoints",
+    // MLB batting
+    "AtBats",
+    "PlateAppearances",
+    "Runs",
+    "Hits",
+    "Singles",
+    "Doubles",
+    "Triples",
+    "HomeRuns",
+    "RunsBattedIn",
+    "TotalBases",
+    "Walks",
+    "IntentionalWalks",
+    "HitByPitch",
+    "Strikeouts",
+    "StolenBases",
+    "CaughtStealing",
+    "Sacrifices",
+    "SacrificeFlies",
+    "GroundIntoDoublePlay",
+    "Errors",
+    // MLB pitching
+    "Wins",
+    "Losses",
+    "Saves",
+    "BlownSaves",
+    "Holds",
+    "PitchingInningsPitchedOuts",
+    "PitchingHits",
+    "PitchingRuns",
+    "PitchingEarnedRuns",
+    "PitchingWalks",
+    "PitchingStrikeouts",
+    "PitchingHomeRuns",
+    "PitchingBattersFaced",
+    "PitchingCompleteGames",
+    "PitchingShutOuts",
+    // NFL
+    "PassingAttempts",
+    "PassingCompletions",
+    "PassingYards",
+    "PassingTouchdowns",
+    "PassingInterceptions",
+    "PassingSacks",
+    "PassingSackYards",
+    "RushingAttempts",
+    "RushingYards",
+    "RushingTouchdowns",
+    "ReceivingTargets",
+    "Receptions",
+    "ReceivingYards",
+    "ReceivingTouchdowns",
+    "Fumbles",
+    "FumblesLost",
+    "FumblesRecovered",
+    "Tackles",
+    "SoloTackles",
+    "AssistedTackles",
+    "Sacks",
+    "Interceptions",
+    "PassesDefended",
+    "FieldGoalsMade",
+    "FieldGoalsAttempted",
+    "ExtraPointsMade",
+    "ExtraPointsAttempted",
+    "Punts",
+    "PuntYards",
+    "KickReturns",
+    "KickReturnYards",
+    "PuntReturns",
+    "PuntReturnYards",
+    // NBA (field goals are shared with NFL kickers above)
+    "TwoPointersMade",
+    "TwoPointersAttempted",
+    "ThreePointersMade",
+    "ThreePointersAttempted",
+    "FreeThrowsMade",
+    "FreeThrowsAttempted",
+    "OffensiveRebounds",
+    "DefensiveRebounds",
+    "Rebounds",
+    "Assists",
+    "Steals",
+    "BlockedShots",
+    "Turnovers",
+    "PersonalFouls",
+    "Points",
+    "DoubleDoubles",
+    "TripleDoubles",
+];
+
+/// One game in a player's game log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameLogEntry {
+    pub game_id: String,
+    pub day: String,
+    pub team: String,
+    pub opponent: String,
+    /// `None` when the provider doesn't say (e.g. neutral site games)
+    pub home: Option<bool>,
+    pub started: Option<bool>,
+    pub stats: BTreeMap<String, f64>,
+}
+
+impl From<&PlayerGameStat> for GameLogEntry {
+    fn from(game: &PlayerGameStat) -> Self {
+        let game_id = game
+            .game_id
+            .map(|id| id.to_string())
+            .or_else(|| game.game_key.clone())
+            .unwrap_or_default();
+        let day = game
+            .day
+            .clone()
+            .or_else(|| game.game_date.clone())
+            .or_else(|| game.date_time.clone())
+            .unwrap_or_default();
+        let home = game.home_or_away.as_deref().and_then(|venue| {
+            match venue.to_uppercase().as_str() {
+                "HOME" => Some(true),
+                "AWAY" => Some(false),
+                _ => None,
+            }
+        });
+
+        Self {
+            game_id,
+            day,
+            team: game.team.clone().unwrap_or_default(),
+            opponent: game.opponent.clone().unwrap_or_default(),
+            home,
+            started: game.started.map(|started| started > 0),
+            stats: numeric_stats(&game.stats),
+        }
+    }
+}
+
+/// Game log ordered oldest to newest
+pub fn game_log(games: &[PlayerGameStat]) -> Vec<GameLogEntry> {
+    let mut entries: Vec<GameLogEntry> = games.iter().map(GameLogEntry::from).collect();
+    entries.sort_by(|a, b| a.day.cmp(&b.day));
+    entries
+}
+
+/// Totals and per-game averages over a subset of games
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Split {
+    pub label: String,
+    pub games: u32,
+    pub totals: BTreeMap<String, f64>,
+    pub per_game: BTreeMap<String, f64>,
+}
+
+impl Split {
+    fn from_games<'a>(
+        label: impl Into<String>,
+        games: impl Iterator<Item = &'a GameLogEntry>,
+    ) -> Self {
+        let games: Vec<&GameLogEntry> = games.collect();
+        let totals = counting_totals(games.iter().map(|game| &game.stats));
+        let count = games.len() as u32;
+        let per_game = totals
+            .iter()
+            .map(|(key, total)| (key.clone(), total / count.max(1) as f64))
+            .collect();
+
+        Self {
+            label: label.into(),
+            games: count,
+            totals,
+            per_game,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSplits {
+    pub home: Split,
+    pub away: Split,
+    /// One split per calendar month played, in order (`YYYY-MM`)
+    pub months: Vec<Split>,
+}
+
+/// Home/away and month splits from a game log
+pub fn splits(entries: &[GameLogEntry]) -> PlayerSplits {
+    let mut months: BTreeMap<String, Vec<&GameLogEntry>> = BTreeMap::new();
+    for entry in entries {
+        if entry.day.len() >= 7 {
+            months
+                .entry(entry.day[..7].to_string())
+                .or_default()
+                .push(entry);
+        }
+    }
+
+    PlayerSplits {
+        home: Split::from_games("home", entries.iter().filter(|e| e.home == Some(true))),
+        away: Split::from_games("away", entries.iter().filter(|e| e.home == Some(false))),
+        months: months
+            .into_iter()
+            .map(|(month, games)| Split::from_games(month, games.into_iter()))
+            .collect(),
+    }
+}
+
+/// Sum the counting stats across lines
+///
+/// Only stats in [`COUNTING_STATS`] are summed; rates such as batting average
+/// and numbers such as the season or a jersey number are left out.
+pub fn counting_totals<'a>(
+    lines: impl Iterator<Item = &'a BTreeMap<String, f64>>,
+) -> BTreeMap<String, f64> {
+    let mut totals: BTreeMap<String, f64> = BTreeMap::new();
+    for stats in lines {
+        for (key, value) in stats {
+            if COUNTING_STATS.contains(&key.as_str()) {
+                *totals.entry(key.clone()).or_insert(0.0) += value;
+            }
+        }
+    }
+    totals
+}
+
+/// One season in a player's career
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CareerSeason {
+    pub season: String,
+    pub team: String,
+    pub games: f64,
+    pub stats: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CareerTotals {
+    pub seasons: Vec<CareerSeason>,
+    pub games: f64,
+    pub totals: BTreeMap<String, f64>,
+}
+
+/// Career totals from per-season stat lines, ordered oldest season first
+pub fn career_totals(mut seasons: Vec<(String, StatLine)>) -> CareerTotals {
+    seasons.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let totals = counting_totals(seasons.iter().map(|(_, line)| &line.stats));
+
+    CareerTotals {
+        games: seasons.iter().map(|(_, line)| line.games).sum(),
+        seasons: seasons
+            .into_iter()
+            .map(|(season, line)| CareerSeason {
+                season,
+                team: line.team,
+                games: line.games,
+                stats: line.stats,
+            })
+            .collect(),
+        totals,
+    }
+}
+
+/// First season to look up for a player's career
+///
+/// Uses the pro debut year when the league sends one (MLB), otherwise years of
+/// experience (NFL, NBA), capped at [`MAX_CAREER_SEASONS`].
+pub fn career_start(player: &SportsDataPlayer, current_season: i32) -> i32 {
+    let debut = player
+        .pro_debut
+        .as_deref()
+        .and_then(|debut| debut.get(..4))
+        .and_then(|year| year.parse::<i32>().ok());
+    let experience = player
+        .experience
+        .as_ref()
+        .and_then(|experience| match experience {
+            serde_json::Value::Number(years) => years.as_i64(),
+            serde_json::Value::String(years) => years.trim().parse::<i64>().ok(),
+            _ => None,
+        });
+
+    let start = debut
+        .or_else(|| experience.map(|years| current_season - years as i32))
+        .unwrap_or(current_season);
+    start.clamp(current_season - MAX_CAREER_SEASONS + 1, current_season)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn games() -> Vec<PlayerGameStat> {
+        serde_json::from_value(serde_json::json!([
+            {"GameID": 2, "Day": "2025-05-02T00:00:00", "Team": "NYY", "Opponent": "BOS",
+             "HomeOrAway": "AWAY", "Games": 1, "Hits": 1, "BattingAverage": 0.25},
+            {"GameID": 1, "Day": "2025-04-30T00:00:00", "Team": "NYY", "Opponent": "BOS",
+             "HomeOrAway": "HOME", "Games": 1, "Hits": 3, "BattingAverage": 0.6},
+            {"GameID": 3, "Day": "2025-05-03T00:00:00", "Team": "NYY", "Opponent": "TB",
+             "HomeOrAway": "HOME", "Games": 1, "Hits": 0, "BattingAverage": 0.0, "PlayerID": 7,
+             "Updated": 20250503}
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_game_log_orders_by_day() {
+        let log = game_log(&games());
+        assert_eq!(log[0].game_id, "1");
+        assert_eq!(log[0].home, Some(true));
+        assert!(!log[2].stats.contains_key("PlayerID"));
+    }
+
+    #[test]
+    fn test_splits_sum_counting_stats_only() {
+        let splits = splits(&game_log(&games()));
+        assert_eq!(splits.home.games, 2);
+        assert_eq!(splits.home.totals.get("Hits"), Some(&3.0));
+        assert!(!splits.home.totals.contains_key("BattingAverage"));
+        assert!(!splits.home.totals.contains_key("Updated"));
+        assert_eq!(splits.away.per_game.get("Hits"), Some(&1.0));
+        assert_eq!(
+            splits
+                .months
+                .iter()
+                .map(|m| m.label.as_str())
+                .collect::<Vec<_>>(),
+            ["2025-04", "2025-05"]
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::{path::League, services::moderation::Strictness};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+/// Which push notifications the user wants
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    /// A favorite team's game is starting
+    #[serde(default)]
+    pub game_start: bool,
+    /// A favorite team's game has ended
+    #[serde(default)]
+    pub final_score: bool,
+    /// News mentioning a favorite team
+    #[serde(default)]
+    pub news: bool,
+}
+
+/// A signed-in user's settings, stored by user id
+///
+/// Every field is optional so unset ones fall back to defaults and partial
+/// updates can tell "leave alone" from "set".
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserPreferences {
+    /// Social feed moderation; the configured default applies when unset
+    #[serde(default)]
+    pub moderation: Option<Strictness>,
+    /// Leagues shown first, e.g. `["mlb", "nfl"]`
+    #[serde(default)]
+    pub preferred_leagues: Option<Vec<String>>,
+    #[serde(default)]
+    pub theme: Option<Theme>,
+    /// IANA time zone, e.g. "America/New_York"; UTC when unset
+    #[serde(default)]
+    pub time_zone: Option<String>,
+    #[serde(default)]
+    pub notifications: Option<NotificationSettings>,
+}
+
+impl UserPreferences {
+    /// Fields set here, the rest from `fallback`
+    fn or(self, fallback: UserPreferences) -> Self {
+        Self {
+            moderation: self.moderation.or(fallback.moderation),
+            preferred_leagues: self.preferred_leagues.or(fallback.preferred_leagues),
+            theme: self.theme.or(fallback.theme),
+            time_zone: self.time_zone.or(fallback.time_zone),
+            notifications: self.notifications.or(fallback.notifications),
+        }
+    }
+
+    /// Fold a guest's settings into the account they signed in to: anything
+    /// the account already set wins, the guest's choices fill the gaps
+    pub fn merge_guest(self, guest: UserPreferences) -> Self {
+        self.or(guest)
+    }
+
+    /// Apply a partial update: fields set in `changes` replace the stored ones
+    pub fn update(self, changes: UserPreferences) -> Self {
+        changes.or(self)
+    }
+
+    /// Check leagues and time zone, lowercasing league names
+    pub fn validate(mut self) -> Result<Self, String> {
+        if let Some(leagues) = self.preferred_leagues.as_mut() {
+            for league in leagues.iter_mut() {
+                *league = league.trim().to_lowercase();
+                league
+                    .parse::<League>()
+                    .map_err(|_| format!("Unknown league: {}", league))?;
+            }
+            let mut seen = HashSet::new();
+            leagues.retain(|league| seen.insert(league.clone()));
+        }
+        if let Some(time_zone) = self.time_zone.as_deref() {
+            time_zone
+                .parse::<Tz>()
+                .map_err(|_| format!("Unknown time zone: {}", time_zone))?;
+        }
+        Ok(self)
+    }
+
+    /// The user's time zone, UTC when unset or no longer known
+    pub fn tz(&self) -> Tz {
+        self.time_zone
+            .as_deref()
+            .and_then(|time_zone| time_zone.parse().ok())
+            .unwrap_or(Tz::UTC)
+    }
+}
+
+/// A team the user follows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FavoriteTeam {
+    pub league: String,
+    /// Team key, e.g. "CIN"
+    pub team: String,
+    /// When it was added, as a Unix timestamp
+    pub added_at: i64,
+}
+
+impl FavoriteTeam {
+    fn same_team(&self, other: &FavoriteTeam) -> bool {
+        self.league.eq_ignore_ascii_case(&other.league)
+            && self.team.eq_ignore_ascii_case(&other.team)
+    }
+}
+
+/// A user's favorite teams, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserFavorites {
+    pub teams: Vec<FavoriteTeam>,
+}
+
+impl UserFavorites {
+    /// Add a team unless it's already a favorite; returns whether it was added
+    pub fn add(&mut self, favorite: FavoriteTeam) -> bool {
+        if self.teams.iter().any(|team| team.same_team(&favorite)) {
+            return false;
+        }
+        self.teams.push(favorite);
+        true
+    }
+
+    /// Remove a team; returns whether it was a favorite
+    pub fn remove(&mut self, league: &str, team: &str) -> bool {
+        let before = self.teams.len();
+        self.teams.retain(|favorite| {
+            !(favorite.league.eq_ignore_ascii_case(league)
+                && favorite.team.eq_ignore_ascii_case(team))
+        });
+        self.teams.len() != before
+    }
+
+    /// Teams followed in `league`
+    pub fn teams_in(&self, league: &str) -> Vec<String> {
+        self.teams
+            .iter()
+            .filter(|favorite| favorite.league.eq_ignore_ascii_case(league))
+            .map(|favorite| favorite.team.clone())
+            .collect()
+    }
+
+    /// Union of both lists; a team on both keeps its earlier `added_at`, so
+    /// the result doesn't depend on which side is merged into which
+    pub fn merge_guest(self, guest: UserFavorites) -> Self {
+        let mut teams: Vec<FavoriteTeam> = Vec::new();
+        for favorite in self.teams.into_iter().chain(guest.teams) {
+            match teams.iter_mut().find(|team| team.same_team(&favorite)) {
+                Some(team) => team.added_at = team.added_at.min(favorite.added_at),
+                None => teams.push(favorite),
+            }
+        }
+        teams.sort_by(|a, b| {
+            (a.added_at, &a.league, &a.team).cmp(&(b.added_at, &b.league, &b.team))
+        });
+        Self { teams }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn favorite(league: &str, team: &str, added_at: i64) -> FavoriteTeam {
+        FavoriteTeam {
+            league: league.to_string(),
+            team: team.to_string(),
+            added_at,
+        }
+    }
+
+    #[test]
+    fn test_guest_merge_is_deterministic() {
+        let account = UserFavorites {
+            teams: vec![favorite("mlb", "CIN", 20), favorite("nba", "BOS", 30)],
+        };
+        let guest = UserFavorites {
+            teams: vec![favorite("MLB", "cin", 10), favorite("nfl", "CIN", 25)],
+        };
+        let merged = account.clone().merge_guest(guest.clone());
+        assert_eq!(
+            merged.teams,
+            vec![
+                favorite("mlb", "CIN", 10),
+                favorite("nfl", "CIN", 25),
+                favorite("nba", "BOS", 30),
+            ]
+        );
+        assert_eq!(guest.merge_guest(account).teams.len(), 3);
+
+        let account = UserPreferences {
+            moderation: Some(Strictness::Strict),
+            ..Default::default()
+        };
+        let guest = UserPreferences {
+            moderation: Some(Strictness::Off),
+            theme: Some(Theme::Dark),
+            ..Default::default()
+        };
+        assert_eq!(
+            account.merge_guest(guest.clone()).moderation,
+            Some(Strictness::Strict)
+        );
+        assert_eq!(
+            UserPreferences::default().merge_guest(guest).moderation,
+            Some(Strictness::Off)
+        );
+    }
+
+    #[test]
+    fn test_update_and_validate() {
+        let stored = UserPreferences {
+            theme: Some(Theme::Dark),
+            time_zone: Some("America/Chicago".to_string()),
+            ..Default::default()
+        };
+        let changes = UserPreferences {
+            preferred_leagues: Some(vec!["MLB".to_string(), "mlb".to_string()]),
+            ..Default::default()
+        };
+        let updated = stored.update(changes).validate().unwrap();
+        assert_eq!(updated.theme, Some(Theme::Dark));
+        assert_eq!(updated.preferred_leagues, Some(vec!["mlb".to_string()]));
+        assert_eq!(updated.tz(), chrono_tz::America::Chicago);
+
+        let bad = UserPreferences {
+            time_zone: Some("Mars/Olympus".to_string()),
+            ..Default::default()
+        };
+        assert!(bad.validate().is_err());
+        assert_eq!(UserPreferences::default().tz(), Tz::UTC);
+    }
+}
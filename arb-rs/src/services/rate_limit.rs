@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{config::RateLimitConfig, services::auth::random_token};
+
+/// Quota tier of a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiTier {
+    /// No API key, limited per IP
+    Anonymous,
+    /// An app's API key
+    #[default]
+    App,
+    /// Operators; may issue and revoke keys
+    Admin,
+}
+
+impl fmt::Display for ApiTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiTier::Anonymous => write!(f, "anonymous"),
+            ApiTier::App => write!(f, "app"),
+            ApiTier::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl ApiTier {
+    /// Requests allowed per window
+    pub fn limit(&self, config: &RateLimitConfig) -> u64 {
+        match self {
+            ApiTier::Anonymous => config.anonymous_requests,
+            ApiTier::App => config.app_requests,
+            ApiTier::Admin => config.admin_requests,
+        }
+    }
+}
+
+/// An issued API key, stored by the hash of the key itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Public id, used to revoke the key and in logs
+    pub key_id: String,
+    pub name: String,
+    pub tier: ApiTier,
+    pub created_at: i64,
+}
+
+/// A new secret API key; only its hash is stored
+pub fn generate_api_key() -> String {
+    format!("arb_{}", random_token())
+}
+
+/// Where a request stands against its quota in the current window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    pub limit: u64,
+    pub remaining: u64,
+    /// Seconds until the window resets
+    pub reset: u64,
+}
+
+impl RateLimitStatus {
+    /// Status after the `count`th request of a fixed window starting at a
+    /// multiple of `window_seconds`
+    pub fn after(count: u64, limit: u64, now: u64, window_seconds: u64) -> Self {
+        let window_seconds = window_seconds.max(1);
+        Self {
+            limit,
+            remaining: limit.saturating_sub(count),
+            reset: window_seconds - now % window_seconds,
+        }
+    }
+
+    pub fn exceeded(&self, count: u64) -> bool {
+        count > self.limit
+    }
+}
+
+/// Start of the fixed window `now` falls in, used to name its counter
+pub fn window_start(now: u64, window_seconds: u64) -> u64 {
+    now - now % window_seconds.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_window_status() {
+        assert_eq!(window_start(125, 60), 120);
+
+        let status = RateLimitStatus::after(1, 3, 125, 60);
+        assert_eq!(
+            status,
+            RateLimitStatus {
+                limit: 3,
+                remaining: 2,
+                reset: 55
+            }
+        );
+        assert!(!status.exceeded(3));
+        assert!(status.exceeded(4));
+        assert_eq!(RateLimitStatus::after(9, 3, 179, 60).remaining, 0);
+        assert_eq!(RateLimitStatus::after(9, 3, 179, 60).reset, 1);
+    }
+}
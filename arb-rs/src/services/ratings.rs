@@ -0,0 +1,379 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+
+use crate::{path::League, services::standings::ScheduledGame};
+
+/// Rating every team starts from before any games are replayed
+pub const BASE_RATING: f64 = 1500.0;
+
+/// Per-league Elo constants
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloParams {
+    /// K-factor applied to each final
+    pub k_factor: f64,
+    /// Home field advantage in rating points
+    pub home_advantage: f64,
+    /// Share of each team's distance from the mean dropped between seasons
+    pub season_regression: f64,
+}
+
+impl EloParams {
+    pub fn for_league(league: &League) -> Option<Self> {
+        match league {
+            League::Mlb => Some(Self {
+                k_factor: 4.0,
+                home_advantage: 24.0,
+                season_regression: 1.0 / 3.0,
+            }),
+            League::Nfl => Some(Self {
+                k_factor: 20.0,
+                home_advantage: 48.0,
+                season_regression: 1.0 / 3.0,
+            }),
+            League::Nba => Some(Self {
+                k_factor: 20.0,
+                home_advantage: 100.0,
+                season_regression: 1.0 / 4.0,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Probability of winning given a rating difference (home advantage included)
+pub fn win_probability(rating_diff: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-rating_diff / 400.0))
+}
+
+/// Rating points the home team gains (negative when it loses them) from a final
+///
+/// Wins are scaled by a margin-of-victory multiplier, damped when the favorite
+/// wins, so blowouts move ratings more than one-run or one-point games.
+pub fn rating_shift(
+    params: EloParams,
+    home_rating: f64,
+    away_rating: f64,
+    home_score: i32,
+    away_score: i32,
+) -> f64 {
+    let diff = home_rating + params.home_advantage - away_rating;
+    let expected = win_probability(diff);
+    let actual = match home_score.cmp(&away_score) {
+        Ordering::Greater => 1.0,
+        Ordering::Less => 0.0,
+        Ordering::Equal => 0.5,
+    };
+
+    let winner_diff = if actual >= 0.5 { diff } else { -diff };
+    let margin = (home_score - away_score).abs() as f64;
+    let multiplier = (margin + 1.0).ln() * 2.2 / (winner_diff * 0.001 + 2.2);
+    params.k_factor * multiplier.max(1.0) * (actual - expected)
+}
+
+/// Current rating for one team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamRating {
+    pub team: String,
+    pub rating: f64,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    /// Rating change from the team's most recent final
+    pub last_change: f64,
+    pub last_game_day: Option<String>,
+}
+
+/// One entry in a team's rating history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingPoint {
+    pub game_id: String,
+    pub day: String,
+    pub opponent: String,
+    pub home: bool,
+    pub points_for: i32,
+    pub points_against: i32,
+    pub rating_before: f64,
+    pub rating_after: f64,
+}
+
+/// Pre-game win probabilities for a matchup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GamePrediction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+    pub home: String,
+    pub away: String,
+    pub home_rating: f64,
+    pub away_rating: f64,
+    pub home_win_probability: f64,
+    pub away_win_probability: f64,
+}
+
+/// Rolling Elo ratings for one league season, persisted between updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingsBook {
+    pub league: String,
+    pub season: i32,
+    pub ratings: BTreeMap<String, TeamRating>,
+    pub history: BTreeMap<String, Vec<RatingPoint>>,
+    /// Game ids already applied, so re-reading a final never double counts it
+    processed: HashSet<String>,
+    pub updated_at: String,
+}
+
+impl RatingsBook {
+    pub fn new(league: &League, season: i32) -> Self {
+        Self {
+            league: league.to_string(),
+            season,
+            ratings: BTreeMap::new(),
+            history: BTreeMap::new(),
+            processed: HashSet::new(),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Start a new season from last season's ratings, regressed toward the mean
+    pub fn carry_over(previous: &RatingsBook, season: i32, params: EloParams) -> Self {
+        let mut book = Self {
+            league: previous.league.clone(),
+            season,
+            ratings: BTreeMap::new(),
+            history: BTreeMap::new(),
+            processed: HashSet::new(),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        };
+        for (team, rating) in &previous.ratings {
+            let regressed =
+                rating.rating - (rating.rating - BASE_RATING) * params.season_regression;
+            book.ratings.insert(
+                team.clone(),
+                TeamRating {
+                    team: team.clone(),
+                    rating: regressed,
+                    games: 0,
+                    wins: 0,
+                    losses: 0,
+                    last_change: 0.0,
+                    last_game_day: None,
+                },
+            );
+        }
+        book
+    }
+
+    pub fn games_processed(&self) -> usize {
+        self.processed.len()
+    }
+
+    pub fn rating(&self, team: &str) -> f64 {
+        self.ratings
+            .get(team)
+            .map(|rating| rating.rating)
+            .unwrap_or(BASE_RATING)
+    }
+
+    /// Current ratings keyed by team
+    pub fn ratings_map(&self) -> HashMap<String, f64> {
+        self.ratings
+            .iter()
+            .map(|(team, rating)| (team.clone(), rating.rating))
+            .collect()
+    }
+
+    /// Apply one final; returns `false` if it isn't final or was already applied
+    pub fn apply(&mut self, game: &ScheduledGame, params: EloParams) -> bool {
+        let (Some(home_score), Some(away_score)) = (game.home_score, game.away_score)
+        else {
+            return false;
+        };
+        if !game.is_final {
+            return false;
+        }
+        if !game.game_id.is_empty() && !self.processed.insert(game.game_id.clone()) {
+            return false;
+        }
+
+        let home_before = self.rating(&game.home);
+        let away_before = self.rating(&game.away);
+        let shift =
+            rating_shift(params, home_before, away_before, home_score, away_score);
+
+        self.record(game, &game.home, &game.away, true, home_before, shift);
+        self.record(game, &game.away, &game.home, false, away_before, -shift);
+        true
+    }
+
+    /// Apply every final in day order, returning how many were new
+    pub fn apply_all(&mut self, games: &[ScheduledGame], params: EloParams) -> usize {
+        let mut finals: Vec<&ScheduledGame> =
+            games.iter().filter(|g| g.is_final).collect();
+        finals.sort_by(|a, b| a.day.cmp(&b.day).then_with(|| a.game_id.cmp(&b.game_id)));
+
+        let applied = finals
+            .into_iter()
+            .filter(|game| self.apply(game, params))
+            .count();
+        if applied > 0 {
+            self.updated_at = chrono::Utc::now().to_rfc3339();
+        }
+        applied
+    }
+
+    /// Teams ordered from highest to lowest rating
+    pub fn leaderboard(&self) -> Vec<TeamRating> {
+        let mut ratings: Vec<TeamRating> = self.ratings.values().cloned().collect();
+        ratings.sort_by(|a, b| {
+            b.rating
+                .partial_cmp(&a.rating)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.team.cmp(&b.team))
+        });
+        ratings
+    }
+
+    pub fn history(&self, team: &str) -> Vec<RatingPoint> {
+        self.history.get(team).cloned().unwrap_or_default()
+    }
+
+    pub fn predict(&self, home: &str, away: &str, params: EloParams) -> GamePrediction {
+        let home_rating = self.rating(home);
+        let away_rating = self.rating(away);
+        let home_win_probability =
+            win_probability(home_rating + params.home_advantage - away_rating);
+
+        GamePrediction {
+            game_id: None,
+            home: home.to_string(),
+            away: away.to_string(),
+            home_rating,
+            away_rating,
+            home_win_probability,
+            away_win_probability: 1.0 - home_win_probability,
+        }
+    }
+
+    fn record(
+        &mut self,
+        game: &ScheduledGame,
+        team: &str,
+        opponent: &str,
+        home: bool,
+        before: f64,
+        shift: f64,
+    ) {
+        let (points_for, points_against) = if home {
+            (game.home_score.unwrap_or(0), game.away_score.unwrap_or(0))
+        } else {
+            (game.away_score.unwrap_or(0), game.home_score.unwrap_or(0))
+        };
+
+        let rating = self
+            .ratings
+            .entry(team.to_string())
+            .or_insert_with(|| TeamRating {
+                team: team.to_string(),
+                rating: BASE_RATING,
+                games: 0,
+                wins: 0,
+                losses: 0,
+                last_change: 0.0,
+                last_game_day: None,
+            });
+        rating.rating = before + shift;
+        rating.games += 1;
+        match points_for.cmp(&points_against) {
+            Ordering::Greater => rating.wins += 1,
+            Ordering::Less => rating.losses += 1,
+            Ordering::Equal => {}
+        }
+        rating.last_change = shift;
+        rating.last_game_day = Some(game.day.clone());
+
+        self.history
+            .entry(team.to_string())
+            .or_default()
+            .push(RatingPoint {
+                game_id: game.game_id.clone(),
+                day: game.day.clone(),
+                opponent: opponent.to_string(),
+                home,
+                points_for,
+                points_against,
+                rating_before: before,
+                rating_after: before + shift,
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn final_game(
+        id: &str,
+        day: &str,
+        home: &str,
+        away: &str,
+        score: (i32, i32),
+    ) -> ScheduledGame {
+        ScheduledGame {
+            game_id: id.to_string(),
+            day: day.to_string(),
+            date_time: None,
+            home: home.to_string(),
+            away: away.to_string(),
+            home_score: Some(score.0),
+            away_score: Some(score.1),
+            is_final: true,
+        }
+    }
+
+    #[test]
+    fn test_winner_gains_what_loser_drops() {
+        let params = EloParams::for_league(&League::Nfl).unwrap();
+        let mut book = RatingsBook::new(&League::Nfl, 2025);
+        book.apply_all(
+            &[final_game("1", "2025-09-07", "KC", "DEN", (31, 10))],
+            params,
+        );
+
+        assert!(book.rating("KC") > BASE_RATING);
+        assert!(
+            (book.rating("KC") + book.rating("DEN") - 2.0 * BASE_RATING).abs() < 1e-9
+        );
+        assert_eq!(book.history("DEN")[0].opponent, "KC");
+    }
+
+    #[test]
+    fn test_finals_are_applied_once() {
+        let params = EloParams::for_league(&League::Mlb).unwrap();
+        let mut book = RatingsBook::new(&League::Mlb, 2025);
+        let games = vec![
+            final_game("2", "2025-04-02", "NYY", "BOS", (2, 3)),
+            final_game("1", "2025-04-01", "NYY", "BOS", (5, 1)),
+        ];
+
+        assert_eq!(book.apply_all(&games, params), 2);
+        assert_eq!(book.apply_all(&games, params), 0);
+        assert_eq!(book.games_processed(), 2);
+        assert_eq!(book.history("NYY")[0].game_id, "1");
+    }
+
+    #[test]
+    fn test_prediction_includes_home_advantage() {
+        let params = EloParams::for_league(&League::Nba).unwrap();
+        let book = RatingsBook::new(&League::Nba, 2026);
+        let prediction = book.predict("BOS", "LAL", params);
+
+        assert!(prediction.home_win_probability > 0.5);
+        assert!(
+            (prediction.home_win_probability + prediction.away_win_probability - 1.0)
+                .abs()
+                < 1e-9
+        );
+    }
+}
@@ -0,0 +1,487 @@
+use reqwest::{header::HeaderMap, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+use crate::{
+    config::RedditOAuthConfig,
+    error::{Error, Result},
+    schema::reddit::{RedditChild, RedditListingData, RedditReplies, RedditResponse},
+    services::usage::{metered, Upstream, UpstreamUsage},
+};
+
+const TOKEN_URL: &str = "https://www.reddit.com/api/v1/access_token";
+const OAUTH_BASE_URL: &str = "https://oauth.reddit.com";
+
+/// Tokens are refreshed this long before Reddit says they expire
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+/// Comment ids Reddit expands per `morechildren` call
+const MORE_CHILDREN_BATCH: usize = 100;
+/// Most `morechildren` calls made while loading one comment tree
+const MAX_MORE_REQUESTS: usize = 10;
+
+/// Request allowance reported by the `X-Ratelimit-*` response headers
+#[derive(Debug, Default)]
+pub struct RateLimit {
+    remaining: Option<f64>,
+    reset_at: Option<Instant>,
+}
+
+impl RateLimit {
+    pub fn update(&mut self, headers: &HeaderMap, now: Instant) {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<f64>().ok())
+        };
+        if let Some(remaining) = header("x-ratelimit-remaining") {
+            self.remaining = Some(remaining);
+        }
+        if let Some(reset) = header("x-ratelimit-reset") {
+            self.reset_at = Some(now + Duration::from_secs_f64(reset.max(0.0)));
+        }
+    }
+
+    /// Ok while requests remain in the window, else how long until it resets
+    pub fn check(&self, now: Instant) -> std::result::Result<(), Duration> {
+        match (self.remaining, self.reset_at) {
+            (Some(remaining), Some(reset_at)) if remaining < 1.0 && now < reset_at => {
+                Err(reset_at - now)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AccessToken {
+    value: String,
+    expires_at: Instant,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct MoreChildrenResponse {
+    json: MoreChildrenJson,
+}
+
+#[derive(Debug, Deserialize)]
+struct MoreChildrenJson {
+    data: Option<MoreChildrenData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MoreChildrenData {
+    things: Vec<RedditChild>,
+}
+
+/// Comment ids behind the expandable "more" stubs of a comment tree
+///
+/// "Continue this thread" stubs carry no ids and are left alone.
+pub fn more_ids(children: &[RedditChild]) -> Vec<String> {
+    let mut ids = Vec::new();
+    for child in children {
+        if child.kind == "more" {
+            ids.extend(child.data.children.iter().flatten().cloned());
+        } else if let Some(RedditReplies::Listing(listing)) = &child.data.replies {
+            ids.extend(more_ids(&listing.data.children));
+        }
+    }
+    ids
+}
+
+/// Drop expanded ids from "more" stubs, and stubs left with none
+fn remove_expanded(children: &mut Vec<RedditChild>, expanded: &HashSet<&str>) {
+    children.retain_mut(|child| {
+        if child.kind == "more" {
+            let Some(ids) = child.data.children.as_mut() else {
+                return true;
+            };
+            if ids.is_empty() {
+                return true;
+            }
+            ids.retain(|id| !expanded.contains(id.as_str()));
+            return !ids.is_empty();
+        }
+        if let Some(RedditReplies::Listing(listing)) = child.data.replies.as_mut() {
+            remove_expanded(&mut listing.data.children, expanded);
+        }
+        true
+    });
+}
+
+fn find_comment<'a>(
+    children: &'a mut [RedditChild],
+    name: &str,
+) -> Option<&'a mut RedditChild> {
+    for child in children.iter_mut() {
+        if child.kind == "t1" && child.data.name.as_deref() == Some(name) {
+            return Some(child);
+        }
+        if let Some(RedditReplies::Listing(listing)) = child.data.replies.as_mut() {
+            if let Some(found) = find_comment(&mut listing.data.children, name) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Splice the comments a `morechildren` call returned into the tree
+///
+/// Reddit returns them flat, parents before replies, so each one can be
+/// attached under its `parent_id` in order. Comments whose parent isn't in the
+/// tree are kept at the top level rather than dropped.
+pub fn attach_more(
+    children: &mut Vec<RedditChild>,
+    link_id: &str,
+    expanded: &[String],
+    things: Vec<RedditChild>,
+) {
+    let expanded: HashSet<&str> = expanded.iter().map(String::as_str).collect();
+    remove_expanded(children, &expanded);
+
+    for thing in things {
+        let parent_id = thing.data.parent_id.clone().unwrap_or_default();
+        let parent = if parent_id == link_id {
+            None
+        } else {
+            find_comment(children, &parent_id)
+        };
+        let Some(parent) = parent else {
+            children.push(thing);
+            continue;
+        };
+        match parent.data.replies.as_mut() {
+            Some(RedditReplies::Listing(listing)) => listing.data.children.push(thing),
+            _ => {
+                parent.data.replies =
+                    Some(RedditReplies::Listing(Box::new(RedditResponse {
+                        kind: "Listing".to_string(),
+                        data: RedditListingData {
+                            modhash: None,
+                            dist: None,
+                            children: vec![thing],
+                            after: None,
+                            before: None,
+                        },
+                    })))
+            }
+        }
+    }
+}
+
+/// App-only OAuth client for the Reddit API
+///
+/// Tokens from the client credentials flow are cached and refreshed shortly
+/// before they expire; requests stop with 429 once the `X-Ratelimit-*` headers
+/// say the window is spent.
+#[derive(Debug, Clone)]
+pub struct RedditClient {
+    http: reqwest::Client,
+    client_id: String,
+    client_secret: String,
+    user_agent: String,
+    token: Arc<Mutex<Option<AccessToken>>>,
+    rate_limit: Arc<std::sync::Mutex<RateLimit>>,
+    usage: UpstreamUsage,
+}
+
+impl RedditClient {
+    pub fn new(
+        oauth: &RedditOAuthConfig,
+        user_agent: &str,
+        timeout: Duration,
+        usage: UpstreamUsage,
+    ) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|e| {
+                tracing::error!(
+                    "Failed to build Reddit HTTP client, using defaults: {}",
+                    e
+                );
+                reqwest::Client::new()
+            });
+
+        Self {
+            http,
+            client_id: oauth.client_id.clone(),
+            client_secret: oauth.client_secret.clone(),
+            user_agent: user_agent.to_string(),
+            token: Arc::new(Mutex::new(None)),
+            rate_limit: Arc::new(std::sync::Mutex::new(RateLimit::default())),
+            usage,
+        }
+    }
+
+    /// A valid bearer token, requesting a new one when the cached one is
+    /// missing or about to expire
+    async fn access_token(&self) -> Result<String> {
+        let mut token = self.token.lock().await;
+        if let Some(token) = token
+            .as_ref()
+            .filter(|token| Instant::now() + TOKEN_REFRESH_MARGIN < token.expires_at)
+        {
+            return Ok(token.value.clone());
+        }
+
+        if self.client_id.is_empty() || self.client_secret.is_empty() {
+            tracing::error!("Reddit OAuth credentials not configured (REDDIT_CLIENT_ID)");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+        }
+
+        let response = self
+            .http
+            .post(TOKEN_URL)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(&[("grant_type", "client_credentials")])
+            .header("User-Agent", &self.user_agent)
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get Reddit token: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+        if !response.status().is_success() {
+            tracing::error!(
+                "Reddit token request failed with status: {}",
+                response.status()
+            );
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+        }
+
+        let fresh: TokenResponse = response.json().await.map_err(|e| {
+            tracing::error!("Failed to parse Reddit token response: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        tracing::debug!("Refreshed Reddit access token ({}s)", fresh.expires_in);
+
+        let value = fresh.access_token.clone();
+        *token = Some(AccessToken {
+            value: fresh.access_token,
+            expires_at: Instant::now() + Duration::from_secs(fresh.expires_in),
+        });
+        Ok(value)
+    }
+
+    /// GET an OAuth API path (e.g. `/r/phillies/search`)
+    ///
+    /// A 401 drops the cached token and retries once with a fresh one.
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T> {
+        let url = format!("{}{}", OAUTH_BASE_URL, path);
+
+        for attempt in 0..2 {
+            let allowed = self
+                .rate_limit
+                .lock()
+                .map(|rate_limit| rate_limit.check(Instant::now()))
+                .unwrap_or(Ok(()));
+            if let Err(wait) = allowed {
+                tracing::warn!(
+                    "Reddit rate limit spent, window resets in {}s",
+                    wait.as_secs()
+                );
+                return Err(StatusCode::TOO_MANY_REQUESTS.into());
+            }
+
+            let token = self.access_token().await?;
+            let response = metered(
+                &self.usage,
+                Upstream::Reddit,
+                self.http
+                    .get(&url)
+                    .query(query)
+                    .bearer_auth(&token)
+                    .header("User-Agent", &self.user_agent),
+            )
+            .await?;
+
+            if let Ok(mut rate_limit) = self.rate_limit.lock() {
+                rate_limit.update(response.headers(), Instant::now());
+            }
+
+            match response.status() {
+                status if status.is_success() => {
+                    let body = response.text().await.map_err(|e| {
+                        tracing::error!(
+                            "Failed to read Reddit response for {}: {}",
+                            path,
+                            e
+                        );
+                        Error::from(StatusCode::INTERNAL_SERVER_ERROR)
+                    })?;
+                    return serde_json::from_str(&body).map_err(|e| {
+                        tracing::error!(
+                            "Failed to parse Reddit response for {}: {}",
+                            path,
+                            e
+                        );
+                        StatusCode::INTERNAL_SERVER_ERROR.into()
+                    });
+                }
+                StatusCode::UNAUTHORIZED if attempt == 0 => {
+                    tracing::warn!("Reddit rejected the access token, refreshing");
+                    *self.token.lock().await = None;
+                }
+                status => {
+                    tracing::warn!(
+                        "Reddit request {} failed with status: {}",
+                        path,
+                        status
+                    );
+                    return Err(status.into());
+                }
+            }
+        }
+
+        Err(StatusCode::UNAUTHORIZED.into())
+    }
+
+    /// A thread's post and its comment tree with "more" stubs expanded
+    ///
+    /// Expansion stops after `MAX_MORE_REQUESTS` calls, or at the first failed
+    /// one, returning the tree loaded so far.
+    pub async fn comment_tree(
+        &self,
+        subreddit: &str,
+        thread_id: &str,
+        sort: &str,
+        limit: u32,
+    ) -> Result<(serde_json::Value, Vec<RedditChild>)> {
+        let limit = limit.to_string();
+        let listings: Vec<serde_json::Value> = self
+            .get(
+                &format!("/r/{}/comments/{}", subreddit, thread_id),
+                &[("sort", sort), ("limit", &limit), ("raw_json", "1")],
+            )
+            .await?;
+
+        let mut listings = listings.into_iter();
+        let (Some(post), Some(comments)) = (listings.next(), listings.next()) else {
+            tracing::error!("Unexpected Reddit comments response for {}", thread_id);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+        };
+        let mut children = serde_json::from_value::<RedditResponse>(comments)?
+            .data
+            .children;
+
+        let link_id = format!("t3_{}", thread_id);
+        for _ in 0..MAX_MORE_REQUESTS {
+            let batch: Vec<String> = more_ids(&children)
+                .into_iter()
+                .take(MORE_CHILDREN_BATCH)
+                .collect();
+            if batch.is_empty() {
+                break;
+            }
+
+            let more: MoreChildrenResponse = match self
+                .get(
+                    "/api/morechildren",
+                    &[
+                        ("api_type", "json"),
+                        ("link_id", &link_id),
+                        ("children", &batch.join(",")),
+                        ("sort", sort),
+                        ("raw_json", "1"),
+                    ],
+                )
+                .await
+            {
+                Ok(more) => more,
+                Err(e) => {
+                    tracing::warn!(
+                        "Stopped expanding comments for thread {}: {}",
+                        thread_id,
+                        e
+                    );
+                    break;
+                }
+            };
+            let things = more.json.data.map(|data| data.things).unwrap_or_default();
+            attach_more(&mut children, &link_id, &batch, things);
+        }
+
+        Ok((post, children))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child(value: serde_json::Value) -> RedditChild {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn comment(id: &str, parent_id: &str) -> RedditChild {
+        child(serde_json::json!({"kind": "t1", "data": {
+            "id": id, "name": format!("t1_{}", id), "parent_id": parent_id,
+            "body": format!("comment {}", id), "replies": ""
+        }}))
+    }
+
+    fn more(ids: &[&str], parent_id: &str) -> RedditChild {
+        child(serde_json::json!({"kind": "more", "data": {
+            "id": ids.first().copied().unwrap_or("_"), "name": "t1__",
+            "parent_id": parent_id, "children": ids
+        }}))
+    }
+
+    #[test]
+    fn test_rate_limit_blocks_until_reset() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "0.0".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "30".parse().unwrap());
+
+        let now = Instant::now();
+        let mut rate_limit = RateLimit::default();
+        assert!(rate_limit.check(now).is_ok());
+        rate_limit.update(&headers, now);
+        assert_eq!(rate_limit.check(now), Err(Duration::from_secs(30)));
+        assert!(rate_limit.check(now + Duration::from_secs(30)).is_ok());
+    }
+
+    #[test]
+    fn test_more_children_are_spliced_under_their_parents() {
+        let mut children = vec![comment("a", "t3_x"), more(&["b", "c"], "t3_x")];
+        let batch = more_ids(&children);
+        assert_eq!(batch, ["b", "c"]);
+
+        let things = vec![
+            comment("b", "t3_x"),
+            comment("d", "t1_b"),
+            comment("c", "t3_x"),
+            more(&["e"], "t1_c"),
+        ];
+        attach_more(&mut children, "t3_x", &batch, things);
+
+        let ids: Vec<&str> = children
+            .iter()
+            .filter_map(|child| child.data.id.as_deref())
+            .collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        let Some(RedditReplies::Listing(replies)) = &children[1].data.replies else {
+            panic!("expected replies under b");
+        };
+        assert_eq!(replies.data.children[0].data.id.as_deref(), Some("d"));
+        assert_eq!(more_ids(&children), ["e"]);
+    }
+}
@@ -0,0 +1,438 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::timeline::{GameClock, TimelineItem, TimelineSource};
+
+/// Word valence from -3 (very negative) to 3 (very positive), tuned for how
+/// fans talk during games
+const LEXICON: &[(&str, f64)] = &[
+    ("amazing", 3.0),
+    ("awesome", 3.0),
+    ("beautiful", 2.5),
+    ("best", 2.5),
+    ("brilliant", 3.0),
+    ("clutch", 2.5),
+    ("dominant", 2.5),
+    ("dominating", 2.5),
+    ("elite", 2.5),
+    ("excellent", 3.0),
+    ("fantastic", 3.0),
+    ("fire", 2.0),
+    ("glad", 1.5),
+    ("goat", 2.5),
+    ("good", 1.5),
+    ("great", 2.5),
+    ("happy", 2.0),
+    ("huge", 1.5),
+    ("incredible", 3.0),
+    ("legend", 2.5),
+    ("love", 2.5),
+    ("nice", 1.5),
+    ("perfect", 3.0),
+    ("proud", 2.0),
+    ("solid", 1.5),
+    ("unreal", 2.0),
+    ("win", 2.0),
+    ("winning", 2.0),
+    ("won", 2.0),
+    ("wow", 1.5),
+    ("yes", 1.5),
+    ("yay", 2.0),
+    ("awful", -3.0),
+    ("bad", -2.0),
+    ("blew", -2.0),
+    ("blown", -2.0),
+    ("boo", -2.0),
+    ("brutal", -2.5),
+    ("bum", -2.0),
+    ("choke", -2.5),
+    ("choked", -2.5),
+    ("disaster", -3.0),
+    ("disgrace", -3.0),
+    ("embarrassing", -2.5),
+    ("garbage", -3.0),
+    ("hate", -3.0),
+    ("horrible", -3.0),
+    ("injured", -2.0),
+    ("injury", -2.0),
+    ("lose", -2.0),
+    ("losing", -2.0),
+    ("lost", -2.0),
+    ("pathetic", -3.0),
+    ("robbed", -2.5),
+    ("sad", -2.0),
+    ("sucks", -2.5),
+    ("terrible", -3.0),
+    ("trash", -3.0),
+    ("ugh", -2.0),
+    ("ugly", -2.0),
+    ("worst", -3.0),
+    ("❤️", 2.5),
+    ("🎉", 2.5),
+    ("🙌", 2.5),
+    ("💪", 2.0),
+    ("🐐", 2.5),
+    ("😍", 3.0),
+    ("😂", 1.0),
+    ("😡", -3.0),
+    ("😤", -1.5),
+    ("😭", -2.0),
+    ("🤮", -3.0),
+    ("💀", -1.0),
+];
+
+/// Words and emoji that signal excitement whichever way it cuts
+const HYPE: &[&str] = &[
+    "lets",
+    "let's",
+    "lfg",
+    "goat",
+    "clutch",
+    "walkoff",
+    "huge",
+    "massive",
+    "insane",
+    "unreal",
+    "wow",
+    "omg",
+    "holy",
+    "incredible",
+    "electric",
+    "🔥",
+    "🚀",
+    "🤯",
+    "😱",
+];
+
+const NEGATIONS: &[&str] = &[
+    "not", "no", "never", "isnt", "isn't", "dont", "don't", "cant", "can't", "wont",
+    "won't", "aint", "ain't",
+];
+
+const INTENSIFIERS: &[&str] = &[
+    "so",
+    "very",
+    "really",
+    "super",
+    "absolutely",
+    "extremely",
+    "totally",
+];
+
+/// Scales the summed valence into -1..1, as VADER does
+const NORMALIZATION_ALPHA: f64 = 15.0;
+/// Weight of the previous moment when trending fan sentiment
+const TREND_WEIGHT: f64 = 0.7;
+
+/// Sentiment of one comment or tweet
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Sentiment {
+    /// -1 (negative) to 1 (positive)
+    pub score: f64,
+    /// 0 (calm) to 1 (hyped), regardless of direction
+    pub hype: f64,
+}
+
+/// Lowercase words, with emoji split out as their own tokens
+fn tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text.split_whitespace() {
+        let mut current = String::new();
+        for c in word.chars() {
+            // Phones type "don’t" with a curly apostrophe
+            let c = if c == '\u{2019}' { '\'' } else { c };
+            if c.is_alphanumeric() || c == '\'' {
+                current.extend(c.to_lowercase());
+                continue;
+            }
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            // Variation selectors ride along with the emoji before them
+            if c == '\u{fe0f}' {
+                if let Some(last) = tokens.last_mut() {
+                    last.push(c);
+                }
+            } else if !c.is_ascii() {
+                tokens.push(c.to_string());
+            }
+        }
+        if !current.is_empty() {
+            tokens.push(current);
+        }
+    }
+    tokens
+}
+
+fn valence(token: &str) -> Option<f64> {
+    LEXICON
+        .iter()
+        .find(|(word, _)| *word == token || word.trim_end_matches('\u{fe0f}') == token)
+        .map(|(_, valence)| *valence)
+}
+
+/// Score a comment or tweet with the bundled lexicon
+///
+/// Negation in the two words before a term flips and dampens it, an
+/// intensifier right before it boosts it. Hype counts hype terms, exclamation
+/// marks and shouted (all caps) words.
+pub fn score_text(text: &str) -> Sentiment {
+    let tokens = tokens(text);
+
+    let mut sum = 0.0;
+    let mut hype_terms = 0.0;
+    for (index, token) in tokens.iter().enumerate() {
+        if HYPE.contains(&token.as_str()) {
+            hype_terms += 1.0;
+        }
+        let Some(mut value) = valence(token) else {
+            continue;
+        };
+        let before = &tokens[index.saturating_sub(2)..index];
+        if before.iter().any(|word| NEGATIONS.contains(&word.as_str())) {
+            value *= -0.75;
+        }
+        if index > 0 && INTENSIFIERS.contains(&tokens[index - 1].as_str()) {
+            value *= 1.5;
+        }
+        sum += value;
+    }
+
+    let exclamations = text.matches('!').count().min(5) as f64;
+    let shouted = text
+        .split_whitespace()
+        .filter(|word| {
+            let letters: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+            letters.len() >= 3 && letters.iter().all(|c| c.is_uppercase())
+        })
+        .count() as f64;
+    let excitement = hype_terms + 0.5 * exclamations + 0.5 * shouted;
+
+    Sentiment {
+        score: sum / (sum * sum + NORMALIZATION_ALPHA).sqrt(),
+        hype: excitement / (excitement + 3.0),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TeamSide {
+    Home,
+    Away,
+}
+
+/// How to tell which team's fans posted a comment or tweet
+#[derive(Debug, Clone, Default)]
+pub struct FanBase {
+    /// Hashtags, handles and names that identify the team
+    pub terms: Vec<String>,
+    /// Subreddits whose threads belong to the team's fans
+    pub subreddits: Vec<String>,
+}
+
+impl FanBase {
+    fn claims(&self, item: &TimelineItem, words: &[String]) -> bool {
+        if item.source == TimelineSource::Reddit {
+            let url = item.url.as_deref().unwrap_or_default().to_lowercase();
+            return self.subreddits.iter().any(|subreddit| {
+                url.contains(&format!("/r/{}/", subreddit.to_lowercase()))
+            });
+        }
+
+        let author = item.author.as_deref().unwrap_or_default();
+        self.terms.iter().any(|term| {
+            let term = term.trim_start_matches(['#', '@']);
+            let term_words = tokens(term);
+            author.eq_ignore_ascii_case(term)
+                || (!term_words.is_empty()
+                    && words
+                        .windows(term_words.len())
+                        .any(|window| window == term_words))
+        })
+    }
+}
+
+/// Side whose fans posted `item`: Reddit comments by the thread's subreddit,
+/// tweets by the one team they name or whose account wrote them
+pub fn fan_side(item: &TimelineItem, home: &FanBase, away: &FanBase) -> Option<TeamSide> {
+    let words = tokens(&item.text);
+    match (home.claims(item, &words), away.claims(item, &words)) {
+        (true, false) => Some(TeamSide::Home),
+        (false, true) => Some(TeamSide::Away),
+        _ => None,
+    }
+}
+
+/// Fan sentiment for one team at one moment
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct TeamSentiment {
+    /// Comments and tweets scored
+    pub count: usize,
+    /// Mean score, 0 when nothing was posted
+    pub score: f64,
+    /// Mean hype, 0 when nothing was posted
+    pub hype: f64,
+    /// Moving average of score across moments so far
+    pub trend: f64,
+}
+
+impl TeamSentiment {
+    fn add(&mut self, sentiment: Sentiment) {
+        let count = self.count as f64;
+        self.score = (self.score * count + sentiment.score) / (count + 1.0);
+        self.hype = (self.hype * count + sentiment.hype) / (count + 1.0);
+        self.count += 1;
+    }
+}
+
+/// Reactions to one play: everything posted after it and before the next one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentimentPoint {
+    /// Play the reactions follow; `None` for what was posted before the first play
+    pub play_id: Option<String>,
+    pub play: Option<String>,
+    /// RFC 3339, UTC
+    pub timestamp: String,
+    pub game_clock: Option<GameClock>,
+    pub home: TeamSentiment,
+    pub away: TeamSentiment,
+    /// Posts neither fan base could be told apart by
+    pub neutral: TeamSentiment,
+}
+
+/// Fan sentiment per team over a merged timeline, one point per play
+///
+/// Items carrying a `sentiment` are bucketed under the latest play before them.
+/// Plays nobody reacted to are kept so the series lines up with the game.
+pub fn series(
+    items: &[TimelineItem],
+    home: &FanBase,
+    away: &FanBase,
+) -> Vec<SentimentPoint> {
+    let mut points: Vec<SentimentPoint> = Vec::new();
+
+    for item in items {
+        if item.source == TimelineSource::Play {
+            points.push(SentimentPoint {
+                play_id: Some(item.id.clone()),
+                play: Some(item.text.clone()).filter(|text| !text.is_empty()),
+                timestamp: item.timestamp.clone(),
+                game_clock: item.game_clock.clone(),
+                home: TeamSentiment::default(),
+                away: TeamSentiment::default(),
+                neutral: TeamSentiment::default(),
+            });
+            continue;
+        }
+
+        let Some(sentiment) = item.sentiment else {
+            continue;
+        };
+        if points.is_empty() {
+            points.push(SentimentPoint {
+                play_id: None,
+                play: None,
+                timestamp: item.timestamp.clone(),
+                game_clock: None,
+                home: TeamSentiment::default(),
+                away: TeamSentiment::default(),
+                neutral: TeamSentiment::default(),
+            });
+        }
+        let point = points.last_mut().expect("a point was just ensured");
+        match fan_side(item, home, away) {
+            Some(TeamSide::Home) => point.home.add(sentiment),
+            Some(TeamSide::Away) => point.away.add(sentiment),
+            None => point.neutral.add(sentiment),
+        }
+    }
+
+    let (mut home_trend, mut away_trend, mut neutral_trend) = (0.0, 0.0, 0.0);
+    for point in &mut points {
+        for (team, trend) in [
+            (&mut point.home, &mut home_trend),
+            (&mut point.away, &mut away_trend),
+            (&mut point.neutral, &mut neutral_trend),
+        ] {
+            if team.count > 0 {
+                *trend = TREND_WEIGHT * *trend + (1.0 - TREND_WEIGHT) * team.score;
+            }
+            team.trend = *trend;
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        source: TimelineSource,
+        id: &str,
+        text: &str,
+        url: Option<&str>,
+    ) -> TimelineItem {
+        TimelineItem {
+            id: id.to_string(),
+            source,
+            timestamp: format!("2025-07-04T23:{}:00Z", id),
+            game_clock: None,
+            sentiment: (source != TimelineSource::Play).then(|| score_text(text)),
+            text: text.to_string(),
+            author: None,
+            url: url.map(String::from),
+            score: None,
+            play: None,
+            sequence: 0,
+        }
+    }
+
+    #[test]
+    fn test_lexicon_handles_negation_and_hype() {
+        let good = score_text("What a great catch");
+        let not_good = score_text("That was not great");
+        assert!(good.score > 0.3);
+        assert!(not_good.score < 0.0);
+        assert_eq!(score_text("pitch count is 45").score, 0.0);
+
+        let hyped = score_text("WALKOFF!!! LETS GO 🔥🔥");
+        assert!(hyped.hype > 0.6);
+        assert!(score_text("❤️ this team").score > 0.0);
+        assert!(score_text("this lineup is fire").score > 0.0);
+        assert!(score_text("I don\u{2019}t love this").score < 0.0);
+    }
+
+    #[test]
+    fn test_series_buckets_fans_by_play() {
+        let home = FanBase {
+            terms: vec!["#RingTheBell".to_string(), "Phillies".to_string()],
+            subreddits: vec!["phillies".to_string()],
+        };
+        let away = FanBase {
+            terms: vec!["#LGM".to_string(), "Mets".to_string()],
+            subreddits: vec!["NewYorkMets".to_string()],
+        };
+        let items = vec![
+            item(TimelineSource::Tweet, "00", "Ready for tonight", None),
+            item(TimelineSource::Play, "01", "Harper homers", None),
+            item(
+                TimelineSource::Reddit,
+                "02",
+                "HARPER IS THE GOAT",
+                Some("https://reddit.com/r/phillies/comments/x/y/z/"),
+            ),
+            item(TimelineSource::Tweet, "03", "Terrible pitch #LGM", None),
+            item(TimelineSource::Play, "04", "Alonso strikes out", None),
+        ];
+
+        let points = series(&items, &home, &away);
+        let plays: Vec<Option<&str>> =
+            points.iter().map(|p| p.play_id.as_deref()).collect();
+        assert_eq!(plays, [None, Some("01"), Some("04")]);
+        assert_eq!(points[0].neutral.count, 1);
+        assert_eq!((points[1].home.count, points[1].away.count), (1, 1));
+        assert!(points[1].home.score > 0.0 && points[1].away.score < 0.0);
+        assert_eq!(points[2].home.count, 0);
+        assert_eq!(points[2].home.trend, points[1].home.trend);
+    }
+}
@@ -0,0 +1,285 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    config::RatingModel,
+    path::League,
+    services::{
+        ratings::{win_probability, EloParams, RatingsBook, BASE_RATING},
+        standings::{LeagueTable, PlayoffFormat, ScheduledGame, TeamRecord},
+    },
+};
+
+/// Upper bound on simulated seasons per request
+pub const MAX_ITERATIONS: u32 = 50_000;
+
+/// Pythagorean exponent used by the point-differential model
+pub fn pythagorean_exponent(league: &League) -> Option<f64> {
+    match league {
+        League::Mlb => Some(1.83),
+        League::Nfl => Some(2.37),
+        League::Nba => Some(13.91),
+        _ => None,
+    }
+}
+
+/// Simulated outcomes for a single team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamOdds {
+    pub team: String,
+    pub name: String,
+    pub conference: String,
+    pub division: String,
+    pub wins: i32,
+    pub losses: i32,
+    pub rating: f64,
+    pub projected_wins: f64,
+    pub projected_losses: f64,
+    /// Probability of a direct playoff berth (play-in excluded)
+    pub make_playoffs: f64,
+    /// Probability of finishing in a play-in seed, for leagues that have one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub make_play_in: Option<f64>,
+    pub win_division: f64,
+    /// Probability of each seed, index 0 being the top seed
+    pub seed_probabilities: Vec<f64>,
+}
+
+/// Playoff odds for a league produced by simulating the rest of the season
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayoffOdds {
+    pub league: String,
+    pub season: i32,
+    pub model: RatingModel,
+    pub iterations: u32,
+    pub games_remaining: usize,
+    pub teams: Vec<TeamOdds>,
+    pub generated_at: String,
+}
+
+/// Ratings from each team's Pythagorean expectation, on the same scale as Elo
+pub fn point_differential_ratings(
+    teams: &[TeamRecord],
+    exponent: f64,
+) -> HashMap<String, f64> {
+    teams
+        .iter()
+        .map(|team| {
+            let scored = team.points_for.max(0.0).powf(exponent);
+            let allowed = team.points_against.max(0.0).powf(exponent);
+            let expectation = if scored + allowed > 0.0 {
+                (scored / (scored + allowed)).clamp(0.01, 0.99)
+            } else {
+                0.5
+            };
+            let rating =
+                BASE_RATING + 400.0 * (expectation / (1.0 - expectation)).log10();
+            (team.team.clone(), rating)
+        })
+        .collect()
+}
+
+/// Monte Carlo simulator for the rest of a regular season
+pub struct SeasonSimulator {
+    table: LeagueTable,
+    ratings: HashMap<String, f64>,
+    /// Remaining games as (home, away, home win probability)
+    games: Vec<(String, String, f64)>,
+}
+
+impl SeasonSimulator {
+    /// Build a simulator from current standings and the season schedule
+    ///
+    /// The Elo model falls back to point differential when no completed games
+    /// are available to replay (e.g. when only standings were loaded).
+    pub fn new(
+        league: &League,
+        model: RatingModel,
+        teams: Vec<TeamRecord>,
+        schedule: &[ScheduledGame],
+    ) -> Option<Self> {
+        let format = PlayoffFormat::for_league(league)?;
+        let params = EloParams::for_league(league)?;
+        let exponent = pythagorean_exponent(league)?;
+
+        let has_results = schedule.iter().any(|game| game.is_final);
+        let ratings = match model {
+            RatingModel::Elo if has_results => {
+                let mut book = RatingsBook::new(league, 0);
+                book.apply_all(schedule, params);
+                book.ratings_map()
+            }
+            _ => point_differential_ratings(&teams, exponent),
+        };
+
+        let rating = |team: &str| ratings.get(team).copied().unwrap_or(BASE_RATING);
+        let games = schedule
+            .iter()
+            .filter(|game| !game.is_final)
+            .map(|game| {
+                let diff =
+                    rating(&game.home) + params.home_advantage - rating(&game.away);
+                (game.home.clone(), game.away.clone(), win_probability(diff))
+            })
+            .collect();
+
+        let table = LeagueTable::new(format, teams, schedule);
+        Some(Self {
+            table,
+            ratings,
+            games,
+        })
+    }
+
+    pub fn games_remaining(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Play out the remaining schedule `iterations` times and tally the outcomes
+    pub fn run<R: Rng>(&self, iterations: u32, rng: &mut R) -> Vec<TeamOdds> {
+        let format = self.table.format();
+        let iterations = iterations.clamp(1, MAX_ITERATIONS);
+
+        let mut wins: HashMap<&str, u64> = HashMap::new();
+        let mut playoffs: HashMap<String, u32> = HashMap::new();
+        let mut play_in: HashMap<String, u32> = HashMap::new();
+        let mut divisions: HashMap<String, u32> = HashMap::new();
+        let mut seeds: HashMap<String, Vec<u32>> = HashMap::new();
+
+        for _ in 0..iterations {
+            let mut table = self.table.clone();
+            for (home, away, home_win) in &self.games {
+                if rng.gen_bool(*home_win) {
+                    table.record_result(home, away);
+                } else {
+                    table.record_result(away, home);
+                }
+            }
+
+            for team in self.table.teams() {
+                let simulated = table
+                    .teams()
+                    .iter()
+                    .find(|simulated| simulated.team == team.team)
+                    .map(|simulated| simulated.wins)
+                    .unwrap_or(team.wins);
+                *wins.entry(team.team.as_str()).or_insert(0) += simulated as u64;
+            }
+
+            for picture in table.playoff_picture() {
+                for seed in picture.seeds {
+                    let counts = if seed.play_in {
+                        &mut play_in
+                    } else {
+                        &mut playoffs
+                    };
+                    *counts.entry(seed.team.clone()).or_insert(0) += 1;
+                    seeds
+                        .entry(seed.team)
+                        .or_insert_with(|| vec![0; format.play_in_spots])
+                        [seed.seed as usize - 1] += 1;
+                }
+            }
+
+            for leader in table.division_leaders() {
+                *divisions.entry(leader).or_insert(0) += 1;
+            }
+        }
+
+        let share = |count: u32| count as f64 / iterations as f64;
+        self.table
+            .teams()
+            .iter()
+            .map(|team| {
+                let total_games =
+                    team.games_played() + self.table.games_remaining(&team.team);
+                let projected_wins = wins.get(team.team.as_str()).copied().unwrap_or(0)
+                    as f64
+                    / iterations as f64;
+                let seed_counts = seeds
+                    .remove(&team.team)
+                    .unwrap_or_else(|| vec![0; format.play_in_spots]);
+
+                TeamOdds {
+                    team: team.team.clone(),
+                    name: team.name.clone(),
+                    conference: team.conference.clone(),
+                    division: team.division.clone(),
+                    wins: team.wins,
+                    losses: team.losses,
+                    rating: self.ratings.get(&team.team).copied().unwrap_or(BASE_RATING),
+                    projected_wins,
+                    projected_losses: (total_games - team.ties) as f64 - projected_wins,
+                    make_playoffs: share(playoffs.get(&team.team).copied().unwrap_or(0)),
+                    make_play_in: format
+                        .has_play_in()
+                        .then(|| share(play_in.get(&team.team).copied().unwrap_or(0))),
+                    win_division: share(divisions.get(&team.team).copied().unwrap_or(0)),
+                    seed_probabilities: seed_counts.into_iter().map(share).collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Simulate the rest of a league's season and report playoff odds
+pub fn simulate_playoff_odds<R: Rng>(
+    league: &League,
+    season: i32,
+    model: RatingModel,
+    iterations: u32,
+    teams: Vec<TeamRecord>,
+    schedule: &[ScheduledGame],
+    rng: &mut R,
+) -> Option<PlayoffOdds> {
+    let simulator = SeasonSimulator::new(league, model, teams, schedule)?;
+    let iterations = iterations.clamp(1, MAX_ITERATIONS);
+    let teams = simulator.run(iterations, rng);
+
+    Some(PlayoffOdds {
+        league: league.to_string(),
+        season,
+        model,
+        iterations,
+        games_remaining: simulator.games_remaining(),
+        teams,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::standings::test_support::{game, record};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_clinched_team_always_makes_playoffs() {
+        let teams = vec![
+            record("NYY", "East", 100, 55),
+            record("BOS", "East", 80, 75),
+        ];
+        let schedule: Vec<ScheduledGame> = (0..7)
+            .map(|_| game("NYY", "BOS", None))
+            .chain(std::iter::once(game("NYY", "BOS", Some((4, 2)))))
+            .collect();
+        let mut rng = StdRng::seed_from_u64(7);
+        let odds = simulate_playoff_odds(
+            &League::Mlb,
+            2025,
+            RatingModel::Elo,
+            200,
+            teams,
+            &schedule,
+            &mut rng,
+        )
+        .unwrap();
+
+        let nyy = odds.teams.iter().find(|t| t.team == "NYY").unwrap();
+        assert_eq!(odds.games_remaining, 7);
+        assert_eq!(nyy.win_division, 1.0);
+        assert_eq!(nyy.seed_probabilities[0], 1.0);
+        assert!(nyy.projected_wins >= 100.0 && nyy.projected_wins <= 107.0);
+    }
+}
@@ -0,0 +1,401 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    config::TeamSocialConfig, database::Database, error::Result,
+    schema::twitterapi::tweet::TwitterTweet,
+};
+
+/// Twitter advanced-search timestamp (`since:` / `until:` operators)
+fn search_time(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%Y-%m-%d_%H:%M:%S_UTC").to_string()
+}
+
+/// Search window around a game's scheduled start
+pub fn game_window(
+    start: DateTime<Utc>,
+    before_minutes: i64,
+    after_minutes: i64,
+) -> (DateTime<Utc>, DateTime<Utc>) {
+    (
+        start - Duration::minutes(before_minutes),
+        start + Duration::minutes(after_minutes),
+    )
+}
+
+/// Search terms for one team: configured hashtags, accounts and beat reporters
+///
+/// Teams without config fall back to their abbreviation as a keyword.
+fn team_terms(team: &str, config: Option<&TeamSocialConfig>) -> Vec<String> {
+    let Some(config) = config else {
+        return vec![format!("\"{}\"", team)];
+    };
+
+    let clean = |value: &String| value.trim().trim_start_matches(['#', '@']).to_string();
+    let mut terms: Vec<String> = Vec::new();
+    terms.extend(config.hashtags.iter().map(|tag| format!("#{}", clean(tag))));
+    for handle in &config.handles {
+        terms.push(format!("from:{}", clean(handle)));
+        terms.push(format!("@{}", clean(handle)));
+    }
+    terms.extend(
+        config
+            .beat_reporters
+            .iter()
+            .map(|reporter| format!("from:{}", clean(reporter))),
+    );
+    terms
+}
+
+/// Advanced-search query covering both teams of a game within its window
+pub fn game_query(
+    home: &str,
+    away: &str,
+    teams: &[TeamSocialConfig],
+    window: (DateTime<Utc>, DateTime<Utc>),
+) -> String {
+    let lookup = |team: &str| teams.iter().find(|t| t.team.eq_ignore_ascii_case(team));
+
+    let mut terms = team_terms(home, lookup(home));
+    for term in team_terms(away, lookup(away)) {
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+
+    format!(
+        "({}) since:{} until:{} -filter:retweets",
+        terms.join(" OR "),
+        search_time(window.0),
+        search_time(window.1)
+    )
+}
+
+/// A collected post, stored as a row of the `social_posts` table (etc/schema)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocialPost {
+    pub platform: String,
+    pub platform_post_id: String,
+    pub author_handle: String,
+    pub author_name: Option<String>,
+    pub author_avatar_url: Option<String>,
+    pub author_verified: bool,
+    pub content: String,
+    pub media_urls: Vec<String>,
+    /// Provider game id, stored as `provider_game_id`
+    pub game_id: Option<String>,
+    /// Team abbreviations the post was collected for, stored as
+    /// `team_abbreviations` and resolved to `team_ids` where `teams` has them
+    pub team_ids: Vec<String>,
+    /// League abbreviation, resolved to `league_id` through `leagues`
+    pub league_id: Option<String>,
+    pub hashtags: Vec<String>,
+    pub mentions: Vec<String>,
+    pub like_count: i64,
+    pub repost_count: i64,
+    pub comment_count: i64,
+    pub post_url: Option<String>,
+    /// RFC 3339, UTC
+    pub posted_at: String,
+}
+
+/// Words in `text` starting with `prefix`, without it or trailing punctuation
+fn tagged_words(text: &str, prefix: char) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        let Some(rest) = word.strip_prefix(prefix) else {
+            continue;
+        };
+        let tag: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        if !tag.is_empty() && !words.contains(&tag) {
+            words.push(tag);
+        }
+    }
+    words
+}
+
+impl SocialPost {
+    pub fn from_tweet(
+        tweet: &TwitterTweet,
+        league: &str,
+        game_id: &str,
+        teams: &[&str],
+    ) -> Self {
+        let author = |key: &str| {
+            tweet
+                .additional_fields
+                .get("author")
+                .and_then(|author| author.get(key))
+        };
+        let media_urls = tweet
+            .additional_fields
+            .pointer("/extendedEntities/media")
+            .and_then(|media| media.as_array())
+            .map(|media| {
+                media
+                    .iter()
+                    .filter_map(|item| item.get("media_url_https")?.as_str())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let posted_at =
+            DateTime::parse_from_str(&tweet.created_at, "%a %b %d %H:%M:%S %z %Y")
+                .or_else(|_| DateTime::parse_from_rfc3339(&tweet.created_at))
+                .map(|posted_at| posted_at.with_timezone(&Utc).to_rfc3339())
+                .unwrap_or_else(|_| tweet.created_at.clone());
+
+        Self {
+            platform: "twitter".to_string(),
+            platform_post_id: tweet.id.clone(),
+            author_handle: author("userName")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            author_name: author("name").and_then(|v| v.as_str()).map(String::from),
+            author_avatar_url: author("profilePicture")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            author_verified: author("isBlueVerified")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            content: tweet.text.clone(),
+            media_urls,
+            game_id: Some(game_id.to_string()),
+            team_ids: teams.iter().map(|team| team.to_string()).collect(),
+            league_id: Some(league.to_string()),
+            hashtags: tagged_words(&tweet.text, '#'),
+            mentions: tagged_words(&tweet.text, '@'),
+            like_count: tweet.like_count,
+            repost_count: tweet.retweet_count,
+            comment_count: tweet.reply_count,
+            post_url: Some(tweet.url.clone()),
+            posted_at,
+        }
+    }
+}
+
+/// Upsert `fresh` posts into `stored`, keyed like the table's
+/// `UNIQUE(platform, platform_post_id)`, newest first
+///
+/// Returns the merged posts and how many were new.
+pub fn upsert_posts(
+    stored: Vec<SocialPost>,
+    fresh: Vec<SocialPost>,
+) -> (Vec<SocialPost>, usize) {
+    let mut posts: HashMap<(String, String), SocialPost> = stored
+        .into_iter()
+        .map(|post| ((post.platform.clone(), post.platform_post_id.clone()), post))
+        .collect();
+
+    let mut inserted = 0;
+    for post in fresh {
+        let key = (post.platform.clone(), post.platform_post_id.clone());
+        if posts.insert(key, post).is_none() {
+            inserted += 1;
+        }
+    }
+
+    let mut posts: Vec<SocialPost> = posts.into_values().collect();
+    posts.sort_by(|a, b| {
+        b.posted_at
+            .cmp(&a.posted_at)
+            .then_with(|| a.platform_post_id.cmp(&b.platform_post_id))
+    });
+    (posts, inserted)
+}
+
+/// When a game's posts were last collected, and with what query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocialRefresh {
+    pub query: String,
+    pub fetched_at: String,
+}
+
+const UPSERT_POST: &str = "
+    INSERT INTO social_posts (
+        platform, platform_post_id, author_handle, author_name, author_avatar_url,
+        author_verified, content, media_urls, provider_game_id, team_abbreviations,
+        team_ids, league_id, hashtags, mentions, like_count, repost_count,
+        comment_count, post_url, posted_at
+    ) VALUES (
+        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+        ARRAY(
+            SELECT teams.id FROM teams JOIN leagues ON leagues.id = teams.league_id
+            WHERE leagues.abbreviation = upper($11) AND teams.abbreviation = ANY($10)
+        ),
+        (SELECT id FROM leagues WHERE abbreviation = upper($11)),
+        $12, $13, $14, $15, $16, $17, $18
+    )
+    ON CONFLICT (platform, platform_post_id) DO UPDATE SET
+        content = EXCLUDED.content,
+        like_count = EXCLUDED.like_count,
+        repost_count = EXCLUDED.repost_count,
+        comment_count = EXCLUDED.comment_count,
+        updated_at = NOW()
+    RETURNING (xmax = 0) AS inserted";
+
+const GAME_POSTS: &str = "
+    SELECT platform, platform_post_id, author_handle, author_name, author_avatar_url,
+        author_verified, content, media_urls, team_abbreviations, hashtags, mentions,
+        like_count, repost_count, comment_count, post_url, posted_at
+    FROM social_posts
+    WHERE league_id = (SELECT id FROM leagues WHERE abbreviation = upper($1))
+        AND provider_game_id = $2
+    ORDER BY posted_at DESC, platform_post_id";
+
+/// Upsert posts into `social_posts`, keyed by its
+/// `UNIQUE(platform, platform_post_id)`; posts already stored get fresh counts
+///
+/// Returns how many posts were new.
+pub async fn store_posts(database: &Database, posts: &[SocialPost]) -> Result<usize> {
+    let count = |value: i64| i32::try_from(value.max(0)).unwrap_or(i32::MAX);
+
+    let mut client = database.client().await?;
+    let transaction = client.transaction().await?;
+    let statement = transaction.prepare_cached(UPSERT_POST).await?;
+    let mut inserted = 0;
+    for post in posts {
+        let Ok(posted_at) = DateTime::parse_from_rfc3339(&post.posted_at) else {
+            tracing::warn!(
+                "Skipping post {} with unreadable time {}",
+                post.platform_post_id,
+                post.posted_at
+            );
+            continue;
+        };
+        let posted_at = posted_at.with_timezone(&Utc);
+        let (likes, reposts, comments) = (
+            count(post.like_count),
+            count(post.repost_count),
+            count(post.comment_count),
+        );
+        let row = transaction
+            .query_one(
+                &statement,
+                &[
+                    &post.platform,
+                    &post.platform_post_id,
+                    &post.author_handle,
+                    &post.author_name,
+                    &post.author_avatar_url,
+                    &post.author_verified,
+                    &post.content,
+                    &post.media_urls,
+                    &post.game_id,
+                    &post.team_ids,
+                    &post.league_id.as_deref().unwrap_or_default(),
+                    &post.hashtags,
+                    &post.mentions,
+                    &likes,
+                    &reposts,
+                    &comments,
+                    &post.post_url,
+                    &posted_at,
+                ],
+            )
+            .await?;
+        if row.get::<_, bool>("inserted") {
+            inserted += 1;
+        }
+    }
+    transaction.commit().await?;
+    Ok(inserted)
+}
+
+/// Posts stored for a game, newest first
+pub async fn game_posts(
+    database: &Database,
+    league: &str,
+    game_id: &str,
+) -> Result<Vec<SocialPost>> {
+    let client = database.client().await?;
+    let statement = client.prepare_cached(GAME_POSTS).await?;
+    let rows = client.query(&statement, &[&league, &game_id]).await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| SocialPost {
+            platform: row.get("platform"),
+            platform_post_id: row.get("platform_post_id"),
+            author_handle: row.get("author_handle"),
+            author_name: row.get("author_name"),
+            author_avatar_url: row.get("author_avatar_url"),
+            author_verified: row
+                .get::<_, Option<bool>>("author_verified")
+                .unwrap_or(false),
+            content: row.get("content"),
+            media_urls: row
+                .get::<_, Option<Vec<String>>>("media_urls")
+                .unwrap_or_default(),
+            game_id: Some(game_id.to_string()),
+            team_ids: row
+                .get::<_, Option<Vec<String>>>("team_abbreviations")
+                .unwrap_or_default(),
+            league_id: Some(league.to_string()),
+            hashtags: row
+                .get::<_, Option<Vec<String>>>("hashtags")
+                .unwrap_or_default(),
+            mentions: row
+                .get::<_, Option<Vec<String>>>("mentions")
+                .unwrap_or_default(),
+            like_count: row.get::<_, Option<i32>>("like_count").unwrap_or(0) as i64,
+            repost_count: row.get::<_, Option<i32>>("repost_count").unwrap_or(0) as i64,
+            comment_count: row.get::<_, Option<i32>>("comment_count").unwrap_or(0) as i64,
+            post_url: row.get("post_url"),
+            posted_at: row.get::<_, DateTime<Utc>>("posted_at").to_rfc3339(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn teams() -> Vec<TeamSocialConfig> {
+        vec![TeamSocialConfig {
+            league: "mlb".to_string(),
+            team: "PHI".to_string(),
+            hashtags: vec!["#RingTheBell".to_string()],
+            handles: vec!["Phillies".to_string()],
+            beat_reporters: vec!["@ToddZolecki".to_string()],
+        }]
+    }
+
+    #[test]
+    fn test_game_query_uses_team_terms_and_window() {
+        let start = DateTime::parse_from_rfc3339("2025-07-04T23:05:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let query = game_query("phi", "NYM", &teams(), game_window(start, 60, 300));
+        assert_eq!(
+            query,
+            "(#RingTheBell OR from:Phillies OR @Phillies OR from:ToddZolecki OR \"NYM\") \
+             since:2025-07-04_22:05:00_UTC until:2025-07-05_04:05:00_UTC -filter:retweets"
+        );
+    }
+
+    #[test]
+    fn test_upsert_keeps_one_row_per_post() {
+        let tweet: TwitterTweet = serde_json::from_value(serde_json::json!({
+            "type": "tweet", "id": "1", "url": "https://x.com/i/1",
+            "text": "Walk-off! #RingTheBell @Phillies", "source": "web",
+            "retweetCount": 1, "replyCount": 2, "likeCount": 3, "quoteCount": 0,
+            "viewCount": 10, "createdAt": "Sat Jul 05 02:00:00 +0000 2025",
+            "lang": "en", "bookmarkCount": 0,
+            "author": {"userName": "fan", "isBlueVerified": true}
+        }))
+        .unwrap();
+        let post = SocialPost::from_tweet(&tweet, "mlb", "123", &["PHI", "NYM"]);
+        assert_eq!(post.hashtags, ["RingTheBell"]);
+        assert_eq!(post.mentions, ["Phillies"]);
+        assert!(post.author_verified);
+
+        let (posts, inserted) = upsert_posts(vec![post.clone()], vec![post]);
+        assert_eq!((posts.len(), inserted), (1, 0));
+    }
+}
@@ -0,0 +1,1145 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+use crate::{
+    path::League,
+    schema::sportradar::{
+        mlb::standings::MLBStanding, nba::standings::NBAStanding,
+        nfl::standings::NFLStanding,
+    },
+};
+
+/// Game statuses that mean a game is over and counted in the standings
+const FINAL_STATUSES: [&str; 4] = ["final", "f/ot", "f/so", "forfeit"];
+
+/// Game statuses that mean a game will never be played
+const VOID_STATUSES: [&str; 2] = ["canceled", "cancelled"];
+
+/// Normalized team record shared by every league's standings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamRecord {
+    pub team: String,
+    pub name: String,
+    pub conference: String,
+    pub division: String,
+    pub wins: i32,
+    pub losses: i32,
+    pub ties: i32,
+    pub division_wins: i32,
+    pub division_losses: i32,
+    pub conference_wins: i32,
+    pub conference_losses: i32,
+    pub points_for: f64,
+    pub points_against: f64,
+}
+
+impl TeamRecord {
+    pub fn games_played(&self) -> i32 {
+        self.wins + self.losses + self.ties
+    }
+
+    /// Wins with ties counted as half a win
+    pub fn win_value(&self) -> f64 {
+        self.wins as f64 + self.ties as f64 / 2.0
+    }
+
+    pub fn win_pct(&self) -> f64 {
+        pct(self.win_value(), self.games_played())
+    }
+
+    pub fn division_pct(&self) -> f64 {
+        pct(
+            self.division_wins as f64,
+            self.division_wins + self.division_losses,
+        )
+    }
+
+    pub fn conference_pct(&self) -> f64 {
+        pct(
+            self.conference_wins as f64,
+            self.conference_wins + self.conference_losses,
+        )
+    }
+
+    pub fn point_differential(&self) -> f64 {
+        self.points_for - self.points_against
+    }
+
+    fn division_key(&self) -> String {
+        format!("{} {}", self.conference, self.division)
+    }
+}
+
+fn pct(wins: f64, games: i32) -> f64 {
+    if games <= 0 {
+        0.0
+    } else {
+        wins / games as f64
+    }
+}
+
+impl From<&MLBStanding> for TeamRecord {
+    fn from(standing: &MLBStanding) -> Self {
+        Self {
+            team: standing.key.clone(),
+            name: standing.name.clone(),
+            conference: standing.league.clone(),
+            division: standing.division.clone(),
+            wins: standing.wins,
+            losses: standing.losses,
+            ties: 0,
+            division_wins: standing.division_wins,
+            division_losses: standing.division_losses,
+            conference_wins: 0,
+            conference_losses: 0,
+            points_for: standing.runs_scored as f64,
+            points_against: standing.runs_against as f64,
+        }
+    }
+}
+
+impl From<&NFLStanding> for TeamRecord {
+    fn from(standing: &NFLStanding) -> Self {
+        Self {
+            team: standing.team.clone(),
+            name: standing.name.clone(),
+            conference: standing.conference.clone(),
+            division: standing.division.clone(),
+            wins: standing.wins,
+            losses: standing.losses,
+            ties: standing.ties,
+            division_wins: standing.division_wins,
+            division_losses: standing.division_losses,
+            conference_wins: standing.conference_wins,
+            conference_losses: standing.conference_losses,
+            points_for: standing.points_for as f64,
+            points_against: standing.points_against as f64,
+        }
+    }
+}
+
+impl From<&NBAStanding> for TeamRecord {
+    fn from(standing: &NBAStanding) -> Self {
+        let games = (standing.wins + standing.losses) as f64;
+        Self {
+            team: standing.key.clone(),
+            name: standing.name.clone(),
+            conference: standing.conference.clone(),
+            division: standing.division.clone(),
+            wins: standing.wins,
+            losses: standing.losses,
+            ties: 0,
+            division_wins: standing.division_wins,
+            division_losses: standing.division_losses,
+            conference_wins: standing.conference_wins,
+            conference_losses: standing.conference_losses,
+            points_for: standing.points_per_game_for * games,
+            points_against: standing.points_per_game_against * games,
+        }
+    }
+}
+
+/// A single regular season game reduced to what the analytics need
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledGame {
+    /// Upstream game identifier, empty when the payload carries none
+    #[serde(default)]
+    pub game_id: String,
+    /// Game day as sent upstream (`YYYY-MM-DDT00:00:00`)
+    #[serde(default)]
+    pub day: String,
+    /// Scheduled start as sent upstream (US Eastern, no offset), when known
+    #[serde(default)]
+    pub date_time: Option<String>,
+    pub home: String,
+    pub away: String,
+    pub home_score: Option<i32>,
+    pub away_score: Option<i32>,
+    pub is_final: bool,
+}
+
+impl ScheduledGame {
+    /// Winner and loser of a completed game, `None` for ties and unplayed games
+    pub fn result(&self) -> Option<(&str, &str)> {
+        if !self.is_final {
+            return None;
+        }
+        match (self.home_score, self.away_score) {
+            (Some(home), Some(away)) if home > away => {
+                Some((self.home.as_str(), self.away.as_str()))
+            }
+            (Some(home), Some(away)) if away > home => {
+                Some((self.away.as_str(), self.home.as_str()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse the raw SportsData schedule payload into regular season games
+///
+/// Bye weeks, canceled games and postseason games are skipped. Scores are read
+/// from whichever key the league uses (`HomeTeamRuns`, `HomeTeamScore` or `HomeScore`).
+pub fn parse_schedule(games: &[serde_json::Value]) -> Vec<ScheduledGame> {
+    games
+        .iter()
+        .filter_map(|game| {
+            let home = game.get("HomeTeam")?.as_str()?;
+            let away = game.get("AwayTeam")?.as_str()?;
+            if home.eq_ignore_ascii_case("BYE") || away.eq_ignore_ascii_case("BYE") {
+                return None;
+            }
+
+            let season_type = game.get("SeasonType").and_then(|v| v.as_i64());
+            if season_type.is_some_and(|season_type| season_type != 1) {
+                return None;
+            }
+
+            let status = game
+                .get("Status")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Scheduled")
+                .to_lowercase();
+            let canceled = game
+                .get("Canceled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if canceled || VOID_STATUSES.contains(&status.as_str()) {
+                return None;
+            }
+
+            let is_final = FINAL_STATUSES.contains(&status.as_str())
+                || game
+                    .get("IsClosed")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+
+            let score = |keys: &[&str]| {
+                keys.iter()
+                    .find_map(|key| game.get(*key).and_then(|v| v.as_i64()))
+                    .map(|score| score as i32)
+            };
+
+            let text = |keys: &[&str]| {
+                keys.iter()
+                    .find_map(|key| match game.get(*key)? {
+                        serde_json::Value::String(value) => Some(value.clone()),
+                        serde_json::Value::Number(value) => Some(value.to_string()),
+                        _ => None,
+                    })
+                    .unwrap_or_default()
+            };
+
+            Some(ScheduledGame {
+                game_id: text(&[
+                    "GameID",
+                    "GameId",
+                    "GlobalGameID",
+                    "ScoreID",
+                    "GameKey",
+                ]),
+                day: text(&["Day", "Date", "DateTime"]),
+                date_time: game
+                    .get("DateTime")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                home: home.to_string(),
+                away: away.to_string(),
+                home_score: score(&["HomeTeamRuns", "HomeTeamScore", "HomeScore"]),
+                away_score: score(&["AwayTeamRuns", "AwayTeamScore", "AwayScore"]),
+                is_final,
+            })
+        })
+        .collect()
+}
+
+/// Postseason qualification rules for a league
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayoffFormat {
+    /// Regular season length per team
+    pub season_games: i32,
+    /// Whether division winners are guaranteed the top seeds
+    pub division_winners_seeded: bool,
+    /// Seeds per conference that go straight to the playoffs
+    pub playoff_spots: usize,
+    /// Seeds per conference that reach the postseason at all, play-in included
+    pub play_in_spots: usize,
+}
+
+impl PlayoffFormat {
+    pub fn for_league(league: &League) -> Option<Self> {
+        match league {
+            League::Mlb => Some(Self {
+                season_games: 162,
+                division_winners_seeded: true,
+                playoff_spots: 6,
+                play_in_spots: 6,
+            }),
+            League::Nfl => Some(Self {
+                season_games: 17,
+                division_winners_seeded: true,
+                playoff_spots: 7,
+                play_in_spots: 7,
+            }),
+            League::Nba => Some(Self {
+                season_games: 82,
+                division_winners_seeded: false,
+                playoff_spots: 6,
+                play_in_spots: 10,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn has_play_in(&self) -> bool {
+        self.play_in_spots > self.playoff_spots
+    }
+}
+
+/// Derived standings numbers for a single team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamAnalytics {
+    pub team: String,
+    pub name: String,
+    pub conference: String,
+    pub division: String,
+    pub wins: i32,
+    pub losses: i32,
+    pub ties: i32,
+    pub win_pct: f64,
+    pub games_remaining: i32,
+    /// Games behind the division leader
+    pub games_back: f64,
+    /// Games behind the conference leader
+    pub conference_games_back: f64,
+    /// Games behind the final wild card spot; negative values lead the first team out
+    pub wild_card_games_back: Option<f64>,
+    /// Division magic number, only set for the division leader
+    pub magic_number: Option<i32>,
+    /// Division elimination number, only set for teams trailing the leader
+    pub elimination_number: Option<i32>,
+    /// Playoff magic number against the first team out
+    pub playoff_magic_number: Option<i32>,
+    /// Playoff elimination number against the team holding the final spot
+    pub playoff_elimination_number: Option<i32>,
+    pub clinched_division: bool,
+    pub clinched_playoffs: bool,
+    pub eliminated_division: bool,
+    pub eliminated_playoffs: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clinched_play_in: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eliminated_play_in: Option<bool>,
+    pub seed: Option<u32>,
+}
+
+/// A seeded team in the current playoff picture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayoffSeed {
+    pub seed: u32,
+    pub team: String,
+    pub name: String,
+    pub division_leader: bool,
+    pub play_in: bool,
+}
+
+/// Current playoff seeding for one conference (or MLB league)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConferencePicture {
+    pub conference: String,
+    pub seeds: Vec<PlayoffSeed>,
+    pub first_out: Option<String>,
+}
+
+/// Full analytics payload for a league's standings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandingsAnalytics {
+    pub league: String,
+    pub season: i32,
+    pub teams: Vec<TeamAnalytics>,
+    pub playoff_picture: Vec<ConferencePicture>,
+    pub generated_at: String,
+}
+
+/// Standings plus remaining schedule for one league, ready to be analyzed
+#[derive(Debug, Clone)]
+pub struct LeagueTable {
+    format: PlayoffFormat,
+    teams: Vec<TeamRecord>,
+    remaining: HashMap<String, i32>,
+    head_to_head_wins: HashMap<(String, String), i32>,
+    remaining_head_to_head: HashMap<(String, String), i32>,
+}
+
+impl LeagueTable {
+    /// Build a table from the current standings and the season schedule
+    ///
+    /// When the schedule is empty (e.g. a past season) remaining games fall back
+    /// to the season length minus games played.
+    pub fn new(
+        format: PlayoffFormat,
+        teams: Vec<TeamRecord>,
+        schedule: &[ScheduledGame],
+    ) -> Self {
+        let mut remaining: HashMap<String, i32> = HashMap::new();
+        let mut head_to_head_wins = HashMap::new();
+        let mut remaining_head_to_head = HashMap::new();
+
+        for game in schedule {
+            if game.is_final {
+                if let Some((winner, loser)) = game.result() {
+                    *head_to_head_wins
+                        .entry((winner.to_string(), loser.to_string()))
+                        .or_insert(0) += 1;
+                }
+            } else {
+                *remaining.entry(game.home.clone()).or_insert(0) += 1;
+                *remaining.entry(game.away.clone()).or_insert(0) += 1;
+                *remaining_head_to_head
+                    .entry(pair_key(&game.home, &game.away))
+                    .or_insert(0) += 1;
+            }
+        }
+
+        if schedule.is_empty() {
+            for team in &teams {
+                remaining.insert(
+                    team.team.clone(),
+                    (format.season_games - team.games_played()).max(0),
+                );
+            }
+        }
+
+        Self {
+            format,
+            teams,
+            remaining,
+            head_to_head_wins,
+            remaining_head_to_head,
+        }
+    }
+
+    pub fn format(&self) -> PlayoffFormat {
+        self.format
+    }
+
+    pub fn teams(&self) -> &[TeamRecord] {
+        &self.teams
+    }
+
+    pub fn games_remaining(&self, team: &str) -> i32 {
+        self.remaining.get(team).copied().unwrap_or(0)
+    }
+
+    /// Best possible final win value if the team wins out
+    pub fn max_wins(&self, team: &TeamRecord) -> f64 {
+        team.win_value() + self.games_remaining(&team.team) as f64
+    }
+
+    pub fn head_to_head(&self, team: &str, opponent: &str) -> (i32, i32) {
+        let wins = self
+            .head_to_head_wins
+            .get(&(team.to_string(), opponent.to_string()))
+            .copied()
+            .unwrap_or(0);
+        let losses = self
+            .head_to_head_wins
+            .get(&(opponent.to_string(), team.to_string()))
+            .copied()
+            .unwrap_or(0);
+        (wins, losses)
+    }
+
+    /// Whether `team` has locked up the head-to-head tiebreaker over `opponent`
+    ///
+    /// Returns `Some(true)` if it can no longer be overturned, `Some(false)` if the
+    /// opponent has locked it up, and `None` while it is still undecided.
+    pub fn holds_tiebreaker(&self, team: &str, opponent: &str) -> Option<bool> {
+        let (wins, losses) = self.head_to_head(team, opponent);
+        let left = self
+            .remaining_head_to_head
+            .get(&pair_key(team, opponent))
+            .copied()
+            .unwrap_or(0);
+
+        if wins > losses + left {
+            Some(true)
+        } else if losses > wins + left {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// `team` finishes ahead of `other` no matter how the rest of the season goes
+    fn guaranteed_ahead(&self, team: &TeamRecord, other: &TeamRecord) -> bool {
+        let team_wins = team.win_value();
+        let other_max = self.max_wins(other);
+        team_wins > other_max
+            || (team_wins == other_max
+                && self.holds_tiebreaker(&team.team, &other.team) == Some(true))
+    }
+
+    /// Wins plus opponent losses `team` needs to finish ahead of `chaser`
+    fn magic_number(&self, team: &TeamRecord, chaser: &TeamRecord) -> i32 {
+        let mut magic = (self.max_wins(chaser) - team.win_value()).floor() as i32 + 1;
+        if self.holds_tiebreaker(&team.team, &chaser.team) == Some(true) {
+            magic -= 1;
+        }
+        magic.max(0)
+    }
+
+    /// Order teams the way the league breaks ties, best first
+    ///
+    /// Win percentage decides first. Two-team ties go to head-to-head record, then
+    /// division record, conference record and point differential.
+    pub fn rank(&self, teams: &[&TeamRecord]) -> Vec<TeamRecord> {
+        let mut ranked: Vec<TeamRecord> =
+            teams.iter().map(|team| (*team).clone()).collect();
+        ranked.sort_by(|a, b| {
+            cmp_desc(a.win_pct(), b.win_pct())
+                .then_with(|| {
+                    if a.division_key() == b.division_key() {
+                        cmp_desc(a.division_pct(), b.division_pct())
+                    } else {
+                        Ordering::Equal
+                    }
+                })
+                .then_with(|| cmp_desc(a.conference_pct(), b.conference_pct()))
+                .then_with(|| cmp_desc(a.point_differential(), b.point_differential()))
+                .then_with(|| a.team.cmp(&b.team))
+        });
+
+        let mut index = 0;
+        while index < ranked.len() {
+            let mut end = index + 1;
+            while end < ranked.len() && ranked[end].win_pct() == ranked[index].win_pct() {
+                end += 1;
+            }
+            if end - index == 2 {
+                let (wins, losses) =
+                    self.head_to_head(&ranked[index + 1].team, &ranked[index].team);
+                if wins > losses {
+                    ranked.swap(index, index + 1);
+                }
+            }
+            index = end;
+        }
+
+        ranked
+    }
+
+    /// Compute per-team analytics and the playoff picture
+    pub fn analyze(&self) -> (Vec<TeamAnalytics>, Vec<ConferencePicture>) {
+        let conferences = self.conferences();
+
+        let mut analytics = Vec::with_capacity(self.teams.len());
+        let mut pictures = Vec::with_capacity(conferences.len());
+
+        for (conference, members) in conferences {
+            let division_ranks = self.division_ranks(&members);
+            let leaders: Vec<String> = division_ranks
+                .values()
+                .filter_map(|ranked| ranked.first().map(|team| team.team.clone()))
+                .collect();
+
+            let picture = self.seed_conference(&conference, &members, &division_ranks);
+            let seeds: HashMap<&str, u32> = picture
+                .seeds
+                .iter()
+                .map(|seed| (seed.team.as_str(), seed.seed))
+                .collect();
+
+            let conference_ranked = self.rank(&members);
+            let conference_leader = conference_ranked.first();
+
+            let last_in =
+                picture
+                    .seeds
+                    .iter()
+                    .rfind(|seed| !seed.division_leader && !seed.play_in)
+                    .and_then(|seed| members.iter().find(|team| team.team == seed.team))
+                    .or_else(|| {
+                        picture.seeds.iter().rfind(|seed| !seed.play_in).and_then(
+                            |seed| members.iter().find(|team| team.team == seed.team),
+                        )
+                    })
+                    .copied();
+            let first_out = picture
+                .first_out
+                .as_ref()
+                .and_then(|first_out| members.iter().find(|team| &team.team == first_out))
+                .copied();
+
+            for team in &members {
+                let ranked = &division_ranks[&team.division];
+                let leader = &ranked[0];
+                let is_leader = leader.team == team.team;
+                let rivals: Vec<&TeamRecord> = members
+                    .iter()
+                    .filter(|other| other.team != team.team)
+                    .copied()
+                    .collect();
+                let division_rivals: Vec<&TeamRecord> = rivals
+                    .iter()
+                    .filter(|other| other.division == team.division)
+                    .copied()
+                    .collect();
+
+                let clinched_division = is_leader
+                    && division_rivals
+                        .iter()
+                        .all(|other| self.guaranteed_ahead(team, other));
+                let eliminated_division = division_rivals
+                    .iter()
+                    .any(|other| self.guaranteed_ahead(other, team));
+
+                let magic_number = if is_leader {
+                    ranked.get(1).map(|chaser| self.magic_number(team, chaser))
+                } else {
+                    None
+                };
+                let elimination_number = if is_leader {
+                    None
+                } else {
+                    Some(self.magic_number(leader, team))
+                };
+
+                let (clinched_playoffs, eliminated_playoffs) = self.qualification(
+                    team,
+                    &rivals,
+                    self.format.playoff_spots,
+                    clinched_division,
+                    eliminated_division,
+                );
+                let (clinched_play_in, eliminated_play_in) = if self.format.has_play_in()
+                {
+                    let (clinched, eliminated) = self.qualification(
+                        team,
+                        &rivals,
+                        self.format.play_in_spots,
+                        false,
+                        true,
+                    );
+                    (Some(clinched), Some(eliminated))
+                } else {
+                    (None, None)
+                };
+
+                let seed = seeds.get(team.team.as_str()).copied();
+                let in_playoff_spot =
+                    seed.is_some_and(|seed| seed as usize <= self.format.playoff_spots);
+
+                let wild_card_games_back = if self.format.division_winners_seeded
+                    && leaders.contains(&team.team)
+                {
+                    None
+                } else if in_playoff_spot {
+                    first_out.map(|first_out| -games_back(team, first_out))
+                } else {
+                    last_in.map(|last_in| games_back(last_in, team))
+                };
+
+                let playoff_magic_number = if in_playoff_spot {
+                    first_out.map(|first_out| self.magic_number(team, first_out))
+                } else {
+                    None
+                };
+                let playoff_elimination_number = if in_playoff_spot {
+                    None
+                } else {
+                    last_in.map(|last_in| self.magic_number(last_in, team))
+                };
+
+                analytics.push(TeamAnalytics {
+                    team: team.team.clone(),
+                    name: team.name.clone(),
+                    conference: team.conference.clone(),
+                    division: team.division.clone(),
+                    wins: team.wins,
+                    losses: team.losses,
+                    ties: team.ties,
+                    win_pct: team.win_pct(),
+                    games_remaining: self.games_remaining(&team.team),
+                    games_back: games_back(leader, team),
+                    conference_games_back: conference_leader
+                        .map(|leader| games_back(leader, team))
+                        .unwrap_or(0.0),
+                    wild_card_games_back,
+                    magic_number,
+                    elimination_number,
+                    playoff_magic_number,
+                    playoff_elimination_number,
+                    clinched_division,
+                    clinched_playoffs,
+                    eliminated_division,
+                    eliminated_playoffs,
+                    clinched_play_in,
+                    eliminated_play_in,
+                    seed,
+                });
+            }
+
+            pictures.push(picture);
+        }
+
+        (analytics, pictures)
+    }
+
+    /// Current playoff seeding per conference, without the per-team numbers
+    pub fn playoff_picture(&self) -> Vec<ConferencePicture> {
+        self.conferences()
+            .into_iter()
+            .map(|(conference, members)| {
+                let division_ranks = self.division_ranks(&members);
+                self.seed_conference(&conference, &members, &division_ranks)
+            })
+            .collect()
+    }
+
+    /// Team keys currently leading each division
+    pub fn division_leaders(&self) -> Vec<String> {
+        self.conferences()
+            .into_iter()
+            .flat_map(|(_, members)| {
+                self.division_ranks(&members)
+                    .into_values()
+                    .filter_map(|ranked| ranked.into_iter().next())
+                    .map(|team| team.team)
+            })
+            .collect()
+    }
+
+    /// Record a game result, moving it from the remaining schedule into the standings
+    pub fn record_result(&mut self, winner: &str, loser: &str) {
+        for team in self.teams.iter_mut() {
+            if team.team == winner {
+                team.wins += 1;
+            } else if team.team == loser {
+                team.losses += 1;
+            }
+        }
+        for team in [winner, loser] {
+            if let Some(remaining) = self.remaining.get_mut(team) {
+                *remaining = (*remaining - 1).max(0);
+            }
+        }
+        if let Some(left) = self
+            .remaining_head_to_head
+            .get_mut(&pair_key(winner, loser))
+        {
+            *left = (*left - 1).max(0);
+        }
+        *self
+            .head_to_head_wins
+            .entry((winner.to_string(), loser.to_string()))
+            .or_insert(0) += 1;
+    }
+
+    fn conferences(&self) -> BTreeMap<String, Vec<&TeamRecord>> {
+        let mut conferences: BTreeMap<String, Vec<&TeamRecord>> = BTreeMap::new();
+        for team in &self.teams {
+            conferences
+                .entry(team.conference.clone())
+                .or_default()
+                .push(team);
+        }
+        conferences
+    }
+
+    fn division_ranks(
+        &self,
+        members: &[&TeamRecord],
+    ) -> HashMap<String, Vec<TeamRecord>> {
+        let mut divisions: BTreeMap<String, Vec<&TeamRecord>> = BTreeMap::new();
+        for team in members {
+            divisions
+                .entry(team.division.clone())
+                .or_default()
+                .push(team);
+        }
+        divisions
+            .iter()
+            .map(|(division, teams)| (division.clone(), self.rank(teams)))
+            .collect()
+    }
+
+    /// Clinched / eliminated flags for finishing within the top `spots` of the conference
+    fn qualification(
+        &self,
+        team: &TeamRecord,
+        rivals: &[&TeamRecord],
+        spots: usize,
+        clinched_division: bool,
+        eliminated_division: bool,
+    ) -> (bool, bool) {
+        let threats = rivals
+            .iter()
+            .filter(|other| !self.guaranteed_ahead(team, other))
+            .count();
+        let ahead = rivals
+            .iter()
+            .filter(|other| self.guaranteed_ahead(other, team))
+            .count();
+
+        let division_path = self.format.division_winners_seeded;
+        let clinched = threats < spots || (division_path && clinched_division);
+        let eliminated = ahead >= spots && (!division_path || eliminated_division);
+        (clinched, eliminated)
+    }
+
+    fn seed_conference(
+        &self,
+        conference: &str,
+        members: &[&TeamRecord],
+        division_ranks: &HashMap<String, Vec<TeamRecord>>,
+    ) -> ConferencePicture {
+        let ordered: Vec<(TeamRecord, bool)> = if self.format.division_winners_seeded {
+            let leaders: Vec<&TeamRecord> = division_ranks
+                .values()
+                .filter_map(|ranked| ranked.first())
+                .collect();
+            let rest: Vec<&TeamRecord> = members
+                .iter()
+                .filter(|team| !leaders.iter().any(|leader| leader.team == team.team))
+                .copied()
+                .collect();
+            self.rank(&leaders)
+                .into_iter()
+                .map(|team| (team, true))
+                .chain(self.rank(&rest).into_iter().map(|team| (team, false)))
+                .collect()
+        } else {
+            let leaders: Vec<String> = division_ranks
+                .values()
+                .filter_map(|ranked| ranked.first().map(|team| team.team.clone()))
+                .collect();
+            self.rank(members)
+                .into_iter()
+                .map(|team| {
+                    let is_leader = leaders.contains(&team.team);
+                    (team, is_leader)
+                })
+                .collect()
+        };
+
+        let seeds = ordered
+            .iter()
+            .take(self.format.play_in_spots)
+            .enumerate()
+            .map(|(index, (team, division_leader))| PlayoffSeed {
+                seed: index as u32 + 1,
+                team: team.team.clone(),
+                name: team.name.clone(),
+                division_leader: *division_leader,
+                play_in: index >= self.format.playoff_spots,
+            })
+            .collect();
+
+        ConferencePicture {
+            conference: conference.to_string(),
+            seeds,
+            first_out: ordered
+                .get(self.format.play_in_spots)
+                .map(|(team, _)| team.team.clone()),
+        }
+    }
+}
+
+/// Games `team` trails `leader` by
+pub fn games_back(leader: &TeamRecord, team: &TeamRecord) -> f64 {
+    ((leader.wins - team.wins) + (team.losses - leader.losses)) as f64 / 2.0
+}
+
+fn cmp_desc(a: f64, b: f64) -> Ordering {
+    b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+}
+
+fn pair_key(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+/// Analyze a league's standings against its remaining schedule
+pub fn analyze_standings(
+    league: &League,
+    season: i32,
+    teams: Vec<TeamRecord>,
+    schedule: &[ScheduledGame],
+) -> Option<StandingsAnalytics> {
+    let format = PlayoffFormat::for_league(league)?;
+    let table = LeagueTable::new(format, teams, schedule);
+    let (teams, playoff_picture) = table.analyze();
+
+    Some(StandingsAnalytics {
+        league: league.to_string(),
+        season,
+        teams,
+        playoff_picture,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+
+    /// A team whose points scored and allowed follow its record
+    pub fn record(team: &str, division: &str, wins: i32, losses: i32) -> TeamRecord {
+        TeamRecord {
+            team: team.to_string(),
+            name: team.to_string(),
+            conference: "AL".to_string(),
+            division: division.to_string(),
+            wins,
+            losses,
+            ties: 0,
+            division_wins: 0,
+            division_losses: 0,
+            conference_wins: 0,
+            conference_losses: 0,
+            points_for: (wins * 5) as f64,
+            points_against: (losses * 5) as f64,
+        }
+    }
+
+    /// A regular season game, final when it has a `(home, away)` score
+    pub fn game(home: &str, away: &str, score: Option<(i32, i32)>) -> ScheduledGame {
+        ScheduledGame {
+            game_id: String::new(),
+            day: String::new(),
+            date_time: None,
+            home: home.to_string(),
+            away: away.to_string(),
+            home_score: score.map(|(home, _)| home),
+            away_score: score.map(|(_, away)| away),
+            is_final: score.is_some(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{test_support::*, *};
+
+    #[test]
+    fn test_parse_schedule_skips_byes_and_postseason() {
+        let raw = serde_json::json!([
+            {"HomeTeam": "NYY", "AwayTeam": "BOS", "Status": "Final", "SeasonType": 1,
+             "HomeTeamRuns": 5, "AwayTeamRuns": 3},
+            {"HomeTeam": "NYY", "AwayTeam": "BOS", "Status": "Scheduled", "SeasonType": 1},
+            {"HomeTeam": "KC", "AwayTeam": "BYE", "Status": "Scheduled", "SeasonType": 1},
+            {"HomeTeam": "NYY", "AwayTeam": "BOS", "Status": "Scheduled", "SeasonType": 3}
+        ]);
+        let games = parse_schedule(raw.as_array().unwrap());
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].result(), Some(("NYY", "BOS")));
+        assert!(!games[1].is_final);
+    }
+
+    #[test]
+    fn test_magic_number_and_clinch() {
+        let format = PlayoffFormat::for_league(&League::Mlb).unwrap();
+        let teams = vec![record("NYY", "East", 95, 60), record("BOS", "East", 85, 70)];
+        let schedule: Vec<ScheduledGame> = (0..7)
+            .map(|_| game("NYY", "TOR", None))
+            .chain((0..7).map(|_| game("BOS", "TOR", None)))
+            .collect();
+        let table = LeagueTable::new(format, teams, &schedule);
+        let (analytics, _) = table.analyze();
+
+        let nyy = analytics.iter().find(|t| t.team == "NYY").unwrap();
+        let bos = analytics.iter().find(|t| t.team == "BOS").unwrap();
+
+        // BOS can reach 92 wins, NYY already has 95
+        assert!(nyy.clinched_division);
+        assert_eq!(nyy.magic_number, Some(0));
+        assert!(bos.eliminated_division);
+        assert_eq!(bos.games_back, 10.0);
+    }
+
+    #[test]
+    fn test_head_to_head_breaks_two_team_tie() {
+        let format = PlayoffFormat::for_league(&League::Nfl).unwrap();
+        let teams = vec![record("BUF", "East", 10, 7), record("MIA", "East", 10, 7)];
+        let schedule = vec![
+            game("MIA", "BUF", Some((10, 20))),
+            game("BUF", "MIA", Some((31, 3))),
+        ];
+        let table = LeagueTable::new(format, teams.clone(), &schedule);
+        let refs: Vec<&TeamRecord> = teams.iter().collect();
+        let ranked = table.rank(&refs);
+
+        assert_eq!(ranked[0].team, "BUF");
+        assert_eq!(table.holds_tiebreaker("BUF", "MIA"), Some(true));
+    }
+
+    #[test]
+    fn test_playoff_format_per_league() {
+        let mlb = PlayoffFormat::for_league(&League::Mlb).unwrap();
+        assert_eq!((mlb.season_games, mlb.playoff_spots), (162, 6));
+        assert!(mlb.division_winners_seeded);
+        assert!(!mlb.has_play_in());
+
+        let nfl = PlayoffFormat::for_league(&League::Nfl).unwrap();
+        assert_eq!((nfl.season_games, nfl.playoff_spots), (17, 7));
+        assert!(nfl.division_winners_seeded);
+        assert!(!nfl.has_play_in());
+
+        let nba = PlayoffFormat::for_league(&League::Nba).unwrap();
+        assert_eq!(
+            (nba.season_games, nba.playoff_spots, nba.play_in_spots),
+            (82, 6, 10)
+        );
+        assert!(!nba.division_winners_seeded);
+        assert!(nba.has_play_in());
+
+        assert!(PlayoffFormat::for_league(&League::Nhl).is_none());
+        assert!(PlayoffFormat::for_league(&League::Soccer).is_none());
+    }
+
+    #[test]
+    fn test_nfl_division_winners_take_top_four_and_seven_seed_is_last_in() {
+        let format = PlayoffFormat::for_league(&League::Nfl).unwrap();
+        let teams = vec![
+            record("E1", "East", 14, 3),
+            record("E2", "East", 12, 5),
+            record("N1", "North", 13, 4),
+            record("N2", "North", 11, 6),
+            record("S1", "South", 10, 7),
+            record("S2", "South", 8, 9),
+            record("W1", "West", 9, 8),
+            record("W2", "West", 7, 10),
+        ];
+        let table = LeagueTable::new(format, teams, &[]);
+        let (analytics, pictures) = table.analyze();
+
+        let seeds: Vec<&str> =
+            pictures[0].seeds.iter().map(|s| s.team.as_str()).collect();
+        // W1 wins the West at 9-8 and is seeded ahead of the 12-5 wild card
+        assert_eq!(seeds, ["E1", "N1", "S1", "W1", "E2", "N2", "S2"]);
+        assert!(pictures[0].seeds[..4].iter().all(|s| s.division_leader));
+        assert!(pictures[0].seeds[4..]
+            .iter()
+            .all(|s| !s.division_leader && !s.play_in));
+        assert_eq!(pictures[0].first_out.as_deref(), Some("W2"));
+
+        let s2 = analytics.iter().find(|t| t.team == "S2").unwrap();
+        let w2 = analytics.iter().find(|t| t.team == "W2").unwrap();
+        assert_eq!(s2.seed, Some(7));
+        assert!(s2.clinched_playoffs);
+        assert_eq!(w2.seed, None);
+        assert!(w2.eliminated_playoffs);
+    }
+
+    #[test]
+    fn test_nba_seeds_seven_through_ten_go_to_the_play_in() {
+        let format = PlayoffFormat::for_league(&League::Nba).unwrap();
+        let teams: Vec<TeamRecord> = (0..11)
+            .map(|index| {
+                let wins = 60 - index * 2;
+                record(&format!("T{:02}", index + 1), "Atlantic", wins, 82 - wins)
+            })
+            .collect();
+        let table = LeagueTable::new(format, teams, &[]);
+        let (analytics, pictures) = table.analyze();
+
+        let seeds = &pictures[0].seeds;
+        assert_eq!(seeds.len(), 10);
+        assert!(seeds[..6].iter().all(|s| !s.play_in));
+        assert!(seeds[6..].iter().all(|s| s.play_in));
+        assert_eq!(pictures[0].first_out.as_deref(), Some("T11"));
+
+        let seventh = analytics.iter().find(|t| t.team == "T07").unwrap();
+        assert!(!seventh.clinched_playoffs);
+        assert!(seventh.eliminated_playoffs);
+        assert_eq!(seventh.clinched_play_in, Some(true));
+
+        let eleventh = analytics.iter().find(|t| t.team == "T11").unwrap();
+        assert_eq!(eleventh.eliminated_play_in, Some(true));
+    }
+
+    #[test]
+    fn test_clinch_waits_on_games_left_against_each_other() {
+        let format = PlayoffFormat::for_league(&League::Mlb).unwrap();
+        let teams = vec![record("NYY", "East", 90, 66), record("BOS", "East", 84, 72)];
+        // NYY leads the series 4-3 with the last six games against each other
+        let schedule: Vec<ScheduledGame> = (0..4)
+            .map(|_| game("NYY", "BOS", Some((5, 2))))
+            .chain((0..3).map(|_| game("BOS", "NYY", Some((5, 2)))))
+            .chain((0..6).map(|_| game("NYY", "BOS", None)))
+            .collect();
+        let mut table = LeagueTable::new(format, teams, &schedule);
+
+        // BOS can still reach 90 wins and the series is undecided
+        let (analytics, _) = table.analyze();
+        let nyy = analytics.iter().find(|t| t.team == "NYY").unwrap();
+        assert_eq!(nyy.games_remaining, 6);
+        assert!(!nyy.clinched_division);
+        assert_eq!(nyy.magic_number, Some(1));
+
+        // One head-to-head win both adds a NYY win and takes one away from BOS
+        table.record_result("NYY", "BOS");
+        let (analytics, _) = table.analyze();
+        let nyy = analytics.iter().find(|t| t.team == "NYY").unwrap();
+        let bos = analytics.iter().find(|t| t.team == "BOS").unwrap();
+        assert!(nyy.clinched_division);
+        assert_eq!(nyy.magic_number, Some(0));
+        assert!(bos.eliminated_division);
+    }
+
+    #[test]
+    fn test_elimination_when_best_case_tie_loses_the_tiebreaker() {
+        let format = PlayoffFormat::for_league(&League::Mlb).unwrap();
+        let teams = vec![record("NYY", "East", 92, 64), record("BOS", "East", 86, 70)];
+        // NYY took the season series 8-5, so a tie at 92 wins goes to NYY
+        let schedule: Vec<ScheduledGame> = (0..8)
+            .map(|_| game("NYY", "BOS", Some((5, 2))))
+            .chain((0..5).map(|_| game("BOS", "NYY", Some((5, 2)))))
+            .chain((0..6).map(|_| game("NYY", "TOR", None)))
+            .chain((0..6).map(|_| game("BOS", "TOR", None)))
+            .collect();
+        let table = LeagueTable::new(format, teams, &schedule);
+        let (analytics, _) = table.analyze();
+
+        let nyy = analytics.iter().find(|t| t.team == "NYY").unwrap();
+        let bos = analytics.iter().find(|t| t.team == "BOS").unwrap();
+        assert_eq!(table.holds_tiebreaker("NYY", "BOS"), Some(true));
+        assert!(nyy.clinched_division);
+        assert!(bos.eliminated_division);
+        assert_eq!(bos.elimination_number, Some(0));
+    }
+
+    #[test]
+    fn test_division_tie_with_split_series_goes_to_division_record() {
+        let format = PlayoffFormat::for_league(&League::Nfl).unwrap();
+        let mut buf = record("BUF", "East", 10, 7);
+        buf.division_wins = 4;
+        buf.division_losses = 2;
+        let mut mia = record("MIA", "East", 10, 7);
+        mia.division_wins = 3;
+        mia.division_losses = 3;
+        let schedule = vec![
+            game("BUF", "MIA", Some((24, 17))),
+            game("MIA", "BUF", Some((27, 20))),
+        ];
+        let table = LeagueTable::new(format, vec![mia, buf], &schedule);
+        let (analytics, _) = table.analyze();
+
+        assert_eq!(table.holds_tiebreaker("BUF", "MIA"), None);
+        assert_eq!(table.division_leaders(), ["BUF"]);
+        let buf = analytics.iter().find(|t| t.team == "BUF").unwrap();
+        let mia = analytics.iter().find(|t| t.team == "MIA").unwrap();
+        assert_eq!(buf.seed, Some(1));
+        assert_eq!(mia.seed, Some(2));
+        assert_eq!(mia.games_back, 0.0);
+        assert!(!buf.clinched_division);
+    }
+}
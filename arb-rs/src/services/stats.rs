@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, collections::BTreeMap};
+
+/// Numeric keys that identify a row rather than measure anything
+const NON_STAT_KEYS: [&str; 3] = ["Season", "SeasonType", "Jersey"];
+
+/// Whether a stat line describes a player or a team
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StatKind {
+    #[default]
+    Player,
+    Team,
+}
+
+impl StatKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatKind::Player => "player",
+            StatKind::Team => "team",
+        }
+    }
+
+    fn id_key(&self) -> &'static str {
+        match self {
+            StatKind::Player => "PlayerID",
+            StatKind::Team => "TeamID",
+        }
+    }
+}
+
+/// Season-to-date totals for one player or team, with every numeric stat kept by name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatLine {
+    pub id: String,
+    pub name: String,
+    pub team: String,
+    pub position: Option<String>,
+    pub games: f64,
+    pub stats: BTreeMap<String, f64>,
+}
+
+impl StatLine {
+    /// Look up a stat by name, ignoring case
+    pub fn stat(&self, name: &str) -> Option<f64> {
+        self.stats.get(name).copied().or_else(|| {
+            self.stats
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| *value)
+        })
+    }
+}
+
+/// Every numeric field of a provider row except identifiers, keyed by field name
+pub fn numeric_stats(
+    object: &serde_json::Map<String, serde_json::Value>,
+) -> BTreeMap<String, f64> {
+    object
+        .iter()
+        .filter(|(key, _)| !key.ends_with("ID") && !NON_STAT_KEYS.contains(&key.as_str()))
+        .filter_map(|(key, value)| Some((key.clone(), value.as_f64()?)))
+        .collect()
+}
+
+/// Parse a provider season-stats payload into stat lines
+///
+/// Every numeric field except identifiers becomes a named stat, so leaderboards
+/// can sort by any column the provider sends.
+pub fn parse_stat_lines(rows: &[serde_json::Value], kind: StatKind) -> Vec<StatLine> {
+    rows.iter()
+        .filter_map(|row| {
+            let object = row.as_object()?;
+            let id = match object.get(kind.id_key())? {
+                serde_json::Value::Number(id) => id.to_string(),
+                serde_json::Value::String(id) => id.clone(),
+                _ => return None,
+            };
+            let text =
+                |key: &str| object.get(key).and_then(|v| v.as_str()).map(String::from);
+
+            let stats = numeric_stats(object);
+            let games = stats
+                .get("Games")
+                .or_else(|| stats.get("Played"))
+                .copied()
+                .unwrap_or(1.0);
+
+            Some(StatLine {
+                id,
+                name: text("Name").unwrap_or_default(),
+                team: text("Team").unwrap_or_default(),
+                position: text("Position"),
+                games,
+                stats,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Filters applied before ranking a leaderboard
+#[derive(Debug, Clone, Default)]
+pub struct LeaderboardFilter {
+    pub position: Option<String>,
+    pub team: Option<String>,
+    pub min_games: Option<f64>,
+    pub order: SortOrder,
+    pub limit: Option<usize>,
+}
+
+impl LeaderboardFilter {
+    pub fn matches(&self, line: &StatLine) -> bool {
+        let position_ok = self.position.as_ref().is_none_or(|position| {
+            line.position
+                .as_ref()
+                .is_some_and(|p| p.eq_ignore_ascii_case(position))
+        });
+        let team_ok = self
+            .team
+            .as_ref()
+            .is_none_or(|team| line.team.eq_ignore_ascii_case(team));
+        let games_ok = self.min_games.is_none_or(|min| line.games >= min);
+        position_ok && team_ok && games_ok
+    }
+}
+
+/// A ranked leaderboard row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    pub id: String,
+    pub name: String,
+    pub team: String,
+    pub position: Option<String>,
+    pub games: f64,
+    pub value: f64,
+}
+
+/// Rank lines by `stat`, returning `None` when no line carries that stat
+///
+/// Ties share a rank (1, 2, 2, 4).
+pub fn rank_leaderboard(
+    lines: &[StatLine],
+    stat: &str,
+    filter: &LeaderboardFilter,
+) -> Option<Vec<LeaderboardEntry>> {
+    if !lines.iter().any(|line| line.stat(stat).is_some()) {
+        return None;
+    }
+
+    let mut rows: Vec<(&StatLine, f64)> = lines
+        .iter()
+        .filter(|line| filter.matches(line))
+        .filter_map(|line| Some((line, line.stat(stat)?)))
+        .collect();
+    rows.sort_by(|(a_line, a), (b_line, b)| {
+        let ordering = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        match filter.order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+        .then_with(|| a_line.name.cmp(&b_line.name))
+    });
+
+    let mut entries: Vec<LeaderboardEntry> = Vec::with_capacity(rows.len());
+    for (index, (line, value)) in rows.into_iter().enumerate() {
+        let rank = match entries.last() {
+            Some(previous) if previous.value == value => previous.rank,
+            _ => index + 1,
+        };
+        entries.push(LeaderboardEntry {
+            rank,
+            id: line.id.clone(),
+            name: line.name.clone(),
+            team: line.team.clone(),
+            position: line.position.clone(),
+            games: line.games,
+            value,
+        });
+    }
+
+    if let Some(limit) = filter.limit {
+        entries.truncate(limit);
+    }
+    Some(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<StatLine> {
+        let raw = serde_json::json!([
+            {"PlayerID": 1, "Name": "Aaron Judge", "Team": "NYY", "Position": "RF",
+             "Games": 150, "HomeRuns": 50, "BattingAverage": 0.31, "TeamID": 9},
+            {"PlayerID": 2, "Name": "Cal Raleigh", "Team": "SEA", "Position": "C",
+             "Games": 148, "HomeRuns": 55, "BattingAverage": 0.25},
+            {"PlayerID": 3, "Name": "Backup", "Team": "SEA", "Position": "C",
+             "Games": 10, "HomeRuns": 50, "BattingAverage": 0.2},
+            {"Name": "No id"}
+        ]);
+        parse_stat_lines(raw.as_array().unwrap(), StatKind::Player)
+    }
+
+    #[test]
+    fn test_parse_keeps_numeric_stats_without_ids() {
+        let lines = rows();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].stat("homeruns"), Some(50.0));
+        assert!(lines[0].stat("TeamID").is_none());
+        assert_eq!(lines[0].games, 150.0);
+    }
+
+    #[test]
+    fn test_leaderboard_filters_and_shares_ranks() {
+        let lines = rows();
+        let board =
+            rank_leaderboard(&lines, "HomeRuns", &LeaderboardFilter::default()).unwrap();
+        assert_eq!(board[0].name, "Cal Raleigh");
+        assert_eq!((board[1].rank, board[2].rank), (2, 2));
+
+        let filter = LeaderboardFilter {
+            position: Some("c".to_string()),
+            min_games: Some(100.0),
+            ..Default::default()
+        };
+        let board = rank_leaderboard(&lines, "HomeRuns", &filter).unwrap();
+        assert_eq!(board.len(), 1);
+
+        assert!(rank_leaderboard(&lines, "Touchdowns", &filter).is_none());
+    }
+}
@@ -0,0 +1,460 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    schema::{reddit::RedditComment, twitterapi::tweet::TwitterTweet},
+    services::{
+        news::eastern_datetime,
+        sentiment::{score_text, Sentiment},
+    },
+};
+
+/// Largest page a timeline request can ask for
+pub const MAX_TIMELINE_PAGE: usize = 200;
+
+/// Where a timeline item came from; also the tie-break order at equal timestamps
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineSource {
+    Play,
+    Tweet,
+    Reddit,
+}
+
+impl TimelineSource {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TimelineSource::Play => "play",
+            TimelineSource::Tweet => "tweet",
+            TimelineSource::Reddit => "reddit",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "play" => Some(TimelineSource::Play),
+            "tweet" => Some(TimelineSource::Tweet),
+            "reddit" => Some(TimelineSource::Reddit),
+            _ => None,
+        }
+    }
+}
+
+/// Game clock at a point in the timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameClock {
+    /// Inning for MLB, quarter (5+ for overtime) for NFL and NBA
+    pub period: i64,
+    /// e.g. "Top 3", "Q2 5:31"
+    pub label: String,
+}
+
+/// One play, tweet or Reddit comment on a game's timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineItem {
+    pub id: String,
+    pub source: TimelineSource,
+    /// RFC 3339, UTC
+    pub timestamp: String,
+    /// Clock of the play this item happened at or after
+    pub game_clock: Option<GameClock>,
+    pub text: String,
+    pub author: Option<String>,
+    pub url: Option<String>,
+    /// Likes for tweets, upvotes for comments
+    pub score: Option<i64>,
+    /// Lexicon sentiment, for tweets and comments only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sentiment: Option<Sentiment>,
+    /// Raw provider play, for plays only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub play: Option<serde_json::Value>,
+    /// Index in the provider feed, for plays only; keeps plays that share a
+    /// timestamp in feed order
+    #[serde(skip)]
+    pub(crate) sequence: usize,
+}
+
+impl TimelineItem {
+    fn position(&self) -> TimelineCursor {
+        TimelineCursor {
+            timestamp: self.timestamp.clone(),
+            source: self.source,
+            sequence: self.sequence,
+            id: self.id.clone(),
+        }
+    }
+}
+
+fn utc(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Normalize RFC 3339, Twitter (`Tue Dec 10 07:00:30 +0000 2024`) and SportsData timestamps
+fn normalize_timestamp(value: &str) -> Option<String> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(utc(timestamp.with_timezone(&Utc)));
+    }
+    if let Ok(timestamp) = DateTime::parse_from_str(value, "%a %b %d %H:%M:%S %z %Y") {
+        return Some(utc(timestamp.with_timezone(&Utc)));
+    }
+    eastern_datetime(value).map(|timestamp| utc(timestamp.with_timezone(&Utc)))
+}
+
+fn play_clock(play: &serde_json::Value) -> Option<GameClock> {
+    let number = |key: &str| play.get(key).and_then(|v| v.as_i64());
+
+    if let Some(inning) = number("InningNumber") {
+        let half = match play.get("InningHalf").and_then(|v| v.as_str()) {
+            Some("B") => "Bot",
+            _ => "Top",
+        };
+        return Some(GameClock {
+            period: inning,
+            label: format!("{} {}", half, inning),
+        });
+    }
+
+    let quarter = number("Quarter").or_else(|| {
+        play.get("QuarterName")
+            .and_then(|v| v.as_str())
+            .and_then(|name| name.parse().ok())
+    })?;
+    let period_label = match quarter {
+        1..=4 => format!("Q{}", quarter),
+        5 => "OT".to_string(),
+        _ => format!("{}OT", quarter - 4),
+    };
+    let label = match (
+        number("TimeRemainingMinutes"),
+        number("TimeRemainingSeconds"),
+    ) {
+        (Some(minutes), Some(seconds)) => {
+            format!("{} {}:{:02}", period_label, minutes, seconds)
+        }
+        _ => period_label,
+    };
+    Some(GameClock {
+        period: quarter,
+        label,
+    })
+}
+
+/// Timeline items for provider plays, in feed order
+///
+/// A play's time is when it was created; `Updated` is only a fallback since
+/// corrections bump it long after the play. Plays without a timestamp, or
+/// stamped before the play ahead of them, take the time of the play before
+/// them (or of the first timestamped play when they lead the feed), so time
+/// never runs backwards through the feed.
+pub fn play_items(plays: &[serde_json::Value]) -> Vec<TimelineItem> {
+    let text = |play: &serde_json::Value, keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| play.get(*key).and_then(|v| v.as_str()))
+            .map(String::from)
+    };
+
+    let mut items: Vec<TimelineItem> = plays
+        .iter()
+        .enumerate()
+        .map(|(index, play)| {
+            let id = ["PlayID", "PlayId", "Sequence"]
+                .iter()
+                .find_map(|key| play.get(*key).filter(|v| !v.is_null()))
+                .map(|id| id.to_string().trim_matches('"').to_string())
+                .unwrap_or_else(|| index.to_string());
+            let timestamp = text(
+                play,
+                &[
+                    "Created",
+                    "PlayTime",
+                    "Timestamp",
+                    "timestamp",
+                    "Time",
+                    "Updated",
+                ],
+            )
+            .as_deref()
+            .and_then(normalize_timestamp)
+            .unwrap_or_default();
+
+            TimelineItem {
+                id,
+                source: TimelineSource::Play,
+                timestamp,
+                game_clock: play_clock(play),
+                text: text(play, &["Description", "Text", "Result"]).unwrap_or_default(),
+                author: None,
+                url: None,
+                score: None,
+                sentiment: None,
+                play: Some(play.clone()),
+                sequence: index,
+            }
+        })
+        .collect();
+
+    let first = items
+        .iter()
+        .find(|item| !item.timestamp.is_empty())
+        .map(|item| item.timestamp.clone())
+        .unwrap_or_default();
+    let mut previous = first;
+    for item in &mut items {
+        if item.timestamp.is_empty() || item.timestamp < previous {
+            item.timestamp = previous.clone();
+        } else {
+            previous = item.timestamp.clone();
+        }
+    }
+    items.retain(|item| !item.timestamp.is_empty());
+    items
+}
+
+pub fn tweet_items(tweets: &[TwitterTweet]) -> Vec<TimelineItem> {
+    tweets
+        .iter()
+        .filter_map(|tweet| {
+            Some(TimelineItem {
+                id: tweet.id.clone(),
+                source: TimelineSource::Tweet,
+                timestamp: normalize_timestamp(&tweet.created_at)?,
+                game_clock: None,
+                text: tweet.text.clone(),
+                author: tweet
+                    .additional_fields
+                    .pointer("/author/userName")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                url: Some(tweet.url.clone()),
+                score: Some(tweet.like_count),
+                sentiment: Some(score_text(&tweet.text)),
+                play: None,
+                sequence: 0,
+            })
+        })
+        .collect()
+}
+
+/// Timeline items for game thread comments, replies included
+pub fn comment_items(comments: &[RedditComment]) -> Vec<TimelineItem> {
+    let mut items = Vec::new();
+    let mut stack: Vec<&RedditComment> = comments.iter().collect();
+    while let Some(comment) = stack.pop() {
+        stack.extend(comment.replies.iter());
+        if let Some(timestamp) = normalize_timestamp(&comment.timestamp) {
+            items.push(TimelineItem {
+                id: comment.id.clone(),
+                source: TimelineSource::Reddit,
+                timestamp,
+                game_clock: None,
+                text: comment.content.clone(),
+                author: Some(comment.author.clone()),
+                url: Some(comment.permalink.clone()),
+                score: Some(comment.score as i64),
+                sentiment: Some(score_text(&comment.content)),
+                play: None,
+                sequence: 0,
+            });
+        }
+    }
+    items
+}
+
+/// Order items by time and give tweets and comments the clock of the latest play
+///
+/// Plays keep their feed order, with tweets and comments placed between them
+/// by time. Social items posted before the first play have no game clock.
+pub fn merge(mut items: Vec<TimelineItem>) -> Vec<TimelineItem> {
+    items.sort_by_key(|item| item.position());
+
+    let mut clock: Option<GameClock> = None;
+    for item in &mut items {
+        if item.source == TimelineSource::Play {
+            if item.game_clock.is_some() {
+                clock = item.game_clock.clone();
+            }
+        } else {
+            item.game_clock = clock.clone();
+        }
+    }
+    items
+}
+
+/// Position in a timeline; items are returned strictly after it
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimelineCursor {
+    timestamp: String,
+    source: TimelineSource,
+    sequence: usize,
+    id: String,
+}
+
+impl TimelineCursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}|{}|{}|{}",
+            self.timestamp,
+            self.source.as_str(),
+            self.sequence,
+            self.id
+        ))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        let decoded = String::from_utf8(bytes).ok()?;
+        let mut parts = decoded.splitn(4, '|');
+        Some(Self {
+            timestamp: parts.next()?.to_string(),
+            source: TimelineSource::from_str(parts.next()?)?,
+            sequence: parts.next()?.parse().ok()?,
+            id: parts.next()?.to_string(),
+        })
+    }
+}
+
+/// One page of a merged timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelinePage {
+    pub items: Vec<TimelineItem>,
+    /// Pass back as `cursor` for the next page; on the last page it still marks
+    /// the newest item, so polling with it only returns what's new since.
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+pub fn page(
+    items: &[TimelineItem],
+    after: Option<&TimelineCursor>,
+    limit: usize,
+) -> TimelinePage {
+    let start = after.map_or(0, |after| {
+        items.partition_point(|item| item.position() <= *after)
+    });
+    let end = (start + limit.clamp(1, MAX_TIMELINE_PAGE)).min(items.len());
+    let page_items = items[start..end].to_vec();
+
+    let next_cursor = page_items
+        .last()
+        .map(|item| item.position().encode())
+        .or_else(|| after.map(TimelineCursor::encode));
+
+    TimelinePage {
+        items: page_items,
+        next_cursor,
+        has_more: end < items.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(id: &str, timestamp: &str) -> RedditComment {
+        RedditComment {
+            id: id.to_string(),
+            author: "fan".to_string(),
+            content: "let's go".to_string(),
+            timestamp: timestamp.to_string(),
+            score: 1,
+            permalink: String::new(),
+            subreddit: "phillies".to_string(),
+            team: None,
+            depth: 0,
+            replies: Vec::new(),
+        }
+    }
+
+    fn timeline() -> Vec<TimelineItem> {
+        let plays = serde_json::json!([
+            {"PlayID": 1, "InningNumber": 1, "InningHalf": "T", "Updated": "2025-07-04T19:05:00",
+             "Description": "Strikeout"},
+            {"PlayID": 2, "InningNumber": 1, "InningHalf": "B", "Description": "Single"},
+            {"PlayID": 3, "InningNumber": 2, "InningHalf": "T", "Updated": "2025-07-04T19:30:00",
+             "Description": "Home run"}
+        ]);
+        let mut items = play_items(plays.as_array().unwrap());
+        items.extend(comment_items(&[
+            // Plays are Eastern daylight time: 23:05 and 23:30 UTC
+            comment("early", "2025-07-04T23:00:00+00:00"),
+            comment("late", "2025-07-04T23:20:00+00:00"),
+        ]));
+        merge(items)
+    }
+
+    #[test]
+    fn test_social_items_take_the_latest_play_clock() {
+        let items = timeline();
+        let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, ["early", "1", "2", "late", "3"]);
+        assert!(items[0].game_clock.is_none());
+        assert_eq!(items[3].game_clock.as_ref().unwrap().label, "Bot 1");
+    }
+
+    #[test]
+    fn test_cursor_pages_forward() {
+        let items = timeline();
+        let first = page(&items, None, 2);
+        assert!(first.has_more);
+
+        let cursor =
+            TimelineCursor::decode(first.next_cursor.as_deref().unwrap()).unwrap();
+        let second = page(&items, Some(&cursor), 10);
+        assert_eq!(second.items.len(), 3);
+        assert!(!second.has_more);
+
+        let cursor =
+            TimelineCursor::decode(second.next_cursor.as_deref().unwrap()).unwrap();
+        let polled = page(&items, Some(&cursor), 10);
+        assert!(polled.items.is_empty());
+        assert_eq!(polled.next_cursor, second.next_cursor);
+    }
+
+    #[test]
+    fn test_plays_use_created_time_over_out_of_order_updates() {
+        let plays = serde_json::json!([
+            {"PlayID": 1, "Created": "2025-07-04T19:05:00", "Updated": "2025-07-04T19:50:00"},
+            {"PlayID": 2, "Created": "2025-07-04T19:10:00", "Updated": "2025-07-04T19:12:00"},
+            // Only an update time, and it runs behind the play before
+            {"PlayID": 3, "Updated": "2025-07-04T19:08:00"},
+            {"PlayID": 4, "Created": "2025-07-04T19:20:00"}
+        ]);
+        let mut items = play_items(plays.as_array().unwrap());
+        items.extend(comment_items(&[comment(
+            "reply",
+            "2025-07-04T23:15:00+00:00",
+        )]));
+        let items = merge(items);
+
+        let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, ["1", "2", "3", "reply", "4"]);
+        assert_eq!(items[0].timestamp, "2025-07-04T23:05:00Z");
+        assert_eq!(items[2].timestamp, "2025-07-04T23:10:00Z");
+    }
+
+    #[test]
+    fn test_untimed_plays_keep_feed_order_across_pages() {
+        let plays: Vec<serde_json::Value> = (1..=12)
+            .map(|id| serde_json::json!({"PlayID": id, "Description": "Pitch"}))
+            .chain([serde_json::json!({"PlayID": 13, "Created": "2025-07-04T19:30:00"})])
+            .collect();
+        let items = merge(play_items(&plays));
+
+        let mut ids = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = page(&items, cursor.as_ref(), 5);
+            ids.extend(page.items.iter().map(|item| item.id.clone()));
+            if !page.has_more {
+                break;
+            }
+            cursor = TimelineCursor::decode(page.next_cursor.as_deref().unwrap());
+        }
+        let expected: Vec<String> = (1..=13).map(|id| id.to_string()).collect();
+        assert_eq!(ids, expected);
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::config::TwitterApiConfig;
+
+/// Backoff applied to a 429 that doesn't say how long to wait
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Classic token bucket: `capacity` requests at once, refilled continuously
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_second: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, per_minute: u32, now: Instant) -> Self {
+        let capacity = capacity.max(1) as f64;
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_second: per_minute.max(1) as f64 / 60.0,
+            last_refill: now,
+        }
+    }
+
+    /// Take one token, or return how long until one is available
+    pub fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.refill_per_second,
+            ))
+        }
+    }
+}
+
+/// Request budget for the Twitter API, shared by every handler that searches
+#[derive(Debug)]
+pub struct TwitterBudget {
+    bucket: TokenBucket,
+    blocked_until: Option<Instant>,
+    max_retry_after: Duration,
+}
+
+impl TwitterBudget {
+    pub fn new(config: &TwitterApiConfig, now: Instant) -> Self {
+        Self {
+            bucket: TokenBucket::new(config.burst, config.requests_per_minute, now),
+            blocked_until: None,
+            max_retry_after: Duration::from_secs(config.max_retry_after_seconds),
+        }
+    }
+
+    /// Spend one request, or return how long to wait before the next one
+    pub fn acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.blocked_until {
+            if now < until {
+                return Err(until - now);
+            }
+            self.blocked_until = None;
+        }
+        self.bucket.try_take(now)
+    }
+
+    /// Stop sending requests after the API answered 429
+    pub fn back_off(&mut self, now: Instant, retry_after: Option<Duration>) -> Duration {
+        let wait = retry_after
+            .unwrap_or(DEFAULT_RETRY_AFTER)
+            .min(self.max_retry_after);
+        self.blocked_until = Some(now + wait);
+        wait
+    }
+}
+
+/// Parse a `Retry-After` header: delay in seconds or an HTTP date
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    (at.with_timezone(&Utc) - now).to_std().ok()
+}
+
+/// HTTP client and request budget reused across Twitter API calls
+#[derive(Debug, Clone)]
+pub struct TwitterClient {
+    pub http: reqwest::Client,
+    pub budget: Arc<Mutex<TwitterBudget>>,
+}
+
+impl TwitterClient {
+    pub fn new(config: &TwitterApiConfig, timeout: Duration) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|e| {
+                tracing::error!(
+                    "Failed to build Twitter HTTP client, using defaults: {}",
+                    e
+                );
+                reqwest::Client::new()
+            });
+
+        Self {
+            http,
+            budget: Arc::new(Mutex::new(TwitterBudget::new(config, Instant::now()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_spends_burst_then_refills() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2, 60, start);
+        assert!(bucket.try_take(start).is_ok());
+        assert!(bucket.try_take(start).is_ok());
+
+        let wait = bucket.try_take(start).unwrap_err();
+        assert!((wait.as_secs_f64() - 1.0).abs() < 1e-6);
+        assert!(bucket.try_take(start + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_budget_honors_capped_retry_after() {
+        let config = TwitterApiConfig {
+            requests_per_minute: 60,
+            burst: 10,
+            max_retry_after_seconds: 120,
+        };
+        let start = Instant::now();
+        let mut budget = TwitterBudget::new(&config, start);
+
+        let now = "2025-07-04T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let retry_after = parse_retry_after("Fri, 04 Jul 2025 12:30:00 GMT", now);
+        assert_eq!(retry_after, Some(Duration::from_secs(1800)));
+
+        let wait = budget.back_off(start, retry_after);
+        assert_eq!(wait, Duration::from_secs(120));
+        assert!(budget.acquire(start + Duration::from_secs(60)).is_err());
+        assert!(budget.acquire(start + Duration::from_secs(120)).is_ok());
+    }
+}
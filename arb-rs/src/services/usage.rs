@@ -0,0 +1,564 @@
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tracing::Instrument;
+
+use crate::{
+    cache::{Cache, CacheKey},
+    config::UsageConfig,
+    error::Result,
+    metrics::Metrics,
+    telemetry::inject_context,
+};
+
+/// A metered or rate limited API arb calls out to
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Upstream {
+    SportsData,
+    RollingInsights,
+    TwitterApi,
+    Reddit,
+}
+
+impl Upstream {
+    pub const ALL: [Upstream; 4] = [
+        Upstream::SportsData,
+        Upstream::RollingInsights,
+        Upstream::TwitterApi,
+        Upstream::Reddit,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Upstream::SportsData => "sports_data",
+            Upstream::RollingInsights => "rolling_insights",
+            Upstream::TwitterApi => "twitter_api",
+            Upstream::Reddit => "reddit",
+        }
+    }
+
+    /// The upstream a cache key stands in for, if any
+    pub fn for_cache_key(key: &str) -> Option<Self> {
+        // Stale copies are fallbacks, read after the fresh lookup missed
+        if key.starts_with("twitter_search:stale:") {
+            return None;
+        }
+        match key.split(':').next()? {
+            // SportsData responses are cached under the provider's old name
+            "sportradar" => Some(Upstream::SportsData),
+            "rolling_insights" => Some(Upstream::RollingInsights),
+            "twitter_search" => Some(Upstream::TwitterApi),
+            "reddit" => Some(Upstream::Reddit),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Upstream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Counters for one upstream, endpoint and league
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageCounts {
+    pub calls: u64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// Response body bytes read, so chunked responses count too
+    pub bytes: u64,
+    /// Failed calls by HTTP status, or "network" when there was none
+    pub errors: BTreeMap<String, u64>,
+}
+
+impl UsageCounts {
+    fn add(&mut self, other: &UsageCounts) {
+        self.calls += other.calls;
+        self.cache_hits += other.cache_hits;
+        self.cache_misses += other.cache_misses;
+        self.bytes += other.bytes;
+        for (code, count) in &other.errors {
+            *self.errors.entry(code.clone()).or_default() += count;
+        }
+    }
+
+    /// Non-zero counters as `(metric, value)`, e.g. `("error:429", 2)`
+    fn metrics(&self) -> Vec<(String, u64)> {
+        let mut metrics: Vec<(String, u64)> = [
+            ("calls", self.calls),
+            ("cache_hits", self.cache_hits),
+            ("cache_misses", self.cache_misses),
+            ("bytes", self.bytes),
+        ]
+        .into_iter()
+        .filter(|(_, value)| *value > 0)
+        .map(|(metric, value)| (metric.to_string(), value))
+        .collect();
+        metrics.extend(
+            self.errors
+                .iter()
+                .map(|(code, count)| (format!("error:{}", code), *count)),
+        );
+        metrics
+    }
+
+    fn add_metric(&mut self, metric: &str, value: u64) {
+        match metric {
+            "calls" => self.calls += value,
+            "cache_hits" => self.cache_hits += value,
+            "cache_misses" => self.cache_misses += value,
+            "bytes" => self.bytes += value,
+            other => {
+                if let Some(code) = other.strip_prefix("error:") {
+                    *self.errors.entry(code.to_string()).or_default() += value;
+                }
+            }
+        }
+    }
+}
+
+/// What a counter is for: the upstream, the arb route that caused the call
+/// and the league it was for
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct UsageKey {
+    pub upstream: Upstream,
+    pub endpoint: String,
+    pub league: String,
+}
+
+/// One row of a daily rollup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageEntry {
+    #[serde(flatten)]
+    pub key: UsageKey,
+    #[serde(flatten)]
+    pub counts: UsageCounts,
+}
+
+/// Redis hash fields for pending counters: `upstream|endpoint|league|metric`
+pub fn rollup_fields(pending: &[(UsageKey, UsageCounts)]) -> Vec<(String, u64)> {
+    pending
+        .iter()
+        .flat_map(|(key, counts)| {
+            counts.metrics().into_iter().map(move |(metric, value)| {
+                (
+                    format!(
+                        "{}|{}|{}|{}",
+                        key.upstream, key.endpoint, key.league, metric
+                    ),
+                    value,
+                )
+            })
+        })
+        .collect()
+}
+
+/// Rows of a daily rollup read back from its Redis hash
+pub fn parse_rollup(fields: HashMap<String, u64>) -> Vec<UsageEntry> {
+    let mut rows: BTreeMap<UsageKey, UsageCounts> = BTreeMap::new();
+    for (field, value) in fields {
+        let mut parts = field.splitn(4, '|');
+        let (Some(upstream), Some(endpoint), Some(league), Some(metric)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let Some(upstream) = Upstream::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str() == upstream)
+        else {
+            continue;
+        };
+        let key = UsageKey {
+            upstream,
+            endpoint: endpoint.to_string(),
+            league: league.to_string(),
+        };
+        rows.entry(key).or_default().add_metric(metric, value);
+    }
+    rows.into_iter()
+        .map(|(key, counts)| UsageEntry { key, counts })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct UsageLedger {
+    /// Counters not yet written to Redis
+    pending: HashMap<UsageKey, UsageCounts>,
+    last_flush: Option<Instant>,
+}
+
+impl UsageLedger {
+    fn record(&mut self, key: UsageKey, counts: &UsageCounts) {
+        self.pending.entry(key).or_default().add(counts);
+    }
+
+    fn flush_due(&self, every: Duration, now: Instant) -> bool {
+        self.last_flush.is_none_or(|last| now >= last + every)
+    }
+
+    fn take_pending(&mut self, now: Instant) -> Vec<(UsageKey, UsageCounts)> {
+        self.last_flush = Some(now);
+        self.pending.drain().collect()
+    }
+
+    fn restore(&mut self, pending: Vec<(UsageKey, UsageCounts)>) {
+        for (key, counts) in pending {
+            self.record(key, &counts);
+        }
+    }
+}
+
+/// Upstream call and cache counters, written to Redis every so often, and
+/// the monthly caps every upstream call is checked against
+#[derive(Clone)]
+pub struct UpstreamUsage {
+    ledger: Arc<Mutex<UsageLedger>>,
+    /// Where each month's call totals are kept, shared by every instance
+    store: Arc<async_std::sync::Mutex<Cache>>,
+    /// Where upstream latency and status are recorded
+    metrics: Metrics,
+    config: Arc<UsageConfig>,
+}
+
+impl fmt::Debug for UpstreamUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpstreamUsage")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl UpstreamUsage {
+    pub fn new(
+        store: Arc<async_std::sync::Mutex<Cache>>,
+        metrics: Metrics,
+        config: &UsageConfig,
+    ) -> Self {
+        Self {
+            ledger: Arc::default(),
+            store,
+            metrics,
+            config: Arc::new(config.clone()),
+        }
+    }
+
+    pub fn record(&self, key: UsageKey, counts: &UsageCounts) {
+        if let Ok(mut ledger) = self.ledger.lock() {
+            ledger.record(key, counts);
+        }
+    }
+
+    /// Calls allowed to `upstream` per month, if it is capped
+    pub fn monthly_cap(&self, upstream: Upstream) -> Option<u64> {
+        self.config
+            .monthly_caps
+            .get(upstream.as_str())
+            .copied()
+            .filter(|cap| *cap > 0)
+    }
+
+    pub fn flush_due(&self, every: Duration, now: Instant) -> bool {
+        self.ledger
+            .lock()
+            .map(|ledger| ledger.flush_due(every, now))
+            .unwrap_or(false)
+    }
+
+    /// Counters to write to the daily rollup
+    pub fn take_pending(&self, now: Instant) -> Vec<(UsageKey, UsageCounts)> {
+        self.ledger
+            .lock()
+            .map(|mut ledger| ledger.take_pending(now))
+            .unwrap_or_default()
+    }
+
+    /// Put back counters a failed write didn't store
+    pub fn restore(&self, pending: Vec<(UsageKey, UsageCounts)>) {
+        if let Ok(mut ledger) = self.ledger.lock() {
+            ledger.restore(pending);
+        }
+    }
+
+    /// Count a call to `upstream` in this month's totals before it is made,
+    /// refusing it once the month's cap is spent. The count is kept in the
+    /// store so instances can't overshoot the cap together.
+    async fn reserve_call(&self, upstream: Upstream) -> Result<()> {
+        let cap = self.monthly_cap(upstream);
+        let month = chrono::Utc::now().format("%Y-%m").to_string();
+        // Outlive the month even with a short retention
+        let expiry = (self.config.retention_days * 86400).max(62 * 86400);
+        let reserved = self
+            .store
+            .lock()
+            .await
+            .increment_field_within(
+                CacheKey::usage_month(&month),
+                upstream.as_str(),
+                cap.unwrap_or(0),
+                expiry,
+            )
+            .await;
+
+        match (reserved, cap) {
+            (Ok(Some(_)), _) => Ok(()),
+            (Ok(None), Some(cap)) => {
+                tracing::warn!(
+                    "{} monthly cap of {} calls reached; not calling upstream",
+                    upstream,
+                    cap
+                );
+                Err(StatusCode::SERVICE_UNAVAILABLE.into())
+            }
+            (Err(e), Some(_)) => {
+                tracing::error!(
+                    "Failed to count a {} call against its monthly cap: {}",
+                    upstream,
+                    e
+                );
+                Err(StatusCode::SERVICE_UNAVAILABLE.into())
+            }
+            (Ok(None), None) | (Err(_), None) => Ok(()),
+        }
+    }
+}
+
+/// Where upstream calls and cache lookups made while handling one request
+/// are attributed
+#[derive(Clone)]
+pub struct UsageScope {
+    pub usage: UpstreamUsage,
+    /// The arb route being served, e.g. `/api/v1/scores`
+    pub endpoint: String,
+    /// The request's league, "other" for one arb doesn't know, or "all"
+    pub league: String,
+}
+
+/// Labels for calls made outside a request, e.g. by health probes
+const BACKGROUND_ENDPOINT: &str = "background";
+
+fn usage_key(upstream: Upstream) -> UsageKey {
+    USAGE_SCOPE
+        .try_with(|scope| UsageKey {
+            upstream,
+            endpoint: scope.endpoint.clone(),
+            league: scope.league.clone(),
+        })
+        .unwrap_or_else(|_| UsageKey {
+            upstream,
+            endpoint: BACKGROUND_ENDPOINT.to_string(),
+            league: "all".to_string(),
+        })
+}
+
+tokio::task_local! {
+    /// Set by the usage middleware around each request, so calls and cache
+    /// lookups deep in a handler are labeled with its route and league
+    pub static USAGE_SCOPE: UsageScope;
+}
+
+/// Count a cache lookup that stands in for an upstream call
+pub fn record_cache_lookup(cache_key: &str, hit: bool) {
+    let Some(upstream) = Upstream::for_cache_key(cache_key) else {
+        return;
+    };
+    let _ = USAGE_SCOPE.try_with(|scope| {
+        let counts = UsageCounts {
+            cache_hits: hit as u64,
+            cache_misses: !hit as u64,
+            ..Default::default()
+        };
+        let key = UsageKey {
+            upstream,
+            endpoint: scope.endpoint.clone(),
+            league: scope.league.clone(),
+        };
+        scope.usage.record(key, &counts);
+    });
+}
+
+/// An upstream response whose body is counted as it's read
+pub struct MeteredResponse {
+    response: reqwest::Response,
+    usage: UpstreamUsage,
+    key: UsageKey,
+}
+
+impl MeteredResponse {
+    pub fn status(&self) -> reqwest::StatusCode {
+        self.response.status()
+    }
+
+    pub fn headers(&self) -> &reqwest::header::HeaderMap {
+        self.response.headers()
+    }
+
+    /// The body as text, counting its size
+    pub async fn text(self) -> reqwest::Result<String> {
+        let text = self.response.text().await?;
+        let counts = UsageCounts {
+            bytes: text.len() as u64,
+            ..Default::default()
+        };
+        self.usage.record(self.key, &counts);
+        Ok(text)
+    }
+}
+
+/// Send an upstream request unless the upstream's monthly cap is spent,
+/// counting the call, latency and any error status, in a span whose W3C trace
+/// context goes along with the request; the body is counted once it's read
+pub async fn metered(
+    usage: &UpstreamUsage,
+    upstream: Upstream,
+    request: reqwest::RequestBuilder,
+) -> Result<MeteredResponse> {
+    usage.reserve_call(upstream).await?;
+
+    let (client, request) = request.build_split();
+    let mut request = request.map_err(|e| {
+        tracing::error!("Failed to build HTTP request to {}: {}", upstream, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let key = usage_key(upstream);
+    let span = tracing::info_span!(
+        "upstream",
+        otel.name = %format!("{} {}", request.method(), upstream),
+        otel.kind = "client",
+        provider = upstream.as_str(),
+        league = key.league.as_str(),
+        http.request.method = %request.method(),
+        server.address = request.url().host_str().unwrap_or_default(),
+        url.path = request.url().path(),
+        http.response.status_code = tracing::field::Empty,
+        otel.status_code = tracing::field::Empty,
+    );
+    inject_context(&span, request.headers_mut());
+
+    let started = Instant::now();
+    let result = client.execute(request).instrument(span.clone()).await;
+    match &result {
+        Ok(response) => {
+            span.record("http.response.status_code", response.status().as_u16());
+            if !response.status().is_success() {
+                span.record("otel.status_code", "ERROR");
+            }
+        }
+        Err(_) => {
+            span.record("otel.status_code", "ERROR");
+        }
+    }
+
+    let status = match &result {
+        Ok(response) => response.status().as_u16().to_string(),
+        Err(_) => "error".to_string(),
+    };
+    usage
+        .metrics
+        .observe_upstream(upstream.as_str(), &status, started.elapsed());
+
+    let mut counts = UsageCounts {
+        calls: 1,
+        ..Default::default()
+    };
+    let error = match &result {
+        Ok(response) => (!response.status().is_success())
+            .then(|| response.status().as_u16().to_string()),
+        Err(e) => Some(
+            e.status()
+                .map(|status| status.as_u16().to_string())
+                .unwrap_or_else(|| "network".to_string()),
+        ),
+    };
+    if let Some(code) = error {
+        counts.errors.insert(code, 1);
+    }
+    usage.record(key.clone(), &counts);
+
+    match result {
+        Ok(response) => Ok(MeteredResponse {
+            response,
+            usage: usage.clone(),
+            key,
+        }),
+        Err(e) => {
+            tracing::error!("Failed to make HTTP request to {}: {}", upstream, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(upstream: Upstream, league: &str) -> UsageKey {
+        UsageKey {
+            upstream,
+            endpoint: "/api/v1/scores".to_string(),
+            league: league.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rollup_round_trip_and_restore() {
+        let mut ledger = UsageLedger::default();
+        let mut call = UsageCounts {
+            calls: 1,
+            bytes: 512,
+            ..Default::default()
+        };
+        ledger.record(key(Upstream::SportsData, "mlb"), &call);
+        call.errors.insert("429".to_string(), 1);
+        ledger.record(key(Upstream::SportsData, "mlb"), &call);
+        ledger.record(
+            key(Upstream::SportsData, "nfl"),
+            &UsageCounts {
+                cache_hits: 3,
+                ..Default::default()
+            },
+        );
+
+        let now = Instant::now();
+        assert!(ledger.flush_due(Duration::from_secs(30), now));
+        let pending = ledger.take_pending(now);
+        assert!(!ledger.flush_due(Duration::from_secs(30), now));
+        assert!(ledger.pending.is_empty());
+
+        let fields: HashMap<String, u64> = rollup_fields(&pending).into_iter().collect();
+        assert_eq!(fields["sports_data|/api/v1/scores|mlb|error:429"], 1);
+        let rows = parse_rollup(fields);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].key.league, "mlb");
+        assert_eq!(rows[0].counts.calls, 2);
+        assert_eq!(rows[0].counts.bytes, 1024);
+        assert_eq!(rows[1].counts.cache_hits, 3);
+
+        ledger.restore(pending);
+        ledger.record(key(Upstream::SportsData, "mlb"), &call);
+        assert_eq!(ledger.pending[&key(Upstream::SportsData, "mlb")].calls, 3);
+    }
+
+    #[test]
+    fn test_cache_keys_map_to_upstreams() {
+        assert_eq!(
+            Upstream::for_cache_key("sportradar:scores:mlb:2025-07-04"),
+            Some(Upstream::SportsData)
+        );
+        assert_eq!(
+            Upstream::for_cache_key("twitter_search:#Reds"),
+            Some(Upstream::TwitterApi)
+        );
+        assert_eq!(Upstream::for_cache_key("twitter_search:stale:#Reds"), None);
+        assert_eq!(Upstream::for_cache_key("auth_session:abc"), None);
+    }
+}
@@ -0,0 +1,693 @@
+use async_std::sync::{Arc, Mutex, MutexGuardArc};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    config::VideoSourceConfig,
+    database::Database,
+    error::Result,
+    services::{pagination::Paging, standings::ScheduledGame},
+};
+
+/// What a video shows, matching the `video_type` check in the `videos` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoType {
+    #[default]
+    Highlight,
+    Recap,
+    Interview,
+    Analysis,
+    LiveStream,
+}
+
+const LIVE_STREAM_PHRASES: [&str; 3] = [" live ", " livestream ", " live stream "];
+const RECAP_PHRASES: [&str; 3] = [" recap ", " condensed game ", " game in "];
+const INTERVIEW_PHRASES: [&str; 6] = [
+    " interview ",
+    " postgame ",
+    " pregame ",
+    " press conference ",
+    " presser ",
+    " talks ",
+];
+const ANALYSIS_PHRASES: [&str; 4] =
+    [" analysis ", " breakdown ", " film room ", " explains "];
+
+/// Classify a video from its title, falling back to the source's usual type
+pub fn classify(title: &str, fallback: VideoType) -> VideoType {
+    let text: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let text = format!(
+        " {} ",
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+    let mentions = |phrases: &[&str]| phrases.iter().any(|phrase| text.contains(phrase));
+
+    if mentions(&LIVE_STREAM_PHRASES) {
+        VideoType::LiveStream
+    } else if mentions(&RECAP_PHRASES) {
+        VideoType::Recap
+    } else if mentions(&INTERVIEW_PHRASES) {
+        VideoType::Interview
+    } else if mentions(&ANALYSIS_PHRASES) {
+        VideoType::Analysis
+    } else {
+        fallback
+    }
+}
+
+/// A catalogued video, shaped like a row of the `videos` table (etc/schema/v1.sql)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Video {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub thumbnail_url: Option<String>,
+    pub video_url: String,
+    pub duration_seconds: Option<i64>,
+    pub game_id: Option<String>,
+    /// Team abbreviations featured
+    pub team_ids: Vec<String>,
+    pub league_id: String,
+    pub video_type: VideoType,
+    pub view_count: Option<i64>,
+    pub source: String,
+    /// RFC 3339, UTC
+    pub published_at: Option<String>,
+}
+
+impl Video {
+    /// Whether the video features a team, by abbreviation
+    pub fn features_team(&self, team: &str) -> bool {
+        self.team_ids.iter().any(|t| t.eq_ignore_ascii_case(team))
+    }
+
+    fn published(&self) -> Option<DateTime<Utc>> {
+        let published =
+            DateTime::parse_from_rfc3339(self.published_at.as_deref()?).ok()?;
+        Some(published.with_timezone(&Utc))
+    }
+}
+
+fn source_name(source: &VideoSourceConfig, fallback: Option<&str>) -> String {
+    source
+        .name
+        .clone()
+        .or_else(|| fallback.map(String::from))
+        .unwrap_or_else(|| source.url.clone())
+}
+
+fn source_teams(source: &VideoSourceConfig) -> Vec<String> {
+    source.team.iter().map(|team| team.to_uppercase()).collect()
+}
+
+/// Videos from a parsed RSS or Atom feed, including YouTube channel feeds
+///
+/// YouTube puts the thumbnail, description and view count in `media:group`.
+pub fn feed_videos(
+    feed: &feed_rs::model::Feed,
+    source: &VideoSourceConfig,
+) -> Vec<Video> {
+    let name = source_name(
+        source,
+        feed.title.as_ref().map(|title| title.content.as_str()),
+    );
+
+    feed.entries
+        .iter()
+        .filter_map(|entry| {
+            let title = entry.title.as_ref()?.content.trim().to_string();
+            let media = entry.media.first();
+            let video_url =
+                entry
+                    .links
+                    .first()
+                    .map(|link| link.href.clone())
+                    .or_else(|| {
+                        media?
+                            .content
+                            .iter()
+                            .find_map(|content| Some(content.url.as_ref()?.to_string()))
+                    })?;
+            let duration = media.and_then(|media| {
+                media
+                    .duration
+                    .or_else(|| media.content.iter().find_map(|content| content.duration))
+            });
+            let description = media
+                .and_then(|media| media.description.as_ref())
+                .or(entry.summary.as_ref())
+                .map(|text| text.content.trim().to_string())
+                .filter(|text| !text.is_empty());
+
+            Some(Video {
+                id: if entry.id.is_empty() {
+                    video_url.clone()
+                } else {
+                    entry.id.clone()
+                },
+                video_type: classify(&title, source.video_type),
+                description,
+                thumbnail_url: media
+                    .and_then(|media| media.thumbnails.first())
+                    .map(|thumbnail| thumbnail.image.uri.clone()),
+                video_url,
+                duration_seconds: duration.map(|duration| duration.as_secs() as i64),
+                game_id: None,
+                team_ids: source_teams(source),
+                league_id: source.league.to_lowercase(),
+                view_count: media
+                    .and_then(|media| media.community.as_ref()?.stats_views)
+                    .map(|views| views as i64),
+                source: name.clone(),
+                published_at: entry
+                    .published
+                    .or(entry.updated)
+                    .map(|published| published.to_rfc3339()),
+                title,
+            })
+        })
+        .collect()
+}
+
+/// First string among `keys`, looked up as JSON pointers or plain fields
+fn json_str<'a>(item: &'a serde_json::Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|key| {
+        let value = if key.starts_with('/') {
+            item.pointer(key)
+        } else {
+            item.get(*key)
+        };
+        value?.as_str().filter(|value| !value.is_empty())
+    })
+}
+
+fn json_i64(item: &serde_json::Value, keys: &[&str]) -> Option<i64> {
+    keys.iter().find_map(|key| {
+        let value = item.get(*key)?;
+        value
+            .as_i64()
+            .or_else(|| value.as_f64().map(|value| value as i64))
+            .or_else(|| value.as_str()?.parse().ok())
+    })
+}
+
+/// Videos from a provider highlight endpoint
+///
+/// Providers disagree on names, so this accepts a bare array or one under
+/// `highlights`, `videos`, `items` or `data`, and the common spellings of each
+/// field. Items without a title or a playable URL are skipped.
+pub fn provider_videos(
+    body: &serde_json::Value,
+    source: &VideoSourceConfig,
+) -> Vec<Video> {
+    let items = ["highlights", "videos", "items", "data"]
+        .iter()
+        .find_map(|key| body.get(*key)?.as_array())
+        .or_else(|| body.as_array());
+    let Some(items) = items else {
+        return Vec::new();
+    };
+    let name = source_name(source, None);
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let title = json_str(item, &["title", "headline", "Title", "name"])?
+                .trim()
+                .to_string();
+            let video_url = json_str(
+                item,
+                &["video_url", "url", "VideoUrl", "/playbacks/0/url", "link"],
+            )?
+            .to_string();
+            let published_at = json_str(
+                item,
+                &["published_at", "date", "Updated", "Created", "published"],
+            )
+            .and_then(|published| {
+                DateTime::parse_from_rfc3339(published)
+                    .map(|published| published.with_timezone(&Utc).to_rfc3339())
+                    .ok()
+                    .or_else(|| crate::services::news::provider_timestamp(published))
+            });
+            let video_type = json_str(item, &["video_type", "type"])
+                .and_then(|kind| {
+                    serde_json::from_value(serde_json::Value::String(kind.to_lowercase()))
+                        .ok()
+                })
+                .unwrap_or_else(|| classify(&title, source.video_type));
+            let mut team_ids = source_teams(source);
+            if let Some(teams) = item.get("teams").and_then(|teams| teams.as_array()) {
+                for team in teams.iter().filter_map(|team| team.as_str()) {
+                    let team = team.to_uppercase();
+                    if !team_ids.contains(&team) {
+                        team_ids.push(team);
+                    }
+                }
+            }
+
+            Some(Video {
+                id: json_str(item, &["id", "guid", "VideoID"])
+                    .map(String::from)
+                    .or_else(|| {
+                        json_i64(item, &["id", "VideoID"]).map(|id| id.to_string())
+                    })
+                    .unwrap_or_else(|| video_url.clone()),
+                description: json_str(item, &["description", "blurb", "Caption"])
+                    .map(String::from),
+                thumbnail_url: json_str(
+                    item,
+                    &["thumbnail_url", "thumbnail", "/image/url", "ThumbnailUrl"],
+                )
+                .map(String::from),
+                duration_seconds: json_i64(item, &["duration_seconds", "duration"]),
+                game_id: json_str(item, &["game_id", "GameID"])
+                    .map(String::from)
+                    .or_else(|| {
+                        json_i64(item, &["game_id", "GameID"]).map(|id| id.to_string())
+                    }),
+                league_id: source.league.to_lowercase(),
+                view_count: json_i64(item, &["view_count", "views"]),
+                source: name.clone(),
+                video_url,
+                video_type,
+                team_ids,
+                published_at,
+                title,
+            })
+        })
+        .collect()
+}
+
+/// Tag videos with every team named in their title, by whole words
+pub fn tag_teams(videos: &mut [Video], names: &[(String, Vec<String>)]) {
+    for video in videos.iter_mut() {
+        let title: String = video
+            .title
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+            .collect();
+        let title = format!(
+            " {} ",
+            title.split_whitespace().collect::<Vec<_>>().join(" ")
+        );
+
+        for (team, team_names) in names {
+            let named = std::iter::once(team)
+                .chain(team_names)
+                .any(|name| title.contains(&format!(" {} ", name.to_lowercase())));
+            if named && !video.features_team(team) {
+                video.team_ids.push(team.to_uppercase());
+            }
+        }
+    }
+}
+
+/// How long before a game's scheduled start its videos can be posted
+const GAME_LEAD_HOURS: i64 = 2;
+/// How long after the scheduled start a game's videos can still be posted
+const GAME_TAIL_HOURS: i64 = 18;
+
+/// Link videos without a game to the game their teams played around the time
+/// they were posted, and tag the opponent
+///
+/// Games featuring both of a video's teams win over games featuring one; ties
+/// go to the game that started closest before the video was posted.
+pub fn link_games(videos: &mut [Video], games: &[(ScheduledGame, DateTime<Utc>)]) {
+    for video in videos.iter_mut().filter(|video| video.game_id.is_none()) {
+        let Some(published) = video.published() else {
+            continue;
+        };
+        let best = games
+            .iter()
+            .filter(|(_, start)| {
+                published >= *start - Duration::hours(GAME_LEAD_HOURS)
+                    && published <= *start + Duration::hours(GAME_TAIL_HOURS)
+            })
+            .filter_map(|(game, start)| {
+                let featured = [&game.home, &game.away]
+                    .into_iter()
+                    .filter(|team| video.features_team(team))
+                    .count();
+                (featured > 0)
+                    .then(|| (featured, (published - *start).num_seconds().abs(), game))
+            })
+            .min_by_key(|(featured, distance, _)| {
+                (std::cmp::Reverse(*featured), *distance)
+            });
+
+        if let Some((_, _, game)) = best {
+            video.game_id = Some(game.game_id.clone());
+            for team in [&game.home, &game.away] {
+                if !video.features_team(team) {
+                    video.team_ids.push(team.to_uppercase());
+                }
+            }
+        }
+    }
+}
+
+/// Canonical form of a video link, so the same clip from two sources is one row
+///
+/// Only the host is case-insensitive; paths and YouTube ids are not.
+fn url_key(url: &str) -> String {
+    let url = url.trim();
+    let url = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let (host, rest) = url.split_at(url.find(['/', '?']).unwrap_or(url.len()));
+    let host = host.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    // YouTube's video id is a query parameter, so keep `v=` and drop the rest
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let video_id = query
+        .split('&')
+        .find(|param| param.starts_with("v="))
+        .map(|param| format!("?{}", param))
+        .unwrap_or_default();
+    format!("{}{}{}", host, path.trim_end_matches('/'), video_id)
+}
+
+/// Upsert `fresh` videos into `stored` by link, newest first, keeping at most
+/// `max_videos`
+///
+/// A fresh copy replaces the stored one but keeps its game link and team tags,
+/// so videos that have dropped out of a feed stay linked. The oldest (and
+/// undated) videos are dropped past the cap. Returns the merged catalog and how
+/// many videos were new.
+pub fn upsert_videos(
+    stored: Vec<Video>,
+    fresh: Vec<Video>,
+    max_videos: usize,
+) -> (Vec<Video>, usize) {
+    let mut videos: HashMap<String, Video> = stored
+        .into_iter()
+        .map(|video| (url_key(&video.video_url), video))
+        .collect();
+
+    let mut inserted = 0;
+    for mut video in fresh {
+        match videos.remove(&url_key(&video.video_url)) {
+            Some(previous) => {
+                video.game_id = video.game_id.or(previous.game_id);
+                for team in previous.team_ids {
+                    if !video.features_team(&team) {
+                        video.team_ids.push(team);
+                    }
+                }
+            }
+            None => inserted += 1,
+        }
+        videos.insert(url_key(&video.video_url), video);
+    }
+
+    let mut videos: Vec<Video> = videos.into_values().collect();
+    // RFC 3339 UTC strings sort chronologically; undated videos go last
+    videos.sort_by(|a, b| {
+        b.published_at
+            .cmp(&a.published_at)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    videos.truncate(max_videos);
+    (videos, inserted)
+}
+
+/// A league's video catalog, persisted between refreshes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoCatalog {
+    pub fetched_at: String,
+    pub videos: Vec<Video>,
+}
+
+/// A lock per league, so requests that find its catalog stale together wait
+/// for a single refresh instead of each reading every source
+#[derive(Debug, Clone, Default)]
+pub struct VideoRefreshes {
+    locks: Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>,
+}
+
+impl VideoRefreshes {
+    /// Held while `league`'s catalog is refreshed
+    pub async fn lock(&self, league: &str) -> MutexGuardArc<()> {
+        let lock = self
+            .locks
+            .lock()
+            .await
+            .entry(league.to_string())
+            .or_default()
+            .clone();
+        lock.lock_arc().await
+    }
+}
+
+const UPSERT_VIDEO: &str = "
+    INSERT INTO videos (
+        url_key, external_id, title, description, thumbnail_url, video_url,
+        duration_seconds, provider_game_id, team_abbreviations, team_ids, league_id,
+        video_type, view_count, source, published_at
+    ) VALUES (
+        $1, $2, $3, $4, $5, $6, $7, $8, $9,
+        ARRAY(
+            SELECT teams.id FROM teams JOIN leagues ON leagues.id = teams.league_id
+            WHERE leagues.abbreviation = upper($10) AND teams.abbreviation = ANY($9)
+        ),
+        (SELECT id FROM leagues WHERE abbreviation = upper($10)),
+        $11, $12, $13, $14
+    )
+    ON CONFLICT (url_key) DO UPDATE SET
+        external_id = EXCLUDED.external_id,
+        title = EXCLUDED.title,
+        description = EXCLUDED.description,
+        thumbnail_url = EXCLUDED.thumbnail_url,
+        video_url = EXCLUDED.video_url,
+        duration_seconds = EXCLUDED.duration_seconds,
+        provider_game_id = COALESCE(EXCLUDED.provider_game_id, videos.provider_game_id),
+        team_abbreviations = ARRAY(
+            SELECT DISTINCT unnest(videos.team_abbreviations || EXCLUDED.team_abbreviations)
+        ),
+        video_type = EXCLUDED.video_type,
+        view_count = EXCLUDED.view_count,
+        source = EXCLUDED.source,
+        published_at = EXCLUDED.published_at,
+        updated_at = NOW()
+    RETURNING (xmax = 0) AS inserted";
+
+const LEAGUE_VIDEOS: &str = "
+    SELECT external_id, title, description, thumbnail_url, video_url, duration_seconds,
+        provider_game_id, team_abbreviations, video_type, view_count, source, published_at
+    FROM videos
+    WHERE league_id = (SELECT id FROM leagues WHERE abbreviation = upper($1))
+    ORDER BY published_at DESC NULLS LAST, external_id
+    LIMIT $2";
+
+/// Upsert videos into `videos`, one row per link like [`upsert_videos`]
+///
+/// A fresh copy replaces the stored row but keeps its game link and team
+/// tags. Returns how many videos were new.
+pub async fn store_videos(
+    database: &Database,
+    league: &str,
+    videos: &[Video],
+) -> Result<usize> {
+    let count = |value: Option<i64>| {
+        value.map(|value| i32::try_from(value.max(0)).unwrap_or(i32::MAX))
+    };
+
+    let mut client = database.client().await?;
+    let transaction = client.transaction().await?;
+    let statement = transaction.prepare_cached(UPSERT_VIDEO).await?;
+    let mut inserted = 0;
+    for video in videos {
+        let video_type = serde_json::to_value(video.video_type)?;
+        let row = transaction
+            .query_one(
+                &statement,
+                &[
+                    &url_key(&video.video_url),
+                    &video.id,
+                    &video.title,
+                    &video.description,
+                    &video.thumbnail_url,
+                    &video.video_url,
+                    &count(video.duration_seconds),
+                    &video.game_id,
+                    &video.team_ids,
+                    &league,
+                    &video_type.as_str(),
+                    &count(video.view_count),
+                    &video.source,
+                    &video.published(),
+                ],
+            )
+            .await?;
+        if row.get::<_, bool>("inserted") {
+            inserted += 1;
+        }
+    }
+    transaction.commit().await?;
+    Ok(inserted)
+}
+
+/// A league's stored videos, newest first, at most `max_videos` of them
+pub async fn league_videos(
+    database: &Database,
+    league: &str,
+    max_videos: usize,
+) -> Result<Vec<Video>> {
+    let client = database.client().await?;
+    let statement = client.prepare_cached(LEAGUE_VIDEOS).await?;
+    let rows = client
+        .query(&statement, &[&league, &(max_videos as i64)])
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| Video {
+            id: row.get("external_id"),
+            title: row.get("title"),
+            description: row.get("description"),
+            thumbnail_url: row.get("thumbnail_url"),
+            video_url: row.get("video_url"),
+            duration_seconds: row
+                .get::<_, Option<i32>>("duration_seconds")
+                .map(i64::from),
+            game_id: row.get("provider_game_id"),
+            team_ids: row
+                .get::<_, Option<Vec<String>>>("team_abbreviations")
+                .unwrap_or_default(),
+            league_id: league.to_string(),
+            video_type: row
+                .get::<_, Option<String>>("video_type")
+                .and_then(|kind| {
+                    serde_json::from_value(serde_json::Value::String(kind)).ok()
+                })
+                .unwrap_or_default(),
+            view_count: row.get::<_, Option<i32>>("view_count").map(i64::from),
+            source: row.get::<_, Option<String>>("source").unwrap_or_default(),
+            published_at: row
+                .get::<_, Option<DateTime<Utc>>>("published_at")
+                .map(|published| published.to_rfc3339()),
+        })
+        .collect())
+}
+
+/// One page of a video list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoPage {
+    #[serde(flatten)]
+    pub paging: Paging,
+    pub videos: Vec<Video>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::VideoSourceKind, services::pagination::paginate};
+
+    fn source() -> VideoSourceConfig {
+        VideoSourceConfig {
+            league: "mlb".to_string(),
+            team: Some("phi".to_string()),
+            kind: VideoSourceKind::Youtube,
+            url: "UC1234".to_string(),
+            name: Some("Phillies".to_string()),
+            video_type: VideoType::Highlight,
+        }
+    }
+
+    #[test]
+    fn test_youtube_feed_is_linked_to_the_game() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:yt="http://www.youtube.com/xml/schemas/2015"
+      xmlns:media="http://search.yahoo.com/mrss/">
+  <title>Phillies</title>
+  <entry>
+    <id>yt:video:abc123</id>
+    <title>Mets vs. Phillies Game Highlights (7/4/25)</title>
+    <link rel="alternate" href="https://www.youtube.com/watch?v=abc123"/>
+    <published>2025-07-05T02:40:00+00:00</published>
+    <media:group>
+      <media:title>Mets vs. Phillies Game Highlights (7/4/25)</media:title>
+      <media:thumbnail url="https://i.ytimg.com/vi/abc123/hqdefault.jpg" width="480" height="360"/>
+      <media:description>Bryce Harper homers twice.</media:description>
+      <media:community><media:statistics views="1520"/></media:community>
+    </media:group>
+  </entry>
+  <entry>
+    <id>yt:video:def456</id>
+    <title>Rob Thomson postgame press conference</title>
+    <link rel="alternate" href="https://www.youtube.com/watch?v=def456"/>
+    <published>2025-07-05T03:10:00+00:00</published>
+  </entry>
+</feed>"#;
+        let feed = feed_rs::parser::parse(xml.as_bytes()).unwrap();
+        let mut videos = feed_videos(&feed, &source());
+        assert_eq!(videos.len(), 2);
+        assert_eq!(videos[0].view_count, Some(1520));
+        assert_eq!(videos[1].video_type, VideoType::Interview);
+
+        tag_teams(
+            &mut videos,
+            &[("NYM".to_string(), vec!["Mets".to_string()])],
+        );
+        assert_eq!(videos[0].team_ids, ["PHI", "NYM"]);
+
+        let game = |game_id: &str, away: &str, start: &str| {
+            let game = ScheduledGame {
+                game_id: game_id.to_string(),
+                day: String::new(),
+                date_time: None,
+                home: "PHI".to_string(),
+                away: away.to_string(),
+                home_score: None,
+                away_score: None,
+                is_final: true,
+            };
+            (game, start.parse::<DateTime<Utc>>().unwrap())
+        };
+        let games = [
+            game("1", "ATL", "2025-07-04T17:05:00Z"),
+            game("2", "NYM", "2025-07-04T23:05:00Z"),
+        ];
+        link_games(&mut videos, &games);
+        assert_eq!(videos[0].game_id.as_deref(), Some("2"));
+        assert_eq!(videos[1].game_id.as_deref(), Some("2"));
+        assert!(videos[1].features_team("NYM"));
+    }
+
+    #[test]
+    fn test_provider_videos_upsert_by_link() {
+        let body = serde_json::json!({"highlights": [{
+            "id": 77, "headline": "Schwarber crushes a 450-foot homer",
+            "playbacks": [{"url": "https://cdn.example.com/clip.mp4"}],
+            "date": "2025-07-05T01:30:00Z", "duration": "42", "game_id": "2"
+        }]});
+        let videos = provider_videos(&body, &source());
+        assert_eq!(videos[0].id, "77");
+        assert_eq!(videos[0].duration_seconds, Some(42));
+        assert_eq!(videos[0].video_type, VideoType::Highlight);
+
+        let mut again = videos.clone();
+        again[0].video_url = "http://cdn.example.com/clip.mp4/".to_string();
+        again[0].game_id = None;
+        let (catalog, inserted) = upsert_videos(videos, again, 10);
+        assert_eq!((catalog.len(), inserted), (1, 0));
+        assert_eq!(catalog[0].game_id.as_deref(), Some("2"));
+
+        let mut other = catalog.clone();
+        other[0].id = "78".to_string();
+        other[0].video_url = "https://CDN.example.com/Clip.mp4".to_string();
+        other[0].published_at = Some("2025-07-04T01:30:00+00:00".to_string());
+        let (capped, inserted) = upsert_videos(catalog.clone(), other, 1);
+        assert_eq!((capped.len(), inserted), (1, 1));
+        assert_eq!(capped[0].id, "77");
+
+        let (paging, page) = paginate(&catalog, 2, 1, 20);
+        assert!(page.is_empty() && !paging.has_more);
+    }
+}
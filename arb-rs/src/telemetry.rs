@@ -0,0 +1,109 @@
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Request};
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    trace::{Sampler, SdkTracerProvider},
+    Resource,
+};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::{config::TelemetryConfig, error::Result};
+
+/// A tracer provider batching spans to the configured OTLP collector, or
+/// `None` when export is disabled
+pub fn tracer_provider(config: &TelemetryConfig) -> Result<Option<SdkTracerProvider>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&config.otlp_endpoint)
+        .build()?;
+    // Sampled by trace id alone: a caller's `traceparent` joins its trace
+    // but can't force sampling, and every span of a trace decides alike
+    let sampler = Sampler::TraceIdRatioBased(config.sample_ratio);
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(sampler)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .build(),
+        )
+        .build();
+    Ok(Some(provider))
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(HeaderName::as_str).collect()
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// Root span for an incoming request, continuing the caller's trace when
+/// it sent a W3C `traceparent`
+pub fn request_span<B>(request: &Request<B>) -> Span {
+    let span = tracing::info_span!(
+        "request",
+        otel.name = %format!("{} {}", request.method(), request.uri().path()),
+        otel.kind = "server",
+        http.request.method = %request.method(),
+        url.path = %request.uri().path(),
+    );
+    let parent =
+        TraceContextPropagator::new().extract(&HeaderExtractor(request.headers()));
+    let _ = span.set_parent(parent);
+    span
+}
+
+/// Add `span`'s W3C trace context to an outgoing request
+pub fn inject_context(span: &Span, headers: &mut HeaderMap) {
+    TraceContextPropagator::new()
+        .inject_context(&span.context(), &mut HeaderInjector(headers));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TraceContextExt;
+
+    #[test]
+    fn test_trace_context_round_trips_through_headers() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let mut incoming = HeaderMap::new();
+        incoming.insert("traceparent", HeaderValue::from_static(traceparent));
+
+        let propagator = TraceContextPropagator::new();
+        let context = propagator.extract(&HeaderExtractor(&incoming));
+        assert_eq!(
+            context.span().span_context().trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+
+        let mut outgoing = HeaderMap::new();
+        propagator.inject_context(&context, &mut HeaderInjector(&mut outgoing));
+        assert_eq!(outgoing["traceparent"], traceparent);
+    }
+}
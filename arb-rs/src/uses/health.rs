@@ -0,0 +1,282 @@
+use std::{collections::BTreeMap, time::Instant};
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde::Serialize;
+
+use crate::{
+    cache::CacheKey,
+    error::{Error, Result},
+    path::{games_in_progress_path, League},
+    services::{
+        health::{
+            credentials_check, readiness, Check, CheckStatus, Readiness, SLOW_REDIS,
+        },
+        usage::{metered, Upstream},
+    },
+    uses::sportradar::UseCaseState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    pub uptime_seconds: u64,
+    pub redis_connected: bool,
+    pub cache_size: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LivenessResponse {
+    pub status: &'static str,
+    pub uptime_seconds: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeasonStatus {
+    pub regular: String,
+    pub postseason: String,
+    /// `MM-DD-YYYY`
+    pub postseason_start: String,
+    /// The season requests for today use
+    pub current: Option<String>,
+    pub in_postseason: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub status: Readiness,
+    pub uptime_seconds: u64,
+    /// `redis`, `database`, `config`, `credentials`, then one per upstream
+    pub checks: BTreeMap<String, Check>,
+    pub seasons: BTreeMap<String, SeasonStatus>,
+}
+
+pub async fn health_check(
+    State(state): State<UseCaseState>,
+) -> Result<Json<HealthResponse>> {
+    let uptime = state.start_time.elapsed().as_secs();
+
+    let redis_connected = state.lock_cache().await.ping().await.is_ok();
+    let cache_size = state.lock_cache().await.get_size().await.unwrap_or(0);
+
+    Ok(Json(HealthResponse {
+        uptime_seconds: uptime,
+        redis_connected,
+        cache_size,
+    }))
+}
+
+/// Whether the process is up; touches no dependencies
+/// GET /health/live
+pub async fn handle_liveness_request(
+    State(state): State<UseCaseState>,
+) -> Json<LivenessResponse> {
+    Json(LivenessResponse {
+        status: "ok",
+        uptime_seconds: state.start_time.elapsed().as_secs(),
+    })
+}
+
+/// Whether this instance should get traffic, with the status of each
+/// dependency; 503 when it shouldn't
+/// GET /health/ready
+pub async fn handle_readiness_request(
+    State(state): State<UseCaseState>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let mut checks = BTreeMap::new();
+    let redis = redis_check(&state).await;
+    let redis_up = matches!(redis.status, CheckStatus::Up | CheckStatus::Degraded);
+    checks.insert("redis".to_string(), redis);
+    checks.insert("database".to_string(), database_check(&state).await);
+
+    let problems = state.config.problems();
+    let config = if problems.is_empty() {
+        Check::up()
+    } else {
+        Check::down(problems.join("; "))
+    };
+    checks.insert("config".to_string(), config);
+    checks.insert(
+        "credentials".to_string(),
+        credentials_check(&state.config.missing_credentials()),
+    );
+
+    // Probes are only as cheap as their stored results; don't spend quota
+    // on every load balancer poll while Redis is away
+    let (sports_data, rolling_insights, twitter_api, reddit) = if redis_up {
+        tokio::join!(
+            probe_upstream(&state, Upstream::SportsData),
+            probe_upstream(&state, Upstream::RollingInsights),
+            probe_upstream(&state, Upstream::TwitterApi),
+            probe_upstream(&state, Upstream::Reddit),
+        )
+    } else {
+        let skipped = || Check::skipped("not probed while Redis is down");
+        (skipped(), skipped(), skipped(), skipped())
+    };
+    for (upstream, check) in [
+        (Upstream::SportsData, sports_data),
+        (Upstream::RollingInsights, rolling_insights),
+        (Upstream::TwitterApi, twitter_api),
+        (Upstream::Reddit, reddit),
+    ] {
+        checks.insert(upstream.to_string(), check);
+    }
+
+    let status = readiness(&checks);
+    if status != Readiness::Ready {
+        tracing::warn!("Readiness is {:?}: {:?}", status, checks);
+    }
+    let code = match status {
+        Readiness::NotReady => StatusCode::SERVICE_UNAVAILABLE,
+        Readiness::Ready | Readiness::Degraded => StatusCode::OK,
+    };
+
+    (
+        code,
+        Json(ReadinessResponse {
+            status,
+            uptime_seconds: state.start_time.elapsed().as_secs(),
+            checks,
+            seasons: seasons(&state),
+        }),
+    )
+}
+
+/// PING of the store, which sessions, API keys and rate limits live in
+/// whether or not caching is enabled
+async fn redis_check(state: &UseCaseState) -> Check {
+    let mut store = state.lock_store().await;
+    let started = Instant::now();
+    let ping = store.ping().await;
+    let latency = started.elapsed();
+    match ping {
+        Ok(()) if latency > SLOW_REDIS => {
+            Check::degraded(format!("PING took over {}ms", SLOW_REDIS.as_millis()))
+                .with_latency(latency)
+        }
+        Ok(()) => Check::up().with_latency(latency),
+        Err(e) => Check::down(e.to_string()).with_latency(latency),
+    }
+}
+
+/// `SELECT 1` against Postgres; only collected social posts need it, so a
+/// failure degrades rather than takes the instance out of rotation
+async fn database_check(state: &UseCaseState) -> Check {
+    let Some(database) = &state.database else {
+        return Check::skipped("no database configured");
+    };
+    let started = Instant::now();
+    match database.ping().await {
+        Ok(()) => Check::up(),
+        Err(e) => Check::down(e.to_string()),
+    }
+    .with_latency(started.elapsed())
+}
+
+/// An upstream's last probe result from the store
+async fn stored_probe(state: &UseCaseState, cache_key: &CacheKey) -> Option<Check> {
+    let stored = state.lock_store().await.get(cache_key).await;
+    stored
+        .ok()
+        .flatten()
+        .and_then(|data| serde_json::from_str::<Check>(&data).ok())
+}
+
+/// An upstream's last probe result, probing it again once that expires
+async fn probe_upstream(state: &UseCaseState, upstream: Upstream) -> Check {
+    let cache_key = CacheKey::health_probe(upstream);
+    if let Some(check) = stored_probe(state, &cache_key).await {
+        return check;
+    }
+
+    // Polls that arrive while a probe is out wait for it and reuse its result
+    let _probing = state.probes.lock(upstream).await;
+    if let Some(check) = stored_probe(state, &cache_key).await {
+        return check;
+    }
+
+    let started = Instant::now();
+    let check = match call_upstream(state, upstream).await {
+        None => return Check::skipped("credentials not configured"),
+        Some(Ok(())) => Check::up(),
+        Some(Err(e)) => Check::down(e.to_string()),
+    }
+    .with_latency(started.elapsed())
+    .checked_at(chrono::Utc::now().timestamp());
+
+    if let Ok(data) = serde_json::to_string(&check) {
+        let ttl = state.config.cache.ttl.health_probe;
+        if let Err(e) = state.lock_store().await.setx(&cache_key, &data, ttl).await {
+            tracing::warn!("Failed to store {} probe: {}", upstream, e);
+        }
+    }
+    check
+}
+
+/// The cheapest authenticated call each upstream offers; `None` when its
+/// credentials aren't configured
+async fn call_upstream(state: &UseCaseState, upstream: Upstream) -> Option<Result<()>> {
+    let api = &state.config.api;
+    let request = match upstream {
+        Upstream::SportsData if !api.sportsdata_api_key.is_empty() => state
+            .probes
+            .http
+            .get(games_in_progress_path(League::Mlb).to_string())
+            .query(&[("key", &api.sportsdata_api_key)]),
+        Upstream::RollingInsights if !api.rsc_token.is_empty() => state
+            .probes
+            .http
+            .get(format!("{}team-info/NBA", api.rolling_insights_base_url))
+            .query(&[("RSC_token", &api.rsc_token)]),
+        Upstream::TwitterApi if !api.twitter_api_key.is_empty() => state
+            .twitter
+            .http
+            .get(format!(
+                "{}/oapi/my/info",
+                api.twitter_base_url.trim_end_matches('/')
+            ))
+            .header("X-API-Key", &api.twitter_api_key),
+        Upstream::Reddit if !api.reddit_oauth.client_id.is_empty() => {
+            let about = state
+                .reddit
+                .get::<serde_json::Value>("/r/sports/about", &[])
+                .await;
+            return Some(about.map(|_| ()));
+        }
+        _ => return None,
+    };
+
+    let response = match metered(&state.usage, upstream, request).await {
+        Ok(response) => response,
+        Err(e) => return Some(Err(e)),
+    };
+    if response.status().is_success() {
+        Some(Ok(()))
+    } else {
+        Some(Err(Error::Status(response.status())))
+    }
+}
+
+fn seasons(state: &UseCaseState) -> BTreeMap<String, SeasonStatus> {
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    state
+        .config
+        .seasons
+        .current_seasons
+        .iter()
+        .map(|(sport, season)| {
+            (
+                sport.clone(),
+                SeasonStatus {
+                    regular: season.regular.clone(),
+                    postseason: season.postseason.clone(),
+                    postseason_start: season.postseason_start.clone(),
+                    current: state.config.get_season_identifier(sport, &today),
+                    in_postseason: state.config.is_postseason_date(sport, &today),
+                },
+            )
+        })
+        .collect()
+}
+
+pub use handle_liveness_request as liveness;
+pub use handle_readiness_request as readiness_check;
@@ -1,2 +1,3 @@
+pub mod health;
 pub mod rolling_insights;
 pub mod sportradar;
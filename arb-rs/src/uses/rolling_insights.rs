@@ -1,6 +1,6 @@
 use std::time::Instant;
 
-use async_std::sync::{Arc, Mutex};
+use async_std::sync::{Arc, Mutex, MutexGuard};
 use axum::{
     extract::{Query, State},
     http::StatusCode,
@@ -14,6 +14,7 @@ use crate::{
     cache::{Cache, CacheKey, Provider},
     config::ArbConfig,
     error::Result,
+    metrics::Metrics,
     path::League,
     schema::rolling_insights::{
         data_type::DataType,
@@ -28,6 +29,7 @@ use crate::{
             teams::NBARollingInsightsTeamProfile,
         },
     },
+    services::usage::{metered, Upstream, UpstreamUsage},
 };
 
 #[derive(Clone)]
@@ -35,17 +37,35 @@ pub struct UseCaseState {
     pub cache: Arc<Mutex<Cache>>,
     pub start_time: Instant,
     pub config: ArbConfig,
+    pub metrics: Metrics,
+    /// Shared with the SportsData routes, so both count against the caps
+    pub usage: UpstreamUsage,
 }
 
 impl UseCaseState {
-    pub fn new(cache: Arc<Mutex<Cache>>, config: ArbConfig) -> Self {
+    pub fn new(
+        cache: Arc<Mutex<Cache>>,
+        config: ArbConfig,
+        metrics: Metrics,
+        usage: UpstreamUsage,
+    ) -> Self {
         Self {
             cache,
             start_time: Instant::now(),
             config,
+            metrics,
+            usage,
         }
     }
 
+    /// Lock the shared cache, recording how long it took
+    pub async fn lock_cache(&self) -> MutexGuard<'_, Cache> {
+        let started = Instant::now();
+        let cache = self.cache.lock().await;
+        self.metrics.observe_cache_lock_wait(started.elapsed());
+        cache
+    }
+
     pub fn provider(&self) -> Provider {
         Provider::RollingInsights
     }
@@ -128,7 +148,7 @@ pub async fn schedule(
 
     // Try cache first
     let cached_data = if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         cache.get(&cache_key).await?
     } else {
         info!("Cache bypass requested — fetching fresh data from Rolling Insights API");
@@ -146,15 +166,12 @@ pub async fn schedule(
         );
 
         let client = reqwest::Client::new();
-        let response = client
-            .get(&api_url)
-            .query(&[("RSC_token", rsc_token)])
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Failed to make HTTP request: {}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+        let response = metered(
+            &use_case_state.usage,
+            Upstream::RollingInsights,
+            client.get(&api_url).query(&[("RSC_token", rsc_token)]),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -199,7 +216,7 @@ pub async fn schedule(
 
         // Cache the full response body
         if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             if let Err(e) = cache
                 .setx(&cache_key, &body, use_case_state.config.cache.ttl.schedule)
                 .await
@@ -305,7 +322,7 @@ pub async fn box_score(
 
     // Try cache first
     let cached_data = if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         cache.get(&cache_key).await?
     } else {
         info!("Cache bypass requested — fetching fresh data from Rolling Insights API");
@@ -323,18 +340,15 @@ pub async fn box_score(
         );
 
         let client = reqwest::Client::new();
-        let response = client
-            .get(&api_url)
-            .query(&[
+        let response = metered(
+            &use_case_state.usage,
+            Upstream::RollingInsights,
+            client.get(&api_url).query(&[
                 ("RSC_token", rsc_token.as_str()),
                 ("game_id", params.game_id.as_str()),
-            ])
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Failed to make HTTP request: {}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            ]),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -380,7 +394,7 @@ pub async fn box_score(
 
         // Cache the full response body
         if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             if let Err(e) = cache
                 .setx(
                     &cache_key,
@@ -521,7 +535,7 @@ pub async fn current_games(
 
         // Try cache first
         let cached_data = if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             cache.get(&date_cache_key).await?
         } else {
             None
@@ -537,15 +551,12 @@ pub async fn current_games(
             );
 
             let client = reqwest::Client::new();
-            let response = client
-                .get(&api_url)
-                .query(&[("RSC_token", rsc_token)])
-                .send()
-                .await
-                .map_err(|e| {
-                    error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &use_case_state.usage,
+                Upstream::RollingInsights,
+                client.get(&api_url).query(&[("RSC_token", rsc_token)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -571,7 +582,7 @@ pub async fn current_games(
 
             // Cache the full response body
             if use_cache {
-                let mut cache = use_case_state.cache.lock().await;
+                let mut cache = use_case_state.lock_cache().await;
                 if let Err(e) = cache
                     .setx(
                         &date_cache_key,
@@ -684,7 +695,7 @@ pub async fn team_profiles(
 
     // Try cache first
     let cached_data = if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         cache.get(&cache_key).await?
     } else {
         info!("Cache bypass requested — fetching fresh data from Rolling Insights API");
@@ -699,15 +710,12 @@ pub async fn team_profiles(
         info!("Fetching team profiles data from Rolling Insights API");
 
         let client = reqwest::Client::new();
-        let response = client
-            .get(&api_url)
-            .query(&[("RSC_token", rsc_token)])
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Failed to make HTTP request: {}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+        let response = metered(
+            &use_case_state.usage,
+            Upstream::RollingInsights,
+            client.get(&api_url).query(&[("RSC_token", rsc_token)]),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -752,7 +760,7 @@ pub async fn team_profiles(
 
         // Cache the full response body
         if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             if let Err(e) = cache
                 .setx(
                     &cache_key,
@@ -884,7 +892,7 @@ pub async fn player_profiles(
 
     // Try cache first
     let cached_data = if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         cache.get(&cache_key).await?
     } else {
         info!("Cache bypass requested — fetching fresh data from Rolling Insights API");
@@ -906,10 +914,8 @@ pub async fn player_profiles(
             request = request.query(&[("team_id", team_id.to_string())]);
         }
 
-        let response = request.send().await.map_err(|e| {
-            error!("Failed to make HTTP request: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        let response =
+            metered(&use_case_state.usage, Upstream::RollingInsights, request).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -955,7 +961,7 @@ pub async fn player_profiles(
 
         // Cache the full response body
         if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             if let Err(e) = cache
                 .setx(
                     &cache_key,
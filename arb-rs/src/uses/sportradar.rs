@@ -1,23 +1,36 @@
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
-use async_std::sync::{Arc, Mutex};
+use async_std::sync::{Arc, Mutex, MutexGuard};
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
-    response::{Json, Redirect},
+    extract::{ConnectInfo, MatchedPath, Query, Request, State},
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Redirect, Response},
+    Extension,
 };
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 use url::Url;
 use urlencoding;
 
 use crate::{
     cache::{Cache, CacheKey, Provider},
-    config::ArbConfig,
+    config::{ArbConfig, RatingModel, TeamSocialConfig, VideoSourceKind},
+    database::Database,
     error::{Error, Result},
+    metrics::Metrics,
     path::{
-        box_score_path, games_by_date_path, headshots_path, odds_by_date_path,
-        play_by_play_path, postseason_schedule_path, schedule_path, stadiums_path,
-        standings_path, team_profile_path, League,
+        box_score_path, depth_charts_path, games_by_date_path, headshots_path,
+        injured_players_path, news_path, odds_by_date_path, play_by_play_path,
+        player_game_log_path, player_path, player_season_stats_by_player_path,
+        player_season_stats_path, postseason_schedule_path, schedule_path,
+        scores_basic_final_path, stadiums_path, standings_path, starting_lineups_path,
+        team_players_path, team_profile_path, team_season_stats_path, League,
     },
     schema::sportradar::{
         data_type::DataType,
@@ -33,15 +46,79 @@ use crate::{
     },
     schema::{
         reddit::{
-            game_thread::{find_live_game_thread, RedditListing},
+            game_thread::{
+                find_game_thread, GameThread, GameThreadTarget, RedditListing,
+            },
             {
                 RedditChild, RedditComment, RedditPost, RedditSearchQuery,
                 RedditSearchResponse,
             },
         },
+        sportradar::{
+            depth_chart::TeamDepthChart,
+            injuries::InjuredPlayer,
+            mlb::lineups::{LineupPlayer, MLBStartingLineup},
+            nba::lineups::NBAStartingLineup,
+            news::SportsDataNews,
+            player::{PlayerGameStat, SportsDataPlayer},
+        },
         twitterapi::tweet::TwitterSearchResponse,
     },
-    services::auth::{AppleOAuth, GoogleOAuth},
+    services::{
+        auth::{
+            is_allowed_return_url, is_pkce_challenge, random_token, sign_value,
+            token_hash, verify_signed_value, AppleOAuth, AuthSession, GoogleOAuth,
+            IdTokenProvider, IdTokenVerifier, JwtUtils, OAuthSession, SignInCode,
+            TokenPair, User, GUEST_PROVIDER, OAUTH_STATE_COOKIE,
+        },
+        feed::{feed_games, FeedGame},
+        health::UpstreamProbes,
+        injuries::{
+            annotate, depth_chart_starters, detect_ruled_out_starters, snapshot,
+            InjuryReportEntry, InjurySnapshot, StarterAlert, MAX_STARTER_ALERTS,
+        },
+        moderation::{Moderator, Strictness},
+        news::{
+            dedupe, eastern_datetime, feed_stories, tag_roster, NewsKind, NewsPage,
+            NewsStory,
+        },
+        pagination::paginate,
+        players::{
+            career_start, career_totals, game_log, splits, CareerTotals, GameLogEntry,
+            PlayerSplits, CAREER_SEASON_FETCHES,
+        },
+        preferences::{FavoriteTeam, UserFavorites, UserPreferences},
+        rate_limit::{generate_api_key, window_start, ApiKey, ApiTier, RateLimitStatus},
+        ratings::{EloParams, GamePrediction, RatingPoint, RatingsBook, TeamRating},
+        reddit::RedditClient,
+        sentiment::{series as sentiment_series, FanBase, SentimentPoint},
+        simulation::{simulate_playoff_odds, PlayoffOdds, MAX_ITERATIONS},
+        social::{
+            game_posts, game_query, game_window, store_posts, upsert_posts, SocialPost,
+            SocialRefresh,
+        },
+        standings::{
+            analyze_standings, parse_schedule, PlayoffFormat, ScheduledGame,
+            StandingsAnalytics, TeamRecord,
+        },
+        stats::{
+            parse_stat_lines, rank_leaderboard, LeaderboardEntry, LeaderboardFilter,
+            SortOrder, StatKind, StatLine,
+        },
+        timeline::{
+            comment_items, merge, page, play_items, tweet_items, TimelineCursor,
+            TimelineItem, TimelinePage,
+        },
+        twitter::{parse_retry_after, TwitterClient},
+        usage::{
+            metered, parse_rollup, rollup_fields, Upstream, UpstreamUsage, UsageEntry,
+            UsageScope, USAGE_SCOPE,
+        },
+        videos::{
+            feed_videos, league_videos, link_games, provider_videos, store_videos,
+            tag_teams, upsert_videos, VideoCatalog, VideoPage, VideoRefreshes, VideoType,
+        },
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -66,6 +143,276 @@ pub struct StandingsQuery {
     pub cache: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PlayoffOddsQuery {
+    pub league: String,
+    pub season: i32,
+    /// Rating model override, defaults to `simulation.model` from config
+    #[serde(default)]
+    pub model: Option<RatingModel>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RatingsQuery {
+    pub league: String,
+    /// Team key, required for the history endpoint
+    #[serde(default)]
+    pub team: Option<String>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WinProbabilityQuery {
+    pub league: String,
+    /// Predict every game on this date (YYYY-MM-DD)
+    #[serde(default)]
+    pub date: Option<String>,
+    /// Predict a single matchup instead of a date
+    #[serde(default)]
+    pub home: Option<String>,
+    #[serde(default)]
+    pub away: Option<String>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SeasonStatsQuery {
+    pub league: String,
+    /// Provider season (e.g. "2025"), defaults to the configured regular season
+    #[serde(default)]
+    pub season: Option<String>,
+    #[serde(default)]
+    pub kind: StatKind,
+    #[serde(default)]
+    pub team: Option<String>,
+    #[serde(default)]
+    pub position: Option<String>,
+    #[serde(default)]
+    pub min_games: Option<f64>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQuery {
+    pub league: String,
+    /// Stat to rank by, matched case-insensitively (e.g. "HomeRuns")
+    pub stat: String,
+    #[serde(default)]
+    pub season: Option<String>,
+    #[serde(default)]
+    pub kind: StatKind,
+    #[serde(default)]
+    pub team: Option<String>,
+    #[serde(default)]
+    pub position: Option<String>,
+    #[serde(default)]
+    pub min_games: Option<f64>,
+    #[serde(default)]
+    pub order: SortOrder,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlayerQuery {
+    pub league: String,
+    pub player_id: i32,
+    /// Provider season (e.g. "2025"), defaults to the configured regular season
+    #[serde(default)]
+    pub season: Option<String>,
+    /// Limit the game log to the most recent N games
+    #[serde(default)]
+    pub games: Option<u32>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InjuriesQuery {
+    pub league: String,
+    /// Team abbreviation filter (e.g. "NYY")
+    #[serde(default)]
+    pub team: Option<String>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DepthChartsQuery {
+    pub league: String,
+    #[serde(default)]
+    pub team_id: Option<i32>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LineupsQuery {
+    pub league: String,
+    pub date: String, // YYYY-MM-DD format
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewsQuery {
+    pub league: String,
+    /// Team abbreviation (e.g. "NYY")
+    pub team: String,
+    #[serde(default)]
+    pub kind: Option<NewsKind>,
+    /// 1-based page number
+    #[serde(default)]
+    pub page: Option<usize>,
+    #[serde(default)]
+    pub page_size: Option<usize>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GameTimelineQuery {
+    pub league: String,
+    pub game_id: String,
+    /// Twitter search query for the game (e.g. "#RingTheBell")
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Subreddit whose game thread comments are merged in
+    #[serde(default)]
+    pub subreddit: Option<String>,
+    /// `next_cursor` from the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GameSentimentQuery {
+    pub league: String,
+    pub game_id: String,
+    /// Twitter search query for the game (e.g. "#RingTheBell")
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Subreddit whose game thread comments are scored
+    #[serde(default)]
+    pub subreddit: Option<String>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuthCodeRequest {
+    /// `code` from a successful sign-in redirect
+    pub code: String,
+    /// PKCE verifier for the `code_challenge` sign-in started with
+    pub code_verifier: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+    /// Required for guest sessions: the device id they were created with
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GuestRequest {
+    /// Stable per-install id; the guest's refresh tokens only work with it
+    pub device_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FavoriteTeamRequest {
+    pub league: String,
+    pub team: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FavoritesRequest {
+    pub teams: Vec<FavoriteTeamRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyRequest {
+    /// Who the key is for, e.g. "ios-app"
+    pub name: String,
+    #[serde(default)]
+    pub tier: ApiTier,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevokeApiKeyQuery {
+    pub key_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UsageQuery {
+    /// Daily rollups to include, most recent first
+    #[serde(default)]
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MeFeedQuery {
+    /// Days to cover starting today, in the user's time zone
+    #[serde(default)]
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VideosQuery {
+    pub league: String,
+    /// Only videos linked to this game
+    #[serde(default)]
+    pub game_id: Option<String>,
+    /// Only videos featuring this team (abbreviation, e.g. "PHI")
+    #[serde(default)]
+    pub team: Option<String>,
+    #[serde(default)]
+    pub video_type: Option<VideoType>,
+    /// 1-based page number
+    #[serde(default)]
+    pub page: Option<usize>,
+    #[serde(default)]
+    pub page_size: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GameSocialQuery {
+    pub league: String,
+    pub game_id: String,
+    /// Result pages to follow, capped by `social.max_pages`
+    #[serde(default)]
+    pub max_pages: Option<u32>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProbablePitchersQuery {
+    pub date: String, // YYYY-MM-DD format
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+/// Cached playoff odds tagged with the results they were simulated from
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedPlayoffOdds {
+    results_marker: String,
+    odds: PlayoffOdds,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct DataQuery {
     pub league: String,
@@ -125,13 +472,6 @@ pub struct CurrentGamesQuery {
     pub cache: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct HealthResponse {
-    pub uptime_seconds: u64,
-    pub redis_connected: bool,
-    pub cache_size: usize,
-}
-
 #[derive(Debug, Serialize)]
 pub struct TeamProfileResponse {
     pub league: String,
@@ -168,80 +508,345 @@ pub struct ScoresResponse {
     pub final_games_count: usize,
 }
 
-#[derive(Clone)]
-pub struct UseCaseState {
-    pub cache: Arc<Mutex<Cache>>,
-    pub start_time: Instant,
-    pub config: ArbConfig,
+#[derive(Debug, Serialize)]
+pub struct RatingsResponse {
+    pub league: String,
+    pub season: i32,
+    pub games_processed: usize,
+    pub updated_at: String,
+    pub ratings: Vec<TeamRating>,
 }
 
-impl UseCaseState {
-    pub fn new(cache: Arc<Mutex<Cache>>, config: ArbConfig) -> Self {
-        Self {
-            cache,
-            start_time: Instant::now(),
-            config,
-        }
-    }
-
-    pub fn provider(&self) -> Provider {
-        Provider::Sportradar
-    }
+#[derive(Debug, Serialize)]
+pub struct RatingHistoryResponse {
+    pub league: String,
+    pub season: i32,
+    pub team: String,
+    pub rating: Option<TeamRating>,
+    pub history: Vec<RatingPoint>,
 }
 
-pub async fn health_check(
-    State(use_case_state): State<UseCaseState>,
-) -> Result<Json<HealthResponse>> {
-    let uptime = use_case_state.start_time.elapsed().as_secs();
+#[derive(Debug, Serialize)]
+pub struct WinProbabilityResponse {
+    pub league: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    pub predictions: Vec<GamePrediction>,
+}
 
-    let redis_connected = use_case_state.cache.lock().await.ping().await.is_ok();
-    let cache_size = use_case_state
-        .cache
-        .lock()
-        .await
-        .get_size()
-        .await
-        .unwrap_or(0);
+#[derive(Debug, Serialize)]
+pub struct SeasonStatsResponse {
+    pub league: String,
+    pub season: String,
+    pub kind: StatKind,
+    pub count: usize,
+    pub lines: Vec<StatLine>,
+}
 
-    Ok(Json(HealthResponse {
-        uptime_seconds: uptime,
-        redis_connected,
-        cache_size,
-    }))
+#[derive(Debug, Serialize)]
+pub struct LeaderboardResponse {
+    pub league: String,
+    pub season: String,
+    pub kind: StatKind,
+    pub stat: String,
+    pub count: usize,
+    pub leaders: Vec<LeaderboardEntry>,
 }
 
-pub async fn team_profile(
-    Query(params): Query<TeamProfileQuery>,
-    State(use_case_state): State<UseCaseState>,
-) -> Result<Json<LeagueResponse>> {
-    let league = params.league.parse::<League>().map_err(|_| {
-        tracing::error!("Invalid league: {}", params.league);
-        StatusCode::BAD_REQUEST
-    })?;
+#[derive(Debug, Serialize)]
+pub struct PlayerProfileResponse {
+    pub league: String,
+    pub player_id: i32,
+    pub name: String,
+    pub headshot_url: Option<String>,
+    pub player: SportsDataPlayer,
+}
 
-    let api_path = team_profile_path(league.clone());
-    let api_url = api_path.to_string();
+#[derive(Debug, Serialize)]
+pub struct PlayerGameLogResponse {
+    pub league: String,
+    pub player_id: i32,
+    pub season: String,
+    pub count: usize,
+    pub games: Vec<GameLogEntry>,
+}
 
-    let cache_key = CacheKey::team_profile(use_case_state.provider(), &league);
-    let use_cache = params.cache.unwrap_or(true);
+#[derive(Debug, Serialize)]
+pub struct PlayerSplitsResponse {
+    pub league: String,
+    pub player_id: i32,
+    pub season: String,
+    pub games: usize,
+    pub splits: PlayerSplits,
+}
 
-    if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
-        if let Some(cached_data) = cache.get(&cache_key).await? {
-            tracing::debug!("Returning cached team profiles data for league: {}", league);
-            let response: LeagueResponse =
-                serde_json::from_str(&cached_data).map_err(|e| {
-                    tracing::error!("Failed to deserialize cached data: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
-            return Ok(Json(response));
-        }
-    } else {
-        tracing::info!("Cache bypass requested - fetching fresh data from API");
-    }
+#[derive(Debug, Serialize)]
+pub struct PlayerCareerResponse {
+    pub league: String,
+    pub player_id: i32,
+    pub name: String,
+    pub career: CareerTotals,
+}
 
-    let raw_data =
-        fetch_team_data_from_api(&api_url, &league, &use_case_state.config).await?;
+#[derive(Debug, Serialize)]
+pub struct InjuriesResponse {
+    pub league: String,
+    pub count: usize,
+    pub injuries: Vec<InjuryReportEntry>,
+    /// Starters ruled out since an earlier report, newest first
+    pub starter_alerts: Vec<StarterAlert>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepthChartsResponse {
+    pub league: String,
+    pub count: usize,
+    pub teams: Vec<TeamDepthChart>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum StartingLineups {
+    Mlb(Vec<MLBStartingLineup>),
+    Nba(Vec<NBAStartingLineup>),
+}
+
+#[derive(Debug, Serialize)]
+pub struct LineupsResponse {
+    pub league: String,
+    pub date: String,
+    pub games: StartingLineups,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProbablePitchers {
+    pub game_id: i32,
+    pub home_team: String,
+    pub away_team: String,
+    pub home_pitcher: Option<LineupPlayer>,
+    pub away_pitcher: Option<LineupPlayer>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProbablePitchersResponse {
+    pub date: String,
+    pub games: Vec<ProbablePitchers>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NewsResponse {
+    pub league: String,
+    pub team: String,
+    #[serde(flatten)]
+    pub page: NewsPage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GameTimelineResponse {
+    pub league: String,
+    pub game_id: String,
+    #[serde(flatten)]
+    pub page: TimelinePage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GameSentimentResponse {
+    pub league: String,
+    pub game_id: String,
+    pub home_team: String,
+    pub away_team: String,
+    /// Tweets and comments scored
+    pub scored: usize,
+    pub points: Vec<SentimentPoint>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssuedApiKey {
+    /// The secret key, sent as `X-API-Key`; it can't be shown again
+    pub key: String,
+    pub key_id: String,
+    pub name: String,
+    pub tier: ApiTier,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpstreamSpend {
+    pub upstream: Upstream,
+    /// Calls this month across all instances
+    pub month_calls: u64,
+    /// `None` when the upstream isn't capped
+    pub monthly_cap: Option<u64>,
+    pub remaining: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageDay {
+    /// `YYYY-MM-DD`, UTC
+    pub date: String,
+    pub entries: Vec<UsageEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageReport {
+    /// `YYYY-MM`, UTC
+    pub month: String,
+    pub upstreams: Vec<UpstreamSpend>,
+    pub days: Vec<UsageDay>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MeFeedResponse {
+    pub time_zone: String,
+    /// First and last day covered, `YYYY-MM-DD` in `time_zone`
+    pub from: String,
+    pub to: String,
+    pub games: Vec<FeedGame>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MeResponse {
+    pub user_id: String,
+    pub email: Option<String>,
+    pub provider: String,
+    /// RFC 3339, UTC
+    pub signed_in_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideosResponse {
+    pub league: String,
+    pub game_id: Option<String>,
+    pub team: Option<String>,
+    /// When the league's sources were last read
+    pub fetched_at: String,
+    #[serde(flatten)]
+    pub page: VideoPage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GameSocialResponse {
+    pub league: String,
+    pub game_id: String,
+    pub home_team: String,
+    pub away_team: String,
+    /// Advanced-search query generated for the game
+    pub query: String,
+    pub pages_fetched: u32,
+    pub new_posts: usize,
+    pub fetched_at: String,
+    pub count: usize,
+    pub posts: Vec<SocialPost>,
+}
+
+#[derive(Clone)]
+pub struct UseCaseState {
+    pub cache: Arc<Mutex<Cache>>,
+    /// Sessions, keys, rate-limit counters and user data; see [`Cache::store`]
+    pub store: Arc<Mutex<Cache>>,
+    pub start_time: Instant,
+    pub config: ArbConfig,
+    pub twitter: TwitterClient,
+    pub reddit: RedditClient,
+    pub id_tokens: IdTokenVerifier,
+    pub usage: UpstreamUsage,
+    pub metrics: Metrics,
+    pub database: Option<Database>,
+    pub probes: UpstreamProbes,
+    pub video_refreshes: VideoRefreshes,
+}
+
+impl UseCaseState {
+    pub fn new(
+        cache: Arc<Mutex<Cache>>,
+        store: Arc<Mutex<Cache>>,
+        config: ArbConfig,
+        metrics: Metrics,
+    ) -> Self {
+        let timeout = std::time::Duration::from_secs(config.api.request_timeout);
+        let usage = UpstreamUsage::new(store.clone(), metrics.clone(), &config.usage);
+        let twitter = TwitterClient::new(&config.api.twitter_api, timeout);
+        let reddit = RedditClient::new(
+            &config.api.reddit_oauth,
+            &config.api.reddit_api.user_agent,
+            timeout,
+            usage.clone(),
+        );
+        let database = Database::new(&config.database).unwrap_or_else(|e| {
+            tracing::error!(
+                "Database unavailable, social posts and videos won't be stored: {}",
+                e
+            );
+            None
+        });
+        Self {
+            cache,
+            store,
+            start_time: Instant::now(),
+            config,
+            twitter,
+            reddit,
+            id_tokens: IdTokenVerifier::new(timeout),
+            usage,
+            metrics,
+            database,
+            probes: UpstreamProbes::new(timeout),
+            video_refreshes: VideoRefreshes::default(),
+        }
+    }
+
+    /// Lock the shared cache, recording how long it took
+    pub async fn lock_cache(&self) -> MutexGuard<'_, Cache> {
+        let started = Instant::now();
+        let cache = self.cache.lock().await;
+        self.metrics.observe_cache_lock_wait(started.elapsed());
+        cache
+    }
+
+    /// Lock the always-on store, recording how long it took
+    pub async fn lock_store(&self) -> MutexGuard<'_, Cache> {
+        let started = Instant::now();
+        let store = self.store.lock().await;
+        self.metrics.observe_cache_lock_wait(started.elapsed());
+        store
+    }
+
+    pub fn provider(&self) -> Provider {
+        Provider::Sportradar
+    }
+}
+
+pub async fn team_profile(
+    Query(params): Query<TeamProfileQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<LeagueResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let api_path = team_profile_path(league.clone());
+    let api_url = api_path.to_string();
+
+    let cache_key = CacheKey::team_profile(use_case_state.provider(), &league);
+    let use_cache = params.cache.unwrap_or(true);
+
+    if use_cache {
+        let mut cache = use_case_state.lock_cache().await;
+        if let Some(cached_data) = cache.get(&cache_key).await? {
+            tracing::debug!("Returning cached team profiles data for league: {}", league);
+            let response: LeagueResponse =
+                serde_json::from_str(&cached_data).map_err(|e| {
+                    tracing::error!("Failed to deserialize cached data: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            return Ok(Json(response));
+        }
+    } else {
+        tracing::info!("Cache bypass requested - fetching fresh data from API");
+    }
+
+    let raw_data = fetch_team_data_from_api(&api_url, &league, &use_case_state).await?;
 
     let response = match league {
         League::Mlb => {
@@ -315,7 +920,7 @@ pub async fn team_profile(
     };
 
     {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         let serialized = serde_json::to_string(&response).map_err(|e| {
             tracing::error!("Failed to serialize response: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
@@ -339,27 +944,24 @@ pub async fn team_profile(
 async fn fetch_team_data_from_api(
     api_url: &str,
     league: &League,
-    config: &ArbConfig,
+    state: &UseCaseState,
 ) -> Result<String> {
     let _parsed_url = Url::parse(api_url)?;
 
     match league {
         League::Mlb => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for team profile: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -386,21 +988,18 @@ async fn fetch_team_data_from_api(
             Ok(body)
         }
         League::Nba => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for NBA team profile: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -427,21 +1026,18 @@ async fn fetch_team_data_from_api(
             Ok(body)
         }
         League::Nfl => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for NFL team profile: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -546,7 +1142,7 @@ async fn handle_schedule_request(
 
     // Attempt to use cache if requested
     let cached_data = if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         match cache.get(&cache_key).await {
             Ok(Some(data)) => {
                 tracing::debug!("Returning cached schedule data for league: {}", league);
@@ -577,11 +1173,10 @@ async fn handle_schedule_request(
 
         let fetched_data = if let Some(ref date) = date_filter {
             tracing::info!("Using GamesByDate endpoint for date: {}", date);
-            fetch_games_by_date_from_api(&api_url, &league, date, &use_case_state.config)
-                .await
+            fetch_games_by_date_from_api(&api_url, &league, date, &use_case_state).await
         } else {
             tracing::info!("Using full schedule endpoint");
-            fetch_schedule_from_api(&api_url, &league, &use_case_state.config).await
+            fetch_schedule_from_api(&api_url, &league, &use_case_state).await
         }
         .map_err(|e| {
             tracing::error!("Failed to fetch schedule data: {}", e);
@@ -590,7 +1185,7 @@ async fn handle_schedule_request(
 
         // Cache fresh data
         if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             if let Err(e) = cache
                 .setx(
                     &cache_key,
@@ -749,43 +1344,15 @@ async fn handle_schedule_request(
     Ok(Json(league_response))
 }
 
-async fn handle_current_games_request(
-    use_case_state: UseCaseState,
-    params: CurrentGamesQuery,
-) -> Result<Json<LeagueResponse>> {
-    let league_str = params.league.to_lowercase();
-    let league: League = league_str.parse().map_err(|_| {
-        tracing::error!("Invalid league: {}", params.league);
-        StatusCode::BAD_REQUEST
-    })?;
-
-    if league != League::Mlb && league != League::Nfl && league != League::Nba {
-        tracing::error!("Current games not yet supported for league: {}", league);
-        return Err(StatusCode::BAD_REQUEST.into());
-    }
-
-    let start_date = chrono::NaiveDate::parse_from_str(&params.start, "%Y-%m-%d")
-        .map_err(|_| {
-            tracing::error!("Invalid start date format: {}", params.start);
-            StatusCode::BAD_REQUEST
-        })?;
-
-    let end_date =
-        chrono::NaiveDate::parse_from_str(&params.end, "%Y-%m-%d").map_err(|_| {
-            tracing::error!("Invalid end date format: {}", params.end);
-            StatusCode::BAD_REQUEST
-        })?;
-
-    if start_date > end_date {
-        tracing::error!(
-            "Start date {} is after end date {}",
-            params.start,
-            params.end
-        );
-        return Err(StatusCode::BAD_REQUEST.into());
-    }
-
-    let use_cache = params.cache.unwrap_or(true);
+/// Raw games for every day from `start_date` through `end_date`, each day
+/// cached under the league's scores key
+async fn fetch_games_between(
+    use_case_state: &UseCaseState,
+    league: &League,
+    start_date: chrono::NaiveDate,
+    end_date: chrono::NaiveDate,
+    use_cache: bool,
+) -> Result<Vec<serde_json::Value>> {
     let mut all_games = Vec::new();
     let mut current_date = start_date;
 
@@ -793,11 +1360,11 @@ async fn handle_current_games_request(
         let date_str = current_date.format("%Y-%m-%d").to_string();
         let api_url =
             games_by_date_path(league.clone(), Some(date_str.clone())).to_string();
-        let cache_key = CacheKey::scores(use_case_state.provider(), &league, &date_str);
+        let cache_key = CacheKey::scores(use_case_state.provider(), league, &date_str);
 
         // Try cache first
         let cached_data = if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             match cache.get(&cache_key).await {
                 Ok(Some(data)) => {
                     tracing::debug!("Cache hit for {}: {}", league, date_str);
@@ -829,24 +1396,20 @@ async fn handle_current_games_request(
                 api_url,
                 date_str
             );
-            let fetched_data = fetch_games_by_date_from_api(
-                &api_url,
-                &league,
-                &date_str,
-                &use_case_state.config,
-            )
-            .await
-            .map_err(|e| {
-                tracing::error!(
-                    "Failed to fetch games data for date {}: {}",
-                    date_str,
-                    e
-                );
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            let fetched_data =
+                fetch_games_by_date_from_api(&api_url, league, &date_str, use_case_state)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!(
+                            "Failed to fetch games data for date {}: {}",
+                            date_str,
+                            e
+                        );
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })?;
 
             if use_cache {
-                let mut cache = use_case_state.cache.lock().await;
+                let mut cache = use_case_state.lock_cache().await;
                 if let Err(e) = cache
                     .setx(
                         &cache_key,
@@ -878,6 +1441,50 @@ async fn handle_current_games_request(
         current_date = current_date.succ_opt().unwrap_or(current_date);
     }
 
+    Ok(all_games)
+}
+
+async fn handle_current_games_request(
+    use_case_state: UseCaseState,
+    params: CurrentGamesQuery,
+) -> Result<Json<LeagueResponse>> {
+    let league_str = params.league.to_lowercase();
+    let league: League = league_str.parse().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    if league != League::Mlb && league != League::Nfl && league != League::Nba {
+        tracing::error!("Current games not yet supported for league: {}", league);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let start_date = chrono::NaiveDate::parse_from_str(&params.start, "%Y-%m-%d")
+        .map_err(|_| {
+            tracing::error!("Invalid start date format: {}", params.start);
+            StatusCode::BAD_REQUEST
+        })?;
+
+    let end_date =
+        chrono::NaiveDate::parse_from_str(&params.end, "%Y-%m-%d").map_err(|_| {
+            tracing::error!("Invalid end date format: {}", params.end);
+            StatusCode::BAD_REQUEST
+        })?;
+
+    if start_date > end_date {
+        tracing::error!(
+            "Start date {} is after end date {}",
+            params.start,
+            params.end
+        );
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let use_cache = params.cache.unwrap_or(true);
+    let all_games =
+        fetch_games_between(&use_case_state, &league, start_date, end_date, use_cache)
+            .await?;
+
     let total_count = all_games.len();
 
     let league_response = match league {
@@ -979,7 +1586,7 @@ async fn handle_headshots_request(
 
     // Try cache first
     let cached_data = if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         match cache.get(&cache_key).await {
             Ok(Some(data)) => {
                 tracing::debug!("Cache hit for headshots data: {}", league);
@@ -1004,20 +1611,16 @@ async fn handle_headshots_request(
         data
     } else {
         tracing::info!("Fetching headshots data from API: {}", api_url);
-        let fetched_data = fetch_data_from_api(
-            &api_url,
-            &league,
-            &DataType::Headshots,
-            &use_case_state.config,
-        )
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch headshots data: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        let fetched_data =
+            fetch_data_from_api(&api_url, &league, &DataType::Headshots, &use_case_state)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to fetch headshots data: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
 
         if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             let ttl = use_case_state.config.cache.ttl.team_profiles; // same TTL used before
             if let Err(e) = cache.setx(&cache_key, &fetched_data, ttl).await {
                 tracing::error!("Failed to cache headshots data: {}", e);
@@ -1096,7 +1699,7 @@ async fn handle_play_by_play_request(
 
     // Try cache first
     let cached_data = if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         match cache.get(&cache_key).await {
             Ok(Some(data)) => {
                 tracing::debug!(
@@ -1144,7 +1747,7 @@ async fn handle_play_by_play_request(
             &params.game_id,
             params.last_timestamp.is_some(),
             params.delta_minutes,
-            &use_case_state.config,
+            &use_case_state,
         )
         .await
         .map_err(|e| {
@@ -1158,7 +1761,7 @@ async fn handle_play_by_play_request(
 
         // Cache fresh data
         if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             if let Err(e) = cache
                 .setx(
                     &cache_key,
@@ -1288,7 +1891,7 @@ async fn handle_scores_request(
 
     // Attempt to use cache first if requested
     let cached_data = if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         match cache.get(&cache_key).await {
             Ok(Some(data)) => {
                 tracing::debug!("Returning cached scores data for date: {}", date_str);
@@ -1326,7 +1929,7 @@ async fn handle_scores_request(
         );
 
         let fetched_data = if is_postseason {
-            fetch_schedule_from_api(&api_url, &league, &use_case_state.config)
+            fetch_schedule_from_api(&api_url, &league, &use_case_state)
                 .await
                 .map_err(|e| {
                     tracing::error!(
@@ -1337,26 +1940,21 @@ async fn handle_scores_request(
                     StatusCode::INTERNAL_SERVER_ERROR
                 })?
         } else {
-            fetch_games_by_date_from_api(
-                &api_url,
-                &league,
-                &date_str,
-                &use_case_state.config,
-            )
-            .await
-            .map_err(|e| {
-                tracing::error!(
-                    "Failed to fetch scores data for date {}: {}",
-                    date_str,
-                    e
-                );
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
+            fetch_games_by_date_from_api(&api_url, &league, &date_str, &use_case_state)
+                .await
+                .map_err(|e| {
+                    tracing::error!(
+                        "Failed to fetch scores data for date {}: {}",
+                        date_str,
+                        e
+                    );
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?
         };
 
         // Cache fresh data
         if use_cache {
-            let mut cache = use_case_state.cache.lock().await;
+            let mut cache = use_case_state.lock_cache().await;
             if let Err(e) = cache
                 .setx(
                     &cache_key,
@@ -1537,7 +2135,7 @@ fn extract_play_by_play_events(
 async fn fetch_schedule_from_api(
     api_url: &str,
     league: &League,
-    config: &ArbConfig,
+    state: &UseCaseState,
 ) -> Result<String> {
     let _parsed_url = Url::parse(api_url)?;
 
@@ -1545,21 +2143,18 @@ async fn fetch_schedule_from_api(
 
     match league {
         League::Mlb => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for schedule: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -1613,21 +2208,18 @@ async fn fetch_schedule_from_api(
             Ok(body)
         }
         League::Nfl => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for NFL schedule: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -1674,21 +2266,18 @@ async fn fetch_schedule_from_api(
             Ok(body)
         }
         League::Nba => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for NBA schedule: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -1752,7 +2341,7 @@ async fn fetch_data_from_api(
     api_url: &str,
     league: &League,
     data_type: &DataType,
-    config: &ArbConfig,
+    state: &UseCaseState,
 ) -> Result<String> {
     let _parsed_url = Url::parse(api_url)?;
 
@@ -1765,21 +2354,18 @@ async fn fetch_data_from_api(
         ))),
         League::Nfl => {
             if *data_type == DataType::Headshots {
-                let api_key = &config.api.sportsdata_api_key;
+                let api_key = &state.config.api.sportsdata_api_key;
 
                 let client = reqwest::Client::new();
 
                 tracing::info!("Making real API request for NFL headshots: {}", api_url);
 
-                let response = client
-                    .get(api_url)
-                    .query(&[("key", &api_key)])
-                    .send()
-                    .await
-                    .map_err(|e| {
-                        tracing::error!("Failed to make HTTP request: {}", e);
-                        StatusCode::INTERNAL_SERVER_ERROR
-                    })?;
+                let response = metered(
+                    &state.usage,
+                    Upstream::SportsData,
+                    client.get(api_url).query(&[("key", &api_key)]),
+                )
+                .await?;
 
                 if !response.status().is_success() {
                     tracing::error!(
@@ -1816,7 +2402,7 @@ async fn fetch_play_by_play_from_api(
     game_id: &str,
     is_delta: bool,
     _delta_minutes: Option<u32>,
-    config: &ArbConfig,
+    state: &UseCaseState,
 ) -> Result<String> {
     let _parsed_url = Url::parse(api_url)?;
 
@@ -1827,7 +2413,7 @@ async fn fetch_play_by_play_from_api(
         game_id
     );
 
-    let api_key = &config.api.sportsdata_api_key;
+    let api_key = &state.config.api.sportsdata_api_key;
 
     let client = reqwest::Client::new();
 
@@ -1838,15 +2424,12 @@ async fn fetch_play_by_play_from_api(
         game_id
     );
 
-    let response = client
-        .get(api_url)
-        .query(&[("key", &api_key)])
-        .send()
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to make HTTP request: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    let response = metered(
+        &state.usage,
+        Upstream::SportsData,
+        client.get(api_url).query(&[("key", &api_key)]),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -1884,7 +2467,7 @@ async fn fetch_games_by_date_from_api(
     api_url: &str,
     league: &League,
     date: &str,
-    config: &ArbConfig,
+    state: &UseCaseState,
 ) -> Result<String> {
     let _parsed_url = Url::parse(api_url)?;
 
@@ -1896,21 +2479,18 @@ async fn fetch_games_by_date_from_api(
 
     match league {
         League::Mlb => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for games by date: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -1927,21 +2507,18 @@ async fn fetch_games_by_date_from_api(
             Ok(response_text)
         }
         League::Nfl => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for NFL games by date: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -1961,21 +2538,18 @@ async fn fetch_games_by_date_from_api(
             Ok(response_text)
         }
         League::Nba => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for NBA games by date: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -2006,7 +2580,7 @@ async fn fetch_scores_from_api(
     api_url: &str,
     league: &League,
     date: &str,
-    config: &ArbConfig,
+    state: &UseCaseState,
 ) -> Result<String> {
     let _parsed_url = Url::parse(api_url)?;
 
@@ -2018,21 +2592,18 @@ async fn fetch_scores_from_api(
 
     match league {
         League::Mlb => {
-            let api_key = &config.api.sportsdata_api_key;
+            let api_key = &state.config.api.sportsdata_api_key;
 
             let client = reqwest::Client::new();
 
             tracing::info!("Making real API request for scores: {}", api_url);
 
-            let response = client
-                .get(api_url)
-                .query(&[("key", &api_key)])
-                .send()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to make HTTP request: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+            let response = metered(
+                &state.usage,
+                Upstream::SportsData,
+                client.get(api_url).query(&[("key", &api_key)]),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -2166,7 +2737,7 @@ pub async fn handle_game_by_date_request(
     let use_cache = params.cache.unwrap_or(true);
 
     if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         if let Some(cached_data) = cache.get(&cache_key).await? {
             tracing::debug!(
                 "Returning cached game by date data for game_id: {}",
@@ -2181,13 +2752,9 @@ pub async fn handle_game_by_date_request(
         }
     }
 
-    let raw_data = fetch_games_by_date_from_api(
-        &api_url,
-        &league,
-        &params.date,
-        &use_case_state.config,
-    )
-    .await?;
+    let raw_data =
+        fetch_games_by_date_from_api(&api_url, &league, &params.date, &use_case_state)
+            .await?;
 
     let games: Vec<serde_json::Value> = serde_json::from_str(&raw_data).map_err(|e| {
         tracing::error!("Failed to parse games by date JSON: {}", e);
@@ -2373,7 +2940,7 @@ pub async fn handle_game_by_date_request(
     };
 
     {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         let serialized = serde_json::to_string(&response).map_err(|e| {
             tracing::error!("Failed to serialize response: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
@@ -2438,7 +3005,7 @@ pub async fn handle_box_score_request(
     let use_cache = params.cache.unwrap_or(true);
 
     if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         if let Some(cached_data) = cache.get(&cache_key).await? {
             tracing::debug!("Returning cached box score for {}: {}", id_param, id_value);
             let response: LeagueResponse =
@@ -2453,8 +3020,7 @@ pub async fn handle_box_score_request(
     }
 
     let api_url = box_score_path(league.clone(), id_value.clone()).to_string();
-    let raw_data =
-        fetch_box_score_from_api(&api_url, &league, &use_case_state.config).await?;
+    let raw_data = fetch_box_score_from_api(&api_url, &league, &use_case_state).await?;
 
     let response = match league {
         League::Mlb => {
@@ -2525,7 +3091,7 @@ pub async fn handle_box_score_request(
     };
 
     {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         let serialized = serde_json::to_string(&response).map_err(|e| {
             tracing::error!("Failed to serialize response: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
@@ -2550,9 +3116,9 @@ pub async fn handle_box_score_request(
 async fn fetch_box_score_from_api(
     api_url: &str,
     league: &League,
-    config: &ArbConfig,
+    state: &UseCaseState,
 ) -> Result<String> {
-    let api_key = &config.api.sportsdata_api_key;
+    let api_key = &state.config.api.sportsdata_api_key;
 
     let client = reqwest::Client::new();
 
@@ -2562,15 +3128,12 @@ async fn fetch_box_score_from_api(
         league
     );
 
-    let response = client
-        .get(api_url)
-        .query(&[("key", &api_key)])
-        .send()
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to make HTTP request: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    let response = metered(
+        &state.usage,
+        Upstream::SportsData,
+        client.get(api_url).query(&[("key", &api_key)]),
+    )
+    .await?;
 
     if !response.status().is_success() {
         tracing::error!("API request failed with status: {}", response.status());
@@ -2601,7 +3164,7 @@ pub async fn handle_stadiums_request(
     let use_cache = params.cache.unwrap_or(true);
 
     if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         if let Some(cached_data) = cache.get(&cache_key).await? {
             tracing::debug!("Returning cached stadiums data for league: {}", league);
             let response: LeagueResponse =
@@ -2615,8 +3178,7 @@ pub async fn handle_stadiums_request(
         tracing::info!("Cache bypass requested - fetching fresh data from API");
     }
 
-    let raw_data =
-        fetch_stadiums_from_api(&api_url, &league, &use_case_state.config).await?;
+    let raw_data = fetch_stadiums_from_api(&api_url, &league, &use_case_state).await?;
 
     let response = match league {
         League::Mlb => {
@@ -2690,7 +3252,7 @@ pub async fn handle_stadiums_request(
     };
 
     {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         let serialized = serde_json::to_string(&response).map_err(|e| {
             tracing::error!("Failed to serialize response: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
@@ -2728,7 +3290,7 @@ pub async fn handle_standings_request(
     let use_cache = params.cache.unwrap_or(true);
 
     if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         if let Some(cached_data) = cache.get(&cache_key).await? {
             tracing::debug!(
                 "Returning cached standings data for league: {} season: {}",
@@ -2746,8 +3308,7 @@ pub async fn handle_standings_request(
         tracing::info!("Cache bypass requested - fetching fresh data from API");
     }
 
-    let raw_data =
-        fetch_standings_from_api(&api_url, &league, &use_case_state.config).await?;
+    let raw_data = fetch_standings_from_api(&api_url, &league, &use_case_state).await?;
 
     let response = match league {
         League::Nfl => {
@@ -2805,7 +3366,7 @@ pub async fn handle_standings_request(
     };
 
     {
-        let mut cache = use_case_state.cache.lock().await;
+        let mut cache = use_case_state.lock_cache().await;
         let serialized = serde_json::to_string(&response).map_err(|e| {
             tracing::error!("Failed to serialize response: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
@@ -2830,19 +3391,16 @@ pub async fn handle_standings_request(
 async fn fetch_standings_from_api(
     api_url: &str,
     _league: &League,
-    config: &ArbConfig,
+    state: &UseCaseState,
 ) -> Result<String> {
-    let api_key = &config.api.sportsdata_api_key;
+    let api_key = &state.config.api.sportsdata_api_key;
 
     let client = reqwest::Client::new();
     let url = format!("{}?key={}", api_url, api_key);
 
     tracing::info!("Fetching standings data from URL: {}", url);
 
-    let response = client.get(&url).send().await.map_err(|e| {
-        tracing::error!("Failed to fetch standings from API: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let response = metered(&state.usage, Upstream::SportsData, client.get(&url)).await?;
 
     if !response.status().is_success() {
         tracing::error!("API request failed with status: {}", response.status());
@@ -2857,747 +3415,4295 @@ async fn fetch_standings_from_api(
     Ok(body)
 }
 
-async fn fetch_stadiums_from_api(
-    api_url: &str,
-    league: &League,
-    config: &ArbConfig,
-) -> Result<String> {
-    let api_key = &config.api.sportsdata_api_key;
+/// Standings analytics endpoint (games back, magic numbers, clinch flags, seeding)
+/// GET /api/v1/standings-analytics
+pub async fn handle_standings_analytics_request(
+    Query(params): Query<StandingsQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<StandingsAnalytics>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
 
-    let client = reqwest::Client::new();
+    if PlayoffFormat::for_league(&league).is_none() {
+        tracing::error!(
+            "Standings analytics not yet supported for league: {}",
+            league
+        );
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
 
-    tracing::info!(
-        "Making real API request to: {} for league: {}",
-        api_url,
-        league
-    );
+    let cache_key =
+        CacheKey::standings_analytics(use_case_state.provider(), &league, params.season);
+    let use_cache = params.cache.unwrap_or(true);
 
-    let response = client
-        .get(api_url)
-        .query(&[("key", &api_key)])
-        .send()
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to make HTTP request: {}", e);
+    if use_cache {
+        let mut cache = use_case_state.lock_cache().await;
+        if let Some(cached_data) = cache.get(&cache_key).await? {
+            tracing::debug!(
+                "Returning cached standings analytics for league: {} season: {}",
+                league,
+                params.season
+            );
+            let response: StandingsAnalytics = serde_json::from_str(&cached_data)
+                .map_err(|e| {
+                    tracing::error!("Failed to deserialize cached data: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            return Ok(Json(response));
+        }
+    } else {
+        tracing::info!("Cache bypass requested - computing fresh standings analytics");
+    }
+
+    let teams = load_standings_records(&use_case_state, &league, params.season).await?;
+
+    // The remaining schedule only exists for the configured regular season; past
+    // seasons fall back to games played vs. season length.
+    let is_current_season =
+        configured_season(&use_case_state.config, &league) == Some(params.season);
+    let schedule = if is_current_season {
+        load_schedule_games(&use_case_state, &league, use_cache).await?
+    } else {
+        Vec::new()
+    };
+
+    let response = analyze_standings(&league, params.season, teams, &schedule)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    {
+        let mut cache = use_case_state.lock_cache().await;
+        let serialized = serde_json::to_string(&response).map_err(|e| {
+            tracing::error!("Failed to serialize response: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        tracing::error!("API request failed with status: {}", status);
-        return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+        cache
+            .setx(
+                &cache_key,
+                &serialized,
+                use_case_state.config.cache.ttl.standings,
+            )
+            .await?;
     }
 
-    let body = response.text().await.map_err(|e| {
-        tracing::error!("Failed to read response body: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
-    tracing::info!("Successfully fetched stadiums data from API");
-    Ok(body)
+    tracing::info!(
+        "Computed standings analytics for league: {} season: {} ({} teams)",
+        league,
+        params.season,
+        response.teams.len()
+    );
+    Ok(Json(response))
 }
 
-#[derive(Debug, Deserialize)]
-pub struct TwitterSearchQuery {
-    pub query: String,
-    #[serde(default)]
-    pub cache: Option<bool>,
-}
+/// Monte Carlo playoff odds endpoint
+/// GET /api/v1/playoff-odds
+///
+/// Cached odds are reused until a new game goes final (tracked through today's
+/// scores), at which point standings and schedule are refetched and re-simulated.
+pub async fn handle_playoff_odds_request(
+    Query(params): Query<PlayoffOddsQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<PlayoffOdds>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
 
-pub async fn handle_twitter_search_request(
-    State(state): State<UseCaseState>,
-    Query(params): Query<TwitterSearchQuery>,
-) -> Result<Json<TwitterSearchResponse>> {
-    tracing::info!("Twitter search request for query: {}", params.query);
+    if PlayoffFormat::for_league(&league).is_none() {
+        tracing::error!("Playoff odds not yet supported for league: {}", league);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
 
-    let cache_key = format!("twitter_search:{}", params.query);
+    let simulation = &use_case_state.config.simulation;
+    let model = params.model.unwrap_or(simulation.model);
+    // Not a query parameter: each value would cost a simulation and a cache key
+    let iterations = simulation.iterations.clamp(1, MAX_ITERATIONS);
+
+    let cache_key = CacheKey::playoff_odds(
+        use_case_state.provider(),
+        &league,
+        params.season,
+        model.as_str(),
+        iterations,
+    );
     let use_cache = params.cache.unwrap_or(true);
 
+    let is_current_season =
+        configured_season(&use_case_state.config, &league) == Some(params.season);
+    let results_marker = if is_current_season {
+        final_games_marker(&use_case_state, &league).await
+    } else {
+        "final".to_string()
+    };
+
+    let mut results_changed = false;
     if use_cache {
-        if let Ok(Some(cached_data)) = state.cache.lock().await.get(&cache_key).await {
-            if let Ok(twitter_response) =
-                serde_json::from_str::<TwitterSearchResponse>(&cached_data)
-            {
-                tracing::debug!("Returning cached Twitter search result");
-                return Ok(Json(twitter_response));
+        let mut cache = use_case_state.lock_cache().await;
+        if let Some(cached_data) = cache.get(&cache_key).await? {
+            let cached: CachedPlayoffOdds =
+                serde_json::from_str(&cached_data).map_err(|e| {
+                    tracing::error!("Failed to deserialize cached data: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            if cached.results_marker == results_marker {
+                tracing::debug!(
+                    "Returning cached playoff odds for league: {} season: {}",
+                    league,
+                    params.season
+                );
+                return Ok(Json(cached.odds));
             }
+            tracing::info!(
+                "New final results for league: {} ({} -> {}), re-simulating playoff odds",
+                league,
+                cached.results_marker,
+                results_marker
+            );
+            results_changed = true;
         }
     } else {
-        tracing::info!("Cache bypass requested - fetching fresh data from Twitter API");
+        tracing::info!("Cache bypass requested - simulating fresh playoff odds");
     }
 
-    let api_key = std::env::var("TWITTERAPIIO_API_KEY").map_err(|_| {
-        tracing::error!("TWITTERAPIIO_API_KEY environment variable not set");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
-    let url = format!(
-        "https://api.twitterapi.io/twitter/tweet/advanced_search?query={}",
-        urlencoding::encode(&params.query)
-    );
+    let teams = load_standings_records(&use_case_state, &league, params.season).await?;
+    let schedule = if is_current_season {
+        load_schedule_games(&use_case_state, &league, use_cache && !results_changed)
+            .await?
+    } else {
+        Vec::new()
+    };
 
-    tracing::info!("Making request to Twitter API: {}", url);
+    let season = params.season;
+    let simulated_league = league.clone();
+    let odds = tokio::task::spawn_blocking(move || {
+        let mut rng = rand::thread_rng();
+        simulate_playoff_odds(
+            &simulated_league,
+            season,
+            model,
+            iterations,
+            teams,
+            &schedule,
+            &mut rng,
+        )
+    })
+    .await
+    .map_err(|e| {
+        tracing::error!("Playoff odds simulation task failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::BAD_REQUEST)?;
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
+    {
+        let mut cache = use_case_state.lock_cache().await;
+        let serialized = serde_json::to_string(&CachedPlayoffOdds {
+            results_marker,
+            odds: odds.clone(),
+        })
         .map_err(|e| {
-            tracing::error!("Failed to create HTTP client: {}", e);
+            tracing::error!("Failed to serialize response: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
+        cache
+            .setx(
+                &cache_key,
+                &serialized,
+                use_case_state.config.cache.ttl.playoff_odds,
+            )
+            .await?;
+    }
 
-    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-
-    let response = client
-        .get(&url)
-        .header("X-API-Key", &api_key)
-        .header("Content-Type", "application/json")
-        .header("User-Agent", "arbitration-app/1.0")
-        .send()
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to send request to Twitter API: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    tracing::info!(
+        "Simulated playoff odds for league: {} season: {} ({} iterations, {} games remaining)",
+        league,
+        params.season,
+        odds.iterations,
+        odds.games_remaining
+    );
+    Ok(Json(odds))
+}
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to read error body".to_string());
-        tracing::error!(
-            "Twitter API returned error status: {} - Body: {}",
-            status,
-            error_body
-        );
+/// Marker that changes whenever one of today's games goes final
+///
+/// Reads today's scores through the short-lived scores cache entry so the
+/// check is cheap; errors fall back to a date-only marker. Game days are US
+/// Eastern upstream, so evening games still count toward the day they started.
+async fn final_games_marker(use_case_state: &UseCaseState, league: &League) -> String {
+    let date = chrono::Utc::now()
+        .with_timezone(&chrono_tz::America::New_York)
+        .format("%Y-%m-%d")
+        .to_string();
+    let cache_key = CacheKey::scores(use_case_state.provider(), league, &date);
+
+    let cached_data = {
+        let mut cache = use_case_state.lock_cache().await;
+        cache.get(&cache_key).await.ok().flatten()
+    };
 
-        match status {
-            StatusCode::TOO_MANY_REQUESTS => {
-                tracing::warn!("Twitter API rate limit exceeded");
-                return Err(StatusCode::TOO_MANY_REQUESTS.into());
-            }
-            StatusCode::UNAUTHORIZED => {
-                tracing::error!("Twitter API authentication failed - check API key");
-                return Err(StatusCode::UNAUTHORIZED.into());
-            }
-            StatusCode::BAD_REQUEST => {
+    let data = match cached_data {
+        Some(data) => data,
+        None => {
+            let api_url =
+                games_by_date_path(league.clone(), Some(date.clone())).to_string();
+            match fetch_games_by_date_from_api(&api_url, league, &date, use_case_state)
+                .await
+            {
+                Ok(fetched_data) => {
+                    let mut cache = use_case_state.lock_cache().await;
+                    if let Err(e) = cache
+                        .setx(
+                            &cache_key,
+                            &fetched_data,
+                            use_case_state.config.cache.ttl.scores,
+                        )
+                        .await
+                    {
+                        tracing::error!(
+                            "Failed to cache scores data for date {}: {}",
+                            date,
+                            e
+                        );
+                    }
+                    fetched_data
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to fetch today's scores for {}: {}",
+                        league,
+                        e
+                    );
+                    return date;
+                }
+            }
+        }
+    };
+
+    let finals = serde_json::from_str::<Vec<serde_json::Value>>(&data)
+        .map(|games| {
+            parse_schedule(&games)
+                .iter()
+                .filter(|game| game.is_final)
+                .count()
+        })
+        .unwrap_or(0);
+    format!("{}:{}", date, finals)
+}
+
+/// Current Elo power ratings endpoint
+/// GET /api/v1/ratings
+pub async fn handle_ratings_request(
+    Query(params): Query<RatingsQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<RatingsResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let (book, _) =
+        load_ratings_book(&use_case_state, &league, params.cache.unwrap_or(true)).await?;
+
+    Ok(Json(RatingsResponse {
+        league: book.league.clone(),
+        season: book.season,
+        games_processed: book.games_processed(),
+        updated_at: book.updated_at.clone(),
+        ratings: book.leaderboard(),
+    }))
+}
+
+/// Rating history for a single team
+/// GET /api/v1/ratings/history
+pub async fn handle_rating_history_request(
+    Query(params): Query<RatingsQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<RatingHistoryResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+    let team = params
+        .team
+        .as_deref()
+        .map(str::to_uppercase)
+        .ok_or_else(|| {
+            tracing::error!("Rating history requested without a team");
+            StatusCode::BAD_REQUEST
+        })?;
+
+    let (book, _) =
+        load_ratings_book(&use_case_state, &league, params.cache.unwrap_or(true)).await?;
+
+    Ok(Json(RatingHistoryResponse {
+        league: book.league.clone(),
+        season: book.season,
+        rating: book.ratings.get(&team).cloned(),
+        history: book.history(&team),
+        team,
+    }))
+}
+
+/// Pre-game win probabilities from current ratings, for a date or a single matchup
+/// GET /api/v1/win-probability
+pub async fn handle_win_probability_request(
+    Query(params): Query<WinProbabilityQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<WinProbabilityResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+    let use_cache = params.cache.unwrap_or(true);
+
+    let (book, elo_params) =
+        load_ratings_book(&use_case_state, &league, use_cache).await?;
+
+    let predictions = match (&params.home, &params.away, &params.date) {
+        (Some(home), Some(away), _) => {
+            vec![book.predict(&home.to_uppercase(), &away.to_uppercase(), elo_params)]
+        }
+        (_, _, Some(date)) => {
+            let api_url =
+                games_by_date_path(league.clone(), Some(date.clone())).to_string();
+            let cache_key = CacheKey::scores(use_case_state.provider(), &league, date);
+            let ttl = use_case_state.config.cache.ttl.scores;
+            let data = cached_fetch(&use_case_state, &cache_key, ttl, use_cache, || {
+                fetch_games_by_date_from_api(&api_url, &league, date, &use_case_state)
+            })
+            .await?;
+            let games: Vec<serde_json::Value> =
+                serde_json::from_str(&data).map_err(|e| {
+                    tracing::error!("Failed to parse games by date JSON: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+
+            parse_schedule(&games)
+                .iter()
+                .map(|game| GamePrediction {
+                    game_id: Some(game.game_id.clone()),
+                    ..book.predict(&game.home, &game.away, elo_params)
+                })
+                .collect()
+        }
+        _ => {
+            tracing::error!("Win probability requires a date or a home/away matchup");
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+    };
+
+    Ok(Json(WinProbabilityResponse {
+        league: league.to_string(),
+        date: params.date,
+        predictions,
+    }))
+}
+
+/// Load the league's rolling ratings, applying any finals not yet counted
+///
+/// A missing book is rebuilt from the season schedule, seeded from last season's
+/// ratings when those are still stored. Yesterday's and today's final scores are
+/// then checked so new finals land without waiting for the schedule cache.
+async fn load_ratings_book(
+    use_case_state: &UseCaseState,
+    league: &League,
+    use_cache: bool,
+) -> Result<(RatingsBook, EloParams)> {
+    let params = EloParams::for_league(league).ok_or_else(|| {
+        tracing::error!("Ratings not yet supported for league: {}", league);
+        StatusCode::BAD_REQUEST
+    })?;
+    let season = configured_season(&use_case_state.config, league).ok_or_else(|| {
+        tracing::error!("No configured season for league: {}", league);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let provider = use_case_state.provider();
+    let cache_key = CacheKey::ratings(provider, league, season);
+
+    let stored = if use_cache {
+        load_ratings_from_cache(use_case_state, &cache_key).await
+    } else {
+        None
+    };
+
+    let mut changed = stored.is_none();
+    let mut book = match stored {
+        Some(book) => book,
+        None => {
+            let previous_key = CacheKey::ratings(provider, league, season - 1);
+            let mut book =
+                match load_ratings_from_cache(use_case_state, &previous_key).await {
+                    Some(previous) => RatingsBook::carry_over(&previous, season, params),
+                    None => RatingsBook::new(league, season),
+                };
+            let schedule = load_schedule_games(use_case_state, league, use_cache).await?;
+            let applied = book.apply_all(&schedule, params);
+            tracing::info!(
+                "Rebuilt {} ratings for season {} from {} finals",
+                league,
+                season,
+                applied
+            );
+            book
+        }
+    };
+
+    let today = chrono::Utc::now().date_naive();
+    for date in [today - chrono::Duration::days(1), today] {
+        let date = date.format("%Y-%m-%d").to_string();
+        let api_url =
+            scores_basic_final_path(league.clone(), Some(date.clone())).to_string();
+        let final_key = CacheKey::final_scores(provider, league, &date);
+        let ttl = use_case_state.config.cache.ttl.scores;
+        let fetched = cached_fetch(use_case_state, &final_key, ttl, true, || {
+            fetch_games_by_date_from_api(&api_url, league, &date, use_case_state)
+        })
+        .await;
+
+        match fetched.map(|data| serde_json::from_str::<Vec<serde_json::Value>>(&data)) {
+            Ok(Ok(games)) => {
+                let applied = book.apply_all(&parse_schedule(&games), params);
+                if applied > 0 {
+                    tracing::info!(
+                        "Applied {} new {} finals from {}",
+                        applied,
+                        league,
+                        date
+                    );
+                    changed = true;
+                }
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("Failed to parse final scores for {}: {}", date, e)
+            }
+            Err(e) => tracing::warn!("Failed to fetch final scores for {}: {}", date, e),
+        }
+    }
+
+    if changed {
+        let serialized = serde_json::to_string(&book).map_err(|e| {
+            tracing::error!("Failed to serialize ratings: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        let mut cache = use_case_state.lock_cache().await;
+        if let Err(e) = cache
+            .setx(
+                &cache_key,
+                &serialized,
+                use_case_state.config.cache.ttl.ratings,
+            )
+            .await
+        {
+            tracing::error!("Failed to store ratings: {}", e);
+        }
+    }
+
+    Ok((book, params))
+}
+
+async fn load_ratings_from_cache(
+    use_case_state: &UseCaseState,
+    cache_key: &CacheKey,
+) -> Option<RatingsBook> {
+    let mut cache = use_case_state.lock_cache().await;
+    let data = cache.get(cache_key).await.ok().flatten()?;
+    serde_json::from_str(&data)
+        .map_err(|e| tracing::error!("Failed to deserialize stored ratings: {}", e))
+        .ok()
+}
+
+/// Read a raw upstream payload through the cache, fetching and storing it on a miss
+async fn cached_fetch<F, Fut>(
+    use_case_state: &UseCaseState,
+    cache_key: &CacheKey,
+    ttl: u64,
+    use_cache: bool,
+    fetch: F,
+) -> Result<String>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<String>>,
+{
+    if use_cache {
+        let mut cache = use_case_state.lock_cache().await;
+        if let Ok(Some(data)) = cache.get(cache_key).await {
+            return Ok(data);
+        }
+    }
+
+    let data = fetch().await?;
+
+    let mut cache = use_case_state.lock_cache().await;
+    if let Err(e) = cache.setx(cache_key, &data, ttl).await {
+        tracing::error!("Failed to cache {}: {}", cache_key.as_ref(), e);
+    }
+    Ok(data)
+}
+
+/// Regular season configured for a league, if it parses as a year
+fn configured_season(config: &ArbConfig, league: &League) -> Option<i32> {
+    config
+        .get_season_info(&league.to_string())
+        .and_then(|season_info| season_info.regular.parse::<i32>().ok())
+}
+
+/// Season-to-date player or team stats
+/// GET /api/v1/season-stats
+pub async fn handle_season_stats_request(
+    Query(params): Query<SeasonStatsQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<SeasonStatsResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+    let season = stats_season(&use_case_state.config, &league, params.season)?;
+
+    let filter = LeaderboardFilter {
+        position: params.position,
+        team: params.team,
+        min_games: params.min_games,
+        ..Default::default()
+    };
+    let lines: Vec<StatLine> = load_season_stats(
+        &use_case_state,
+        &league,
+        &season,
+        params.kind,
+        params.cache.unwrap_or(true),
+    )
+    .await?
+    .into_iter()
+    .filter(|line| filter.matches(line))
+    .collect();
+
+    Ok(Json(SeasonStatsResponse {
+        league: league.to_string(),
+        season,
+        kind: params.kind,
+        count: lines.len(),
+        lines,
+    }))
+}
+
+/// League leaderboard for any stat the provider reports
+/// GET /api/v1/leaderboard
+pub async fn handle_leaderboard_request(
+    Query(params): Query<LeaderboardQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<LeaderboardResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+    let season = stats_season(&use_case_state.config, &league, params.season)?;
+
+    let lines = load_season_stats(
+        &use_case_state,
+        &league,
+        &season,
+        params.kind,
+        params.cache.unwrap_or(true),
+    )
+    .await?;
+
+    let filter = LeaderboardFilter {
+        position: params.position,
+        team: params.team,
+        min_games: params.min_games,
+        order: params.order,
+        limit: params.limit,
+    };
+    let leaders = rank_leaderboard(&lines, &params.stat, &filter).ok_or_else(|| {
+        tracing::error!(
+            "Unknown {} stat for {}: {}",
+            params.kind.as_str(),
+            league,
+            params.stat
+        );
+        StatusCode::BAD_REQUEST
+    })?;
+
+    Ok(Json(LeaderboardResponse {
+        league: league.to_string(),
+        season,
+        kind: params.kind,
+        stat: params.stat,
+        count: leaders.len(),
+        leaders,
+    }))
+}
+
+fn stats_season(
+    config: &ArbConfig,
+    league: &League,
+    season: Option<String>,
+) -> Result<String> {
+    season
+        .or_else(|| {
+            config
+                .get_season_info(&league.to_string())
+                .map(|season_info| season_info.regular.clone())
+        })
+        .ok_or_else(|| {
+            tracing::error!("No season given or configured for league: {}", league);
+            StatusCode::BAD_REQUEST.into()
+        })
+}
+
+/// Fetch and parse a season's player or team stats from the provider
+async fn load_season_stats(
+    use_case_state: &UseCaseState,
+    league: &League,
+    season: &str,
+    kind: StatKind,
+    use_cache: bool,
+) -> Result<Vec<StatLine>> {
+    if !matches!(league, League::Mlb | League::Nfl | League::Nba) {
+        tracing::error!("Season stats not yet supported for league: {}", league);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let api_url = match kind {
+        StatKind::Player => {
+            player_season_stats_path(league.clone(), season.to_string()).to_string()
+        }
+        StatKind::Team => {
+            team_season_stats_path(league.clone(), season.to_string()).to_string()
+        }
+    };
+    let cache_key =
+        CacheKey::season_stats(use_case_state.provider(), league, season, kind.as_str());
+
+    let data = cached_fetch(
+        use_case_state,
+        &cache_key,
+        use_case_state.config.cache.ttl.season_stats,
+        use_cache,
+        || fetch_from_sportsdata_api(&api_url, use_case_state),
+    )
+    .await?;
+
+    parse_season_stats(&data, kind)
+}
+
+fn parse_season_stats(data: &str, kind: StatKind) -> Result<Vec<StatLine>> {
+    let rows: Vec<serde_json::Value> = serde_json::from_str(data).map_err(|e| {
+        tracing::error!("Failed to parse {} season stats JSON: {}", kind.as_str(), e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(parse_stat_lines(&rows, kind))
+}
+
+/// GET a SportsData endpoint with the configured API key and return the raw body
+async fn fetch_from_sportsdata_api(
+    api_url: &str,
+    state: &UseCaseState,
+) -> Result<String> {
+    let _parsed_url = Url::parse(api_url)?;
+
+    tracing::info!("Fetching data from API: {}", api_url);
+
+    let client = reqwest::Client::new();
+    let response = metered(
+        &state.usage,
+        Upstream::SportsData,
+        client
+            .get(api_url)
+            .query(&[("key", &state.config.api.sportsdata_api_key)]),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        tracing::error!("API request failed with status: {}", response.status());
+        return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+    }
+
+    let body = response.text().await.map_err(|e| {
+        tracing::error!("Failed to read response body: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(body)
+}
+
+/// Player profile joined with the league headshot
+/// GET /api/v1/player
+pub async fn handle_player_profile_request(
+    Query(params): Query<PlayerQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<PlayerProfileResponse>> {
+    let league = sportsdata_league(&params.league, "Player endpoints")?;
+    let use_cache = params.cache.unwrap_or(true);
+
+    let player =
+        load_player(&use_case_state, &league, params.player_id, use_cache).await?;
+    let headshot_url = player_headshot(&use_case_state, &league, params.player_id)
+        .await
+        .or_else(|| player.photo_url.clone());
+
+    Ok(Json(PlayerProfileResponse {
+        league: league.to_string(),
+        player_id: params.player_id,
+        name: player.full_name(),
+        headshot_url,
+        player,
+    }))
+}
+
+/// Per-game log for one season
+/// GET /api/v1/player/game-log
+pub async fn handle_player_game_log_request(
+    Query(params): Query<PlayerQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<PlayerGameLogResponse>> {
+    let league = sportsdata_league(&params.league, "Player endpoints")?;
+    let season = stats_season(&use_case_state.config, &league, params.season)?;
+
+    let games = load_player_game_log(
+        &use_case_state,
+        &league,
+        &season,
+        params.player_id,
+        params.games,
+        params.cache.unwrap_or(true),
+    )
+    .await?;
+    let games = game_log(&games);
+
+    Ok(Json(PlayerGameLogResponse {
+        league: league.to_string(),
+        player_id: params.player_id,
+        season,
+        count: games.len(),
+        games,
+    }))
+}
+
+/// Home/away and month splits for one season
+/// GET /api/v1/player/splits
+pub async fn handle_player_splits_request(
+    Query(params): Query<PlayerQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<PlayerSplitsResponse>> {
+    let league = sportsdata_league(&params.league, "Player endpoints")?;
+    let season = stats_season(&use_case_state.config, &league, params.season)?;
+
+    let games = load_player_game_log(
+        &use_case_state,
+        &league,
+        &season,
+        params.player_id,
+        None,
+        params.cache.unwrap_or(true),
+    )
+    .await?;
+    let games = game_log(&games);
+
+    Ok(Json(PlayerSplitsResponse {
+        league: league.to_string(),
+        player_id: params.player_id,
+        season,
+        games: games.len(),
+        splits: splits(&games),
+    }))
+}
+
+/// Season-by-season and career totals
+/// GET /api/v1/player/career
+pub async fn handle_player_career_request(
+    Query(params): Query<PlayerQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<PlayerCareerResponse>> {
+    let league = sportsdata_league(&params.league, "Player endpoints")?;
+    let use_cache = params.cache.unwrap_or(true);
+    let current_season =
+        configured_season(&use_case_state.config, &league).ok_or_else(|| {
+            tracing::error!("No configured season for league: {}", league);
+            StatusCode::BAD_REQUEST
+        })?;
+
+    let player =
+        load_player(&use_case_state, &league, params.player_id, use_cache).await?;
+
+    let first_season = career_start(&player, current_season);
+    let seasons: Vec<(String, StatLine)> =
+        futures_util::stream::iter(first_season..=current_season)
+            .map(|season| {
+                load_career_season(
+                    &use_case_state,
+                    &league,
+                    params.player_id,
+                    season,
+                    season < current_season,
+                    use_cache,
+                )
+            })
+            .buffer_unordered(CAREER_SEASON_FETCHES)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+
+    Ok(Json(PlayerCareerResponse {
+        league: league.to_string(),
+        player_id: params.player_id,
+        name: player.full_name(),
+        career: career_totals(seasons),
+    }))
+}
+
+/// One season of a player's stats, one line per team they played for
+///
+/// Finished seasons don't change, so they're cached for `career_seasons` and
+/// always read from the cache first.
+async fn load_career_season(
+    use_case_state: &UseCaseState,
+    league: &League,
+    player_id: i32,
+    season: i32,
+    finished: bool,
+    use_cache: bool,
+) -> Vec<(String, StatLine)> {
+    let season = season.to_string();
+    let api_url =
+        player_season_stats_by_player_path(league.clone(), season.clone(), player_id)
+            .to_string();
+    let cache_key = CacheKey::player(
+        use_case_state.provider(),
+        league,
+        player_id,
+        &format!("season:{}", season),
+    );
+    let ttl = if finished {
+        use_case_state.config.cache.ttl.career_seasons
+    } else {
+        use_case_state.config.cache.ttl.players
+    };
+    let data = match cached_fetch(
+        use_case_state,
+        &cache_key,
+        ttl,
+        use_cache || finished,
+        || fetch_from_sportsdata_api(&api_url, use_case_state),
+    )
+    .await
+    {
+        Ok(data) => data,
+        Err(e) => {
+            tracing::warn!(
+                "No {} season stats for player {} in {}: {}",
+                league,
+                player_id,
+                season,
+                e
+            );
+            return Vec::new();
+        }
+    };
+
+    // A single season comes back as one object, or one row per team for traded players
+    let rows = match serde_json::from_str::<serde_json::Value>(&data) {
+        Ok(serde_json::Value::Array(rows)) => rows,
+        Ok(row @ serde_json::Value::Object(_)) => vec![row],
+        _ => return Vec::new(),
+    };
+    parse_stat_lines(&rows, StatKind::Player)
+        .into_iter()
+        .map(|line| (season.clone(), line))
+        .collect()
+}
+
+/// Parse a league served by the SportsData MLB, NFL and NBA feeds
+fn sportsdata_league(league: &str, feature: &str) -> Result<League> {
+    let league = league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", league);
+        StatusCode::BAD_REQUEST
+    })?;
+    if !matches!(league, League::Mlb | League::Nfl | League::Nba) {
+        tracing::error!("{} not yet supported for league: {}", feature, league);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+    Ok(league)
+}
+
+/// Uppercase team abbreviation (e.g. "NYY"), checked before it goes into an
+/// upstream path
+fn team_abbreviation(team: &str) -> Result<String> {
+    let team = team.trim();
+    if !(2..=4).contains(&team.len()) || !team.chars().all(|c| c.is_ascii_alphabetic()) {
+        tracing::error!("Invalid team: {}", team);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+    Ok(team.to_uppercase())
+}
+
+async fn load_player(
+    use_case_state: &UseCaseState,
+    league: &League,
+    player_id: i32,
+    use_cache: bool,
+) -> Result<SportsDataPlayer> {
+    let api_url = player_path(league.clone(), player_id).to_string();
+    let cache_key =
+        CacheKey::player(use_case_state.provider(), league, player_id, "profile");
+
+    let data = cached_fetch(
+        use_case_state,
+        &cache_key,
+        use_case_state.config.cache.ttl.players,
+        use_cache,
+        || fetch_from_sportsdata_api(&api_url, use_case_state),
+    )
+    .await?;
+
+    // Unknown players come back as an empty body or `null`
+    serde_json::from_str::<Option<SportsDataPlayer>>(&data)
+        .map_err(|e| {
+            tracing::error!("Failed to parse {} player JSON: {}", league, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or_else(|| {
+            tracing::error!("Player not found: {} {}", league, player_id);
+            StatusCode::NOT_FOUND.into()
+        })
+}
+
+async fn load_player_game_log(
+    use_case_state: &UseCaseState,
+    league: &League,
+    season: &str,
+    player_id: i32,
+    games: Option<u32>,
+    use_cache: bool,
+) -> Result<Vec<PlayerGameStat>> {
+    let api_url =
+        player_game_log_path(league.clone(), season.to_string(), player_id, games)
+            .to_string();
+    let resource = match games {
+        Some(games) => format!("game_log:{}:{}", season, games),
+        None => format!("game_log:{}", season),
+    };
+    let cache_key =
+        CacheKey::player(use_case_state.provider(), league, player_id, &resource);
+
+    let data = cached_fetch(
+        use_case_state,
+        &cache_key,
+        use_case_state.config.cache.ttl.players,
+        use_cache,
+        || fetch_from_sportsdata_api(&api_url, use_case_state),
+    )
+    .await?;
+
+    serde_json::from_str::<Option<Vec<PlayerGameStat>>>(&data)
+        .map(Option::unwrap_or_default)
+        .map_err(|e| {
+            tracing::error!("Failed to parse {} game log JSON: {}", league, e);
+            StatusCode::INTERNAL_SERVER_ERROR.into()
+        })
+}
+
+/// Hosted headshot for a player, read through the `/api/v1/headshots` cache entry
+async fn player_headshot(
+    use_case_state: &UseCaseState,
+    league: &League,
+    player_id: i32,
+) -> Option<String> {
+    let api_url = headshots_path(league.clone()).to_string();
+    let cache_key =
+        CacheKey::data_type(use_case_state.provider(), &DataType::Headshots, league);
+
+    let data = cached_fetch(
+        use_case_state,
+        &cache_key,
+        use_case_state.config.cache.ttl.team_profiles,
+        true,
+        || fetch_from_sportsdata_api(&api_url, use_case_state),
+    )
+    .await
+    .map_err(|e| tracing::warn!("Failed to load {} headshots: {}", league, e))
+    .ok()?;
+
+    let headshots: Vec<serde_json::Value> = serde_json::from_str(&data).ok()?;
+    headshots
+        .iter()
+        .find(|headshot| {
+            headshot.get("PlayerID").and_then(|id| id.as_i64()) == Some(player_id as i64)
+        })
+        .and_then(|headshot| {
+            [
+                "PreferredHostedHeadshotUrl",
+                "HostedHeadshotWithBackgroundUrl",
+            ]
+            .iter()
+            .find_map(|key| headshot.get(*key)?.as_str().map(String::from))
+        })
+}
+
+/// Injury report with starter flags and ruled-out starter alerts
+/// GET /api/v1/injuries
+pub async fn handle_injuries_request(
+    Query(params): Query<InjuriesQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<InjuriesResponse>> {
+    let league = sportsdata_league(&params.league, "Injuries and lineups")?;
+    let use_cache = params.cache.unwrap_or(true);
+    let cache_key = CacheKey::injuries(use_case_state.provider(), &league);
+
+    let cached_data = if use_cache {
+        let mut cache = use_case_state.lock_cache().await;
+        cache.get(&cache_key).await.ok().flatten()
+    } else {
+        None
+    };
+
+    let (data, fresh) = match cached_data {
+        Some(data) => (data, false),
+        None => {
+            let api_url = injured_players_path(league.clone()).to_string();
+            let fetched_data =
+                fetch_from_sportsdata_api(&api_url, &use_case_state).await?;
+
+            let mut cache = use_case_state.lock_cache().await;
+            if let Err(e) = cache
+                .setx(
+                    &cache_key,
+                    &fetched_data,
+                    use_case_state.config.cache.ttl.injuries,
+                )
+                .await
+            {
+                tracing::error!("Failed to cache injuries data: {}", e);
+            }
+            (fetched_data, true)
+        }
+    };
+
+    let injuries: Vec<InjuredPlayer> = serde_json::from_str(&data).map_err(|e| {
+        tracing::error!("Failed to parse {} injuries JSON: {}", league, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let starters = load_starters(&use_case_state, &league).await;
+
+    // Only a freshly fetched report can contain changes since the last snapshot
+    let starter_alerts = if fresh {
+        record_starter_alerts(&use_case_state, &league, &injuries, &starters).await
+    } else {
+        load_starter_alerts(&use_case_state, &league).await
+    };
+
+    let team_matches = |team: &Option<String>| {
+        params.team.as_ref().is_none_or(|wanted| {
+            team.as_ref()
+                .is_some_and(|team| team.eq_ignore_ascii_case(wanted))
+        })
+    };
+    let injuries: Vec<InjuryReportEntry> = annotate(injuries, &starters)
+        .into_iter()
+        .filter(|entry| team_matches(&entry.player.team))
+        .collect();
+
+    Ok(Json(InjuriesResponse {
+        league: league.to_string(),
+        count: injuries.len(),
+        injuries,
+        starter_alerts: starter_alerts
+            .into_iter()
+            .filter(|alert| team_matches(&alert.team))
+            .collect(),
+    }))
+}
+
+/// Team depth charts
+/// GET /api/v1/depth-charts
+pub async fn handle_depth_charts_request(
+    Query(params): Query<DepthChartsQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<DepthChartsResponse>> {
+    let league = sportsdata_league(&params.league, "Injuries and lineups")?;
+
+    let teams: Vec<TeamDepthChart> =
+        load_depth_charts(&use_case_state, &league, params.cache.unwrap_or(true))
+            .await?
+            .into_iter()
+            .filter(|chart| {
+                params
+                    .team_id
+                    .is_none_or(|team_id| chart.team_id == team_id)
+            })
+            .collect();
+
+    Ok(Json(DepthChartsResponse {
+        league: league.to_string(),
+        count: teams.len(),
+        teams,
+    }))
+}
+
+/// Projected and confirmed starting lineups for a date (MLB, NBA)
+/// GET /api/v1/lineups
+pub async fn handle_lineups_request(
+    Query(params): Query<LineupsQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<LineupsResponse>> {
+    let league = sportsdata_league(&params.league, "Injuries and lineups")?;
+
+    let games = load_lineups(
+        &use_case_state,
+        &league,
+        &params.date,
+        params.cache.unwrap_or(true),
+    )
+    .await?;
+
+    Ok(Json(LineupsResponse {
+        league: league.to_string(),
+        date: params.date,
+        games,
+    }))
+}
+
+/// MLB probable pitchers for a date, read from the starting lineups feed
+/// GET /api/v1/probable-pitchers
+pub async fn handle_probable_pitchers_request(
+    Query(params): Query<ProbablePitchersQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<ProbablePitchersResponse>> {
+    let games = match load_lineups(
+        &use_case_state,
+        &League::Mlb,
+        &params.date,
+        params.cache.unwrap_or(true),
+    )
+    .await?
+    {
+        StartingLineups::Mlb(games) => games,
+        StartingLineups::Nba(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR.into()),
+    };
+
+    Ok(Json(ProbablePitchersResponse {
+        date: params.date,
+        games: games
+            .into_iter()
+            .map(|game| ProbablePitchers {
+                game_id: game.game_id,
+                home_team: game.home_team,
+                away_team: game.away_team,
+                home_pitcher: game.home_starting_pitcher,
+                away_pitcher: game.away_starting_pitcher,
+            })
+            .collect(),
+    }))
+}
+
+async fn load_depth_charts(
+    use_case_state: &UseCaseState,
+    league: &League,
+    use_cache: bool,
+) -> Result<Vec<TeamDepthChart>> {
+    let api_url = depth_charts_path(league.clone()).to_string();
+    let cache_key = CacheKey::depth_charts(use_case_state.provider(), league);
+
+    let data = cached_fetch(
+        use_case_state,
+        &cache_key,
+        use_case_state.config.cache.ttl.depth_charts,
+        use_cache,
+        || fetch_from_sportsdata_api(&api_url, use_case_state),
+    )
+    .await?;
+
+    serde_json::from_str(&data).map_err(|e| {
+        tracing::error!("Failed to parse {} depth charts JSON: {}", league, e);
+        StatusCode::INTERNAL_SERVER_ERROR.into()
+    })
+}
+
+async fn load_lineups(
+    use_case_state: &UseCaseState,
+    league: &League,
+    date: &str,
+    use_cache: bool,
+) -> Result<StartingLineups> {
+    if *league == League::Nfl {
+        tracing::error!("Starting lineups not available for NFL; use depth charts");
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let api_url = starting_lineups_path(league.clone(), date.to_string()).to_string();
+    let cache_key = CacheKey::lineups(use_case_state.provider(), league, date);
+
+    let data = cached_fetch(
+        use_case_state,
+        &cache_key,
+        use_case_state.config.cache.ttl.lineups,
+        use_cache,
+        || fetch_from_sportsdata_api(&api_url, use_case_state),
+    )
+    .await?;
+
+    let games = match league {
+        League::Mlb => serde_json::from_str(&data).map(StartingLineups::Mlb),
+        _ => serde_json::from_str(&data).map(StartingLineups::Nba),
+    };
+    games.map_err(|e| {
+        tracing::error!("Failed to parse {} lineups JSON: {}", league, e);
+        StatusCode::INTERNAL_SERVER_ERROR.into()
+    })
+}
+
+/// Starters from depth charts plus anyone in today's starting lineups
+///
+/// Best effort: a feed that fails to load just contributes no starters.
+async fn load_starters(use_case_state: &UseCaseState, league: &League) -> HashSet<i32> {
+    let mut starters = match load_depth_charts(use_case_state, league, true).await {
+        Ok(charts) => depth_chart_starters(&charts),
+        Err(e) => {
+            tracing::warn!("Failed to load {} depth charts: {}", league, e);
+            HashSet::new()
+        }
+    };
+
+    if *league != League::Nfl {
+        // Lineups are posted per US Eastern game day
+        let today = chrono::Utc::now()
+            .with_timezone(&chrono_tz::America::New_York)
+            .format("%Y-%m-%d")
+            .to_string();
+        match load_lineups(use_case_state, league, &today, true).await {
+            Ok(StartingLineups::Mlb(games)) => {
+                starters.extend(games.iter().flat_map(|game| {
+                    game.home_batting_lineup
+                        .iter()
+                        .chain(&game.away_batting_lineup)
+                        .chain(&game.home_starting_pitcher)
+                        .chain(&game.away_starting_pitcher)
+                        .map(|player| player.player_id)
+                }))
+            }
+            Ok(StartingLineups::Nba(games)) => {
+                starters.extend(games.iter().flat_map(|game| {
+                    game.home_lineup
+                        .iter()
+                        .chain(&game.away_lineup)
+                        .filter(|player| player.starting.unwrap_or(true))
+                        .map(|player| player.player_id)
+                }))
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load {} lineups for {}: {}", league, today, e)
+            }
+        }
+    }
+
+    starters
+}
+
+/// Diff a fresh injury report against the stored snapshot and persist any new alerts
+async fn record_starter_alerts(
+    use_case_state: &UseCaseState,
+    league: &League,
+    injuries: &[InjuredPlayer],
+    starters: &HashSet<i32>,
+) -> Vec<StarterAlert> {
+    let provider = use_case_state.provider();
+    let snapshot_key = CacheKey::injury_snapshot(provider, league);
+    let alerts_key = CacheKey::starter_alerts(provider, league);
+    let ttl = use_case_state.config.cache.ttl.starter_alerts;
+
+    let previous: InjurySnapshot = {
+        let mut cache = use_case_state.lock_cache().await;
+        cache
+            .get(&snapshot_key)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    };
+
+    let new_alerts = detect_ruled_out_starters(&previous, injuries, starters);
+    for alert in &new_alerts {
+        tracing::warn!(
+            "{} starter ruled out: {} ({}) {} -> {}",
+            league,
+            alert.name,
+            alert.team.as_deref().unwrap_or("?"),
+            alert.previous_status.as_deref().unwrap_or("healthy"),
+            alert.status
+        );
+    }
+
+    let mut alerts = new_alerts;
+    alerts.extend(load_starter_alerts(use_case_state, league).await);
+    alerts.truncate(MAX_STARTER_ALERTS);
+
+    let mut cache = use_case_state.lock_cache().await;
+    for (key, value) in [
+        (&snapshot_key, serde_json::to_string(&snapshot(injuries))),
+        (&alerts_key, serde_json::to_string(&alerts)),
+    ] {
+        let stored = match value {
+            Ok(value) => cache.setx(key, &value, ttl).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = stored {
+            tracing::error!("Failed to store {}: {}", key.as_ref(), e);
+        }
+    }
+
+    alerts
+}
+
+async fn load_starter_alerts(
+    use_case_state: &UseCaseState,
+    league: &League,
+) -> Vec<StarterAlert> {
+    let alerts_key = CacheKey::starter_alerts(use_case_state.provider(), league);
+    let mut cache = use_case_state.lock_cache().await;
+    cache
+        .get(&alerts_key)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Paginated team news: provider stories merged with the team's RSS/Atom feeds
+/// GET /api/v1/news
+pub async fn handle_news_request(
+    Query(params): Query<NewsQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<NewsResponse>> {
+    let league = sportsdata_league(&params.league, "News")?;
+    let team = team_abbreviation(&params.team)?;
+    let use_cache = params.cache.unwrap_or(true);
+    let cache_key = CacheKey::team_news(use_case_state.provider(), &league, &team);
+
+    let cached_stories = if use_cache {
+        let mut cache = use_case_state.lock_cache().await;
+        cache
+            .get(&cache_key)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|data| serde_json::from_str::<Vec<NewsStory>>(&data).ok())
+    } else {
+        None
+    };
+
+    let stories = match cached_stories {
+        Some(stories) => stories,
+        None => {
+            let stories =
+                load_team_news(&use_case_state, &league, &team, use_cache).await?;
+            if let Ok(json_data) = serde_json::to_string(&stories) {
+                let mut cache = use_case_state.lock_cache().await;
+                if let Err(e) = cache
+                    .setx(&cache_key, &json_data, use_case_state.config.cache.ttl.news)
+                    .await
+                {
+                    tracing::error!("Failed to cache {} news: {}", team, e);
+                }
+            }
+            stories
+        }
+    };
+
+    let stories: Vec<NewsStory> = match params.kind {
+        Some(kind) => stories.into_iter().filter(|s| s.kind == kind).collect(),
+        None => stories,
+    };
+    let news = &use_case_state.config.news;
+    let (paging, stories) = paginate(
+        &stories,
+        params.page.unwrap_or(1),
+        params.page_size.unwrap_or(news.page_size),
+        news.max_page_size,
+    );
+
+    Ok(Json(NewsResponse {
+        league: league.to_string(),
+        page: NewsPage { paging, stories },
+        team,
+    }))
+}
+
+/// Provider news for the team plus every configured feed, tagged and deduplicated
+async fn load_team_news(
+    use_case_state: &UseCaseState,
+    league: &League,
+    team: &str,
+    use_cache: bool,
+) -> Result<Vec<NewsStory>> {
+    let api_url = news_path(league.clone()).to_string();
+    let news_key = CacheKey::news(use_case_state.provider(), league);
+    let data = cached_fetch(
+        use_case_state,
+        &news_key,
+        use_case_state.config.cache.ttl.news,
+        use_cache,
+        || fetch_from_sportsdata_api(&api_url, use_case_state),
+    )
+    .await?;
+    let provider_news: Vec<SportsDataNews> =
+        serde_json::from_str(&data).map_err(|e| {
+            tracing::error!("Failed to parse {} news JSON: {}", league, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let mut stories: Vec<NewsStory> = provider_news
+        .iter()
+        .map(NewsStory::from)
+        .filter(|story| story.mentions_team(team))
+        .collect();
+
+    let league_name = league.to_string();
+    let feeds = use_case_state.config.news.feeds.iter().filter(|feed| {
+        feed.league.eq_ignore_ascii_case(&league_name)
+            && feed.team.eq_ignore_ascii_case(team)
+    });
+    for feed in feeds {
+        // One broken feed shouldn't take down the team's news
+        match fetch_feed(&feed.url).await {
+            Ok(parsed) => {
+                stories.extend(feed_stories(&parsed, feed.name.as_deref(), team))
+            }
+            Err(e) => tracing::warn!("Skipping news feed {}: {}", feed.url, e),
+        }
+    }
+
+    let roster_url = team_players_path(league.clone(), team.to_string()).to_string();
+    let roster_key = CacheKey::team_players(use_case_state.provider(), league, team);
+    let roster = cached_fetch(
+        use_case_state,
+        &roster_key,
+        use_case_state.config.cache.ttl.players,
+        use_cache,
+        || fetch_from_sportsdata_api(&roster_url, use_case_state),
+    )
+    .await
+    .ok()
+    .and_then(|data| serde_json::from_str::<Vec<SportsDataPlayer>>(&data).ok())
+    .unwrap_or_default();
+    tag_roster(&mut stories, team, &roster);
+
+    Ok(dedupe(stories))
+}
+
+/// Body of an RSS/Atom feed or JSON endpoint
+async fn fetch_feed_body(url: &str) -> Result<Vec<u8>> {
+    tracing::info!("Fetching feed: {}", url);
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| {
+            tracing::error!("Failed to create HTTP client: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let response = client
+        .get(url)
+        .header("User-Agent", "arbitration-app/1.0")
+        .send()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch feed {}: {}", url, e);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    if !response.status().is_success() {
+        tracing::error!("Feed {} returned status: {}", url, response.status());
+        return Err(StatusCode::BAD_GATEWAY.into());
+    }
+
+    let body = response.bytes().await.map_err(|e| {
+        tracing::error!("Failed to read feed {}: {}", url, e);
+        StatusCode::BAD_GATEWAY
+    })?;
+    Ok(body.to_vec())
+}
+
+async fn fetch_feed(url: &str) -> Result<feed_rs::model::Feed> {
+    let body = fetch_feed_body(url).await?;
+    feed_rs::parser::parse(body.as_slice()).map_err(|e| {
+        tracing::error!("Failed to parse feed {}: {}", url, e);
+        StatusCode::BAD_GATEWAY.into()
+    })
+}
+
+/// Paginated videos for a game or team from the league's catalog
+/// GET /api/v1/videos
+pub async fn handle_videos_request(
+    Query(params): Query<VideosQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<VideosResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+    let team = params.team.map(|team| team.trim().to_uppercase());
+
+    let catalog = load_video_catalog(&use_case_state, &league).await?;
+    let videos: Vec<_> = catalog
+        .videos
+        .into_iter()
+        .filter(|video| {
+            params
+                .game_id
+                .as_ref()
+                .is_none_or(|game_id| video.game_id.as_ref() == Some(game_id))
+        })
+        .filter(|video| team.as_ref().is_none_or(|team| video.features_team(team)))
+        .filter(|video| {
+            params
+                .video_type
+                .is_none_or(|video_type| video.video_type == video_type)
+        })
+        .collect();
+    let config = &use_case_state.config.videos;
+    let (paging, videos) = paginate(
+        &videos,
+        params.page.unwrap_or(1),
+        params.page_size.unwrap_or(config.page_size),
+        config.max_page_size,
+    );
+
+    Ok(Json(VideosResponse {
+        league: league.to_string(),
+        game_id: params.game_id,
+        team,
+        fetched_at: catalog.fetched_at,
+        page: VideoPage { paging, videos },
+    }))
+}
+
+/// Video sources read at once during a catalog refresh
+const VIDEO_SOURCE_FETCHES: usize = 4;
+
+/// The league's stored video catalog, refreshed from its sources once it's older
+/// than `videos.refresh_seconds`
+///
+/// Requests can't force a refresh: each one would re-read every source. With a
+/// database the videos live in the `videos` table and Redis only records when
+/// they were last refreshed.
+async fn load_video_catalog(
+    use_case_state: &UseCaseState,
+    league: &League,
+) -> Result<VideoCatalog> {
+    if let Some(catalog) = fresh_video_catalog(use_case_state, league).await? {
+        return Ok(catalog);
+    }
+
+    // Requests that arrive during a refresh wait for it and serve its result
+    let _refreshing = use_case_state
+        .video_refreshes
+        .lock(&league.to_string())
+        .await;
+    if let Some(catalog) = fresh_video_catalog(use_case_state, league).await? {
+        return Ok(catalog);
+    }
+    refresh_video_catalog(use_case_state, league).await
+}
+
+/// The league's catalog from Redis, fresh or not; only used without a database
+async fn cached_video_catalog(
+    use_case_state: &UseCaseState,
+    league: &League,
+) -> Option<VideoCatalog> {
+    let mut cache = use_case_state.lock_cache().await;
+    cache
+        .get(&CacheKey::video_catalog(league))
+        .await
+        .ok()
+        .flatten()
+        .and_then(|data| serde_json::from_str(&data).ok())
+}
+
+/// The league's catalog if it was refreshed within `videos.refresh_seconds`
+async fn fresh_video_catalog(
+    use_case_state: &UseCaseState,
+    league: &League,
+) -> Result<Option<VideoCatalog>> {
+    let config = &use_case_state.config;
+    let refresh_after = config.videos.refresh_seconds as i64;
+    let fresh_enough = |fetched_at: &str| {
+        chrono::DateTime::parse_from_rfc3339(fetched_at).is_ok_and(|fetched_at| {
+            (chrono::Utc::now() - fetched_at.with_timezone(&chrono::Utc)).num_seconds()
+                < refresh_after
+        })
+    };
+
+    let Some(database) = use_case_state.database.as_ref() else {
+        return Ok(cached_video_catalog(use_case_state, league)
+            .await
+            .filter(|catalog| fresh_enough(&catalog.fetched_at)));
+    };
+    let fetched_at: Option<String> = {
+        let mut cache = use_case_state.lock_cache().await;
+        cache
+            .get(&CacheKey::video_refresh(league))
+            .await
+            .ok()
+            .flatten()
+    };
+    match fetched_at.filter(|fetched_at| fresh_enough(fetched_at)) {
+        Some(fetched_at) => Ok(Some(VideoCatalog {
+            fetched_at,
+            videos: league_videos(
+                database,
+                &league.to_string(),
+                config.videos.max_videos,
+            )
+            .await?,
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Read every source of the league, link the videos to games and store them
+async fn refresh_video_catalog(
+    use_case_state: &UseCaseState,
+    league: &League,
+) -> Result<VideoCatalog> {
+    let config = &use_case_state.config;
+    let league_name = league.to_string();
+    let sources: Vec<_> = config
+        .videos
+        .sources
+        .iter()
+        .filter(|source| source.league.eq_ignore_ascii_case(&league_name))
+        .cloned()
+        .collect();
+    let mut fetched = Vec::new();
+    let mut results = futures_util::stream::iter(sources)
+        .map(|source| async move {
+            let videos = match source.kind {
+                VideoSourceKind::Feed => fetch_feed(&source.url)
+                    .await
+                    .map(|feed| feed_videos(&feed, &source)),
+                VideoSourceKind::Youtube => fetch_feed(&format!(
+                    "https://www.youtube.com/feeds/videos.xml?channel_id={}",
+                    source.url
+                ))
+                .await
+                .map(|feed| feed_videos(&feed, &source)),
+                VideoSourceKind::Provider => {
+                    fetch_feed_body(&source.url).await.and_then(|body| {
+                        let body: serde_json::Value = serde_json::from_slice(&body)?;
+                        Ok(provider_videos(&body, &source))
+                    })
+                }
+            };
+            (source, videos)
+        })
+        .buffered(VIDEO_SOURCE_FETCHES);
+    while let Some((source, videos)) = results.next().await {
+        // One broken source shouldn't take down the catalog
+        match videos {
+            Ok(videos) => fetched.extend(videos),
+            Err(e) => tracing::warn!("Skipping video source {}: {}", source.url, e),
+        }
+    }
+
+    let names: Vec<(String, Vec<String>)> = config
+        .api
+        .reddit_api
+        .teams
+        .iter()
+        .filter(|team| team.league.eq_ignore_ascii_case(&league_name))
+        .map(|team| (team.team.clone(), team.names.clone()))
+        .collect();
+    tag_teams(&mut fetched, &names);
+
+    match load_schedule_games(use_case_state, league, true).await {
+        Ok(games) => {
+            let games: Vec<_> = games
+                .into_iter()
+                .filter_map(|game| {
+                    let start = scheduled_start(&game)?;
+                    Some((game, start))
+                })
+                .collect();
+            link_games(&mut fetched, &games);
+        }
+        Err(e) => tracing::warn!("Videos not linked to {} games: {}", league, e),
+    }
+
+    let fetched_at = chrono::Utc::now().to_rfc3339();
+    let (videos, new_videos) = match use_case_state.database.as_ref() {
+        Some(database) => {
+            let new_videos = store_videos(database, &league_name, &fetched).await?;
+            let videos =
+                league_videos(database, &league_name, config.videos.max_videos).await?;
+            let mut cache = use_case_state.lock_cache().await;
+            if let Err(e) = cache
+                .setx(
+                    &CacheKey::video_refresh(league),
+                    &fetched_at,
+                    config.videos.refresh_seconds,
+                )
+                .await
+            {
+                tracing::error!("Failed to record {} video refresh: {}", league, e);
+            }
+            (videos, new_videos)
+        }
+        None => {
+            let stored = cached_video_catalog(use_case_state, league)
+                .await
+                .map(|stored| stored.videos)
+                .unwrap_or_default();
+            upsert_videos(stored, fetched, config.videos.max_videos)
+        }
+    };
+    tracing::info!("Catalogued {} new {} videos", new_videos, league);
+    let catalog = VideoCatalog { fetched_at, videos };
+    if use_case_state.database.is_none() {
+        if let Ok(json_data) = serde_json::to_string(&catalog) {
+            let mut cache = use_case_state.lock_cache().await;
+            if let Err(e) = cache
+                .setx(
+                    &CacheKey::video_catalog(league),
+                    &json_data,
+                    config.cache.ttl.videos,
+                )
+                .await
+            {
+                tracing::error!("Failed to store {} video catalog: {}", league, e);
+            }
+        }
+    }
+
+    Ok(catalog)
+}
+
+/// Plays, tweets and game thread comments for one game, merged in time order
+/// GET /api/v1/game-timeline
+pub async fn handle_game_timeline_request(
+    Query(params): Query<GameTimelineQuery>,
+    State(use_case_state): State<UseCaseState>,
+    headers: HeaderMap,
+) -> Result<Json<GameTimelineResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+    if params.game_id.trim().is_empty() {
+        tracing::error!("Missing or empty game_id parameter");
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+    let cursor = match params.cursor.as_deref() {
+        Some(cursor) => Some(TimelineCursor::decode(cursor).ok_or_else(|| {
+            tracing::error!("Invalid timeline cursor: {}", cursor);
+            StatusCode::BAD_REQUEST
+        })?),
+        None => None,
+    };
+
+    let items = load_timeline_items(
+        &use_case_state,
+        &league,
+        &params.game_id,
+        params.query.clone(),
+        params.subreddit.clone(),
+        moderation_strictness(&use_case_state, &headers).await,
+        params.cache.unwrap_or(true),
+    )
+    .await?;
+    Ok(Json(GameTimelineResponse {
+        league: league.to_string(),
+        game_id: params.game_id,
+        page: page(&items, cursor.as_ref(), params.limit.unwrap_or(50)),
+    }))
+}
+
+/// Fan sentiment per team over a game, one point per play
+/// GET /api/v1/game-sentiment
+pub async fn handle_game_sentiment_request(
+    Query(params): Query<GameSentimentQuery>,
+    State(use_case_state): State<UseCaseState>,
+    headers: HeaderMap,
+) -> Result<Json<GameSentimentResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+    let use_cache = params.cache.unwrap_or(true);
+
+    let game = load_schedule_games(&use_case_state, &league, true)
+        .await?
+        .into_iter()
+        .find(|game| game.game_id == params.game_id)
+        .ok_or_else(|| {
+            tracing::error!("Game {} not found in {} schedule", params.game_id, league);
+            StatusCode::NOT_FOUND
+        })?;
+
+    let items = load_timeline_items(
+        &use_case_state,
+        &league,
+        &params.game_id,
+        params.query.clone(),
+        params.subreddit.clone(),
+        moderation_strictness(&use_case_state, &headers).await,
+        use_cache,
+    )
+    .await?;
+
+    let league_name = league.to_string();
+    let fan_base = |team: &str| {
+        let mut fan_base = FanBase {
+            terms: vec![team.to_string()],
+            subreddits: Vec::new(),
+        };
+        let config = &use_case_state.config;
+        let in_league = |config_league: &str, config_team: &str| {
+            config_league.eq_ignore_ascii_case(&league_name)
+                && config_team.eq_ignore_ascii_case(team)
+        };
+        for social in &config.social.teams {
+            if in_league(&social.league, &social.team) {
+                fan_base.terms.extend(social.hashtags.iter().cloned());
+                fan_base.terms.extend(social.handles.iter().cloned());
+                fan_base.terms.extend(social.beat_reporters.iter().cloned());
+            }
+        }
+        for reddit in &config.api.reddit_api.teams {
+            if in_league(&reddit.league, &reddit.team) {
+                fan_base.terms.extend(reddit.names.iter().cloned());
+                fan_base.subreddits.push(reddit.subreddit.clone());
+            }
+        }
+        fan_base
+    };
+
+    let points = sentiment_series(&items, &fan_base(&game.home), &fan_base(&game.away));
+    Ok(Json(GameSentimentResponse {
+        league: league_name,
+        game_id: params.game_id,
+        home_team: game.home,
+        away_team: game.away,
+        scored: items.iter().filter(|item| item.sentiment.is_some()).count(),
+        points,
+    }))
+}
+
+/// Plays, tweets and game thread comments for a game, merged in time order
+async fn load_timeline_items(
+    state: &UseCaseState,
+    league: &League,
+    game_id: &str,
+    query: Option<String>,
+    subreddit: Option<String>,
+    strictness: Strictness,
+    use_cache: bool,
+) -> Result<Vec<TimelineItem>> {
+    // Checked here too, since a failed thread lookup below only skips comments
+    let subreddit = subreddit.as_deref().map(subreddit_name).transpose()?;
+    let api_url =
+        play_by_play_path(league.clone(), Some(game_id.to_string())).to_string();
+    let cache_key = CacheKey::play_by_play(state.provider(), league, game_id);
+    let data = cached_fetch(
+        state,
+        &cache_key,
+        state.config.cache.ttl.play_by_play,
+        use_cache,
+        || fetch_play_by_play_from_api(&api_url, league, game_id, false, None, state),
+    )
+    .await?;
+    let json_data: serde_json::Value = serde_json::from_str(&data).map_err(|e| {
+        tracing::error!(
+            "Failed to parse play-by-play JSON for game_id {}: {}",
+            game_id,
+            e
+        );
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let (events, _, _, _) = extract_play_by_play_events(json_data, &None, None)?;
+    let mut items = play_items(events.as_array().map(Vec::as_slice).unwrap_or_default());
+
+    // Social sources are best effort: the plays alone are still a timeline
+    if let Some(query) = query {
+        let tweets = search_twitter(
+            state,
+            TwitterSearchQuery {
+                query,
+                cursor: None,
+                cache: Some(use_cache),
+            },
+        )
+        .await;
+        match tweets {
+            Ok(mut response) => {
+                Moderator::new(&state.config.moderation, strictness)
+                    .tweets(&mut response);
+                items.extend(tweet_items(&response.tweets))
+            }
+            Err(e) => {
+                tracing::warn!("Skipping tweets for game {}: {}", game_id, e)
+            }
+        }
+    }
+    if let Some(subreddit) = subreddit {
+        let thread = load_thread_comments(
+            state,
+            RedditSearchQuery {
+                league: Some(league.to_string()),
+                subreddit: Some(subreddit),
+                game_id: Some(game_id.to_string()),
+                kind: None,
+                limit: Some(state.config.api.reddit_api.max_comment_limit),
+                cache: Some(use_cache),
+            },
+        )
+        .await;
+        match thread {
+            Ok(mut response) => {
+                Moderator::new(&state.config.moderation, strictness)
+                    .reddit(&mut response);
+                for post in &response.posts {
+                    items.extend(comment_items(&post.comments));
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Skipping Reddit comments for game {}: {}", game_id, e)
+            }
+        }
+    }
+
+    Ok(merge(items))
+}
+
+/// A game's scheduled start, or midnight Eastern of its day when no time is set
+fn scheduled_start(game: &ScheduledGame) -> Option<chrono::DateTime<chrono::Utc>> {
+    game.date_time
+        .as_deref()
+        .or(Some(game.day.as_str()))
+        .and_then(eastern_datetime)
+        .map(|start| start.with_timezone(&chrono::Utc))
+}
+
+/// Tweets for a game from a query generated out of team hashtags, accounts,
+/// beat reporters and the game window, collected across result pages
+/// GET /api/v1/game-social
+pub async fn handle_game_social_request(
+    Query(params): Query<GameSocialQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<GameSocialResponse>> {
+    let league = sportsdata_league(&params.league, "Game social search")?;
+    let use_cache = params.cache.unwrap_or(true);
+
+    let game = load_schedule_games(&use_case_state, &league, true)
+        .await?
+        .into_iter()
+        .find(|game| game.game_id == params.game_id)
+        .ok_or_else(|| {
+            tracing::error!("Game {} not found in {} schedule", params.game_id, league);
+            StatusCode::NOT_FOUND
+        })?;
+    let start = scheduled_start(&game).ok_or_else(|| {
+        tracing::error!("Game {} has no scheduled start", params.game_id);
+        StatusCode::NOT_FOUND
+    })?;
+
+    let social = &use_case_state.config.social;
+    let league_name = league.to_string();
+    let teams: Vec<TeamSocialConfig> = social
+        .teams
+        .iter()
+        .filter(|team| team.league.eq_ignore_ascii_case(&league_name))
+        .cloned()
+        .collect();
+    let window = game_window(
+        start,
+        social.window_before_minutes,
+        social.window_after_minutes,
+    );
+    let query = game_query(&game.home, &game.away, &teams, window);
+
+    let database = use_case_state.database.as_ref();
+    let refresh_key = CacheKey::social_refresh(&league, &params.game_id);
+    let refreshed: Option<SocialRefresh> = match database {
+        Some(_) if use_cache => {
+            let mut cache = use_case_state.lock_cache().await;
+            cache
+                .get(&refresh_key)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|data| serde_json::from_str(&data).ok())
+        }
+        _ => None,
+    };
+
+    // Stored posts are served as-is until they're as old as a Twitter search result
+    let refresh_after = use_case_state.config.cache.ttl.twitter_search as i64;
+    let fresh_enough = |refreshed: &SocialRefresh| {
+        chrono::DateTime::parse_from_rfc3339(&refreshed.fetched_at).is_ok_and(
+            |fetched_at| {
+                (chrono::Utc::now() - fetched_at.with_timezone(&chrono::Utc))
+                    .num_seconds()
+                    < refresh_after
+            },
+        )
+    };
+    if let (Some(refreshed), Some(database)) = (refreshed, database) {
+        if fresh_enough(&refreshed) {
+            let posts = game_posts(database, &league_name, &params.game_id).await?;
+            return Ok(Json(GameSocialResponse {
+                league: league_name,
+                game_id: params.game_id,
+                home_team: game.home,
+                away_team: game.away,
+                query: refreshed.query,
+                pages_fetched: 0,
+                new_posts: 0,
+                fetched_at: refreshed.fetched_at,
+                count: posts.len(),
+                posts,
+            }));
+        }
+    }
+
+    let max_pages = params
+        .max_pages
+        .unwrap_or(social.max_pages)
+        .clamp(1, social.max_pages.max(1));
+    let team_abbrs = [game.home.as_str(), game.away.as_str()];
+    let mut fetched: Vec<SocialPost> = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut pages_fetched = 0;
+    while pages_fetched < max_pages {
+        let page = match fetch_twitter_search(&use_case_state, &query, cursor.as_deref())
+            .await
+        {
+            Ok(page) => page,
+            // Keep the pages (or stored posts) we have instead of failing the refresh
+            Err(e)
+                if is_rate_limited(&e) && (pages_fetched > 0 || database.is_some()) =>
+            {
+                tracing::warn!(
+                    "Twitter rate limited after {} pages for game {}",
+                    pages_fetched,
+                    params.game_id
+                );
+                break;
+            }
+            Err(e) => return Err(e),
+        };
+        pages_fetched += 1;
+        fetched.extend(page.tweets.iter().map(|tweet| {
+            SocialPost::from_tweet(tweet, &league_name, &params.game_id, &team_abbrs)
+        }));
+
+        match page
+            .next_cursor
+            .filter(|next| page.has_next_page && !next.is_empty())
+        {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    let refreshed = SocialRefresh {
+        query,
+        fetched_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let (posts, new_posts) = match database {
+        Some(database) => {
+            let new_posts = store_posts(database, &fetched).await?;
+            let posts = game_posts(database, &league_name, &params.game_id).await?;
+            if let Ok(json_data) = serde_json::to_string(&refreshed) {
+                let mut cache = use_case_state.lock_cache().await;
+                if let Err(e) = cache
+                    .setx(&refresh_key, &json_data, refresh_after as u64)
+                    .await
+                {
+                    tracing::error!(
+                        "Failed to record social refresh for game {}: {}",
+                        params.game_id,
+                        e
+                    );
+                }
+            }
+            (posts, new_posts)
+        }
+        // Without a database nothing outlives the request
+        None => upsert_posts(Vec::new(), fetched),
+    };
+
+    tracing::info!(
+        "Collected {} new posts for game {} across {} pages",
+        new_posts,
+        params.game_id,
+        pages_fetched
+    );
+
+    Ok(Json(GameSocialResponse {
+        league: league_name,
+        game_id: params.game_id,
+        home_team: game.home,
+        away_team: game.away,
+        query: refreshed.query,
+        pages_fetched,
+        new_posts,
+        fetched_at: refreshed.fetched_at,
+        count: posts.len(),
+        posts,
+    }))
+}
+
+/// Fetch a season's standings and normalize them into `TeamRecord`s
+async fn load_standings_records(
+    use_case_state: &UseCaseState,
+    league: &League,
+    season: i32,
+) -> Result<Vec<TeamRecord>> {
+    let api_url = standings_path(league.clone(), season).to_string();
+    let raw_data = fetch_standings_from_api(&api_url, league, use_case_state).await?;
+
+    let teams = match league {
+        League::Mlb => {
+            let standings: crate::schema::sportradar::mlb::standings::MLBStandings =
+                serde_json::from_str(&raw_data).map_err(|e| {
+                    tracing::error!("Failed to parse MLB standings JSON: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            standings.iter().map(TeamRecord::from).collect()
+        }
+        League::Nfl => {
+            let standings: crate::schema::sportradar::nfl::standings::NFLStandings =
+                serde_json::from_str(&raw_data).map_err(|e| {
+                    tracing::error!("Failed to parse NFL standings JSON: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            standings.iter().map(TeamRecord::from).collect()
+        }
+        League::Nba => {
+            let standings: crate::schema::sportradar::nba::standings::NBAStandings =
+                serde_json::from_str(&raw_data).map_err(|e| {
+                    tracing::error!("Failed to parse NBA standings JSON: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            standings.iter().map(TeamRecord::from).collect()
+        }
+        _ => {
+            tracing::error!("Unsupported league for standings: {}", league);
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+    };
+
+    Ok(teams)
+}
+
+/// Load the full regular season schedule, sharing the `/api/v1/schedule` cache entry
+async fn load_schedule_games(
+    use_case_state: &UseCaseState,
+    league: &League,
+    use_cache: bool,
+) -> Result<Vec<ScheduledGame>> {
+    let cache_key =
+        CacheKey::data_type(use_case_state.provider(), &DataType::Schedule, league);
+
+    let cached_data = if use_cache {
+        let mut cache = use_case_state.lock_cache().await;
+        match cache.get(&cache_key).await {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!("Failed to get schedule data from cache: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let data = match cached_data {
+        Some(data) => data,
+        None => {
+            let api_url =
+                schedule_path(league.clone(), use_case_state.config.clone()).to_string();
+            let fetched_data =
+                fetch_schedule_from_api(&api_url, league, use_case_state).await?;
+
+            let mut cache = use_case_state.lock_cache().await;
+            if let Err(e) = cache
+                .setx(
+                    &cache_key,
+                    &fetched_data,
+                    use_case_state.config.cache.ttl.schedule,
+                )
+                .await
+            {
+                tracing::error!("Failed to cache schedule data: {}", e);
+            }
+
+            fetched_data
+        }
+    };
+
+    let games: Vec<serde_json::Value> = serde_json::from_str(&data).map_err(|e| {
+        tracing::error!("Failed to parse schedule JSON data: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(parse_schedule(&games))
+}
+
+async fn fetch_stadiums_from_api(
+    api_url: &str,
+    league: &League,
+    state: &UseCaseState,
+) -> Result<String> {
+    let api_key = &state.config.api.sportsdata_api_key;
+
+    let client = reqwest::Client::new();
+
+    tracing::info!(
+        "Making real API request to: {} for league: {}",
+        api_url,
+        league
+    );
+
+    let response = metered(
+        &state.usage,
+        Upstream::SportsData,
+        client.get(api_url).query(&[("key", &api_key)]),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        tracing::error!("API request failed with status: {}", status);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+    }
+
+    let body = response.text().await.map_err(|e| {
+        tracing::error!("Failed to read response body: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tracing::info!("Successfully fetched stadiums data from API");
+    Ok(body)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TwitterSearchQuery {
+    pub query: String,
+    /// `next_cursor` from the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+pub async fn handle_twitter_search_request(
+    State(state): State<UseCaseState>,
+    headers: HeaderMap,
+    Query(params): Query<TwitterSearchQuery>,
+) -> Result<Json<TwitterSearchResponse>> {
+    let strictness = moderation_strictness(&state, &headers).await;
+    let mut twitter_response = search_twitter(&state, params).await?;
+
+    let mut moderator = Moderator::new(&state.config.moderation, strictness);
+    moderator.tweets(&mut twitter_response);
+    tracing::debug!(
+        "Moderated Twitter results at {}: {:?}",
+        strictness,
+        moderator.summary
+    );
+
+    Ok(Json(twitter_response))
+}
+
+/// Twitter search results for a query, unmoderated, cached per cursor
+async fn search_twitter(
+    state: &UseCaseState,
+    params: TwitterSearchQuery,
+) -> Result<TwitterSearchResponse> {
+    tracing::info!(
+        "Twitter search request for query: {} (cursor: {:?})",
+        params.query,
+        params.cursor
+    );
+
+    let cursor = params.cursor.as_deref().filter(|cursor| !cursor.is_empty());
+    let cache_key = CacheKey::twitter_search(&params.query, cursor);
+    let stale_key = CacheKey::twitter_search_stale(&params.query, cursor);
+    let use_cache = params.cache.unwrap_or(true);
+
+    if use_cache {
+        if let Some(cached) = cached_twitter_search(state, &cache_key).await {
+            tracing::debug!("Returning cached Twitter search result");
+            return Ok(cached);
+        }
+    } else {
+        tracing::info!("Cache bypass requested - fetching fresh data from Twitter API");
+    }
+
+    let twitter_response = match fetch_twitter_search(state, &params.query, cursor).await
+    {
+        Ok(twitter_response) => twitter_response,
+        Err(e) if is_rate_limited(&e) => {
+            // Serve whatever we last saw rather than fail while the budget recovers
+            for key in [&cache_key, &stale_key] {
+                if let Some(cached) = cached_twitter_search(state, key).await {
+                    tracing::warn!(
+                        "Twitter rate limited, serving cached results for: {}",
+                        params.query
+                    );
+                    return Ok(cached);
+                }
+            }
+            return Err(e);
+        }
+        Err(e) => return Err(e),
+    };
+
+    if let Ok(json_data) = serde_json::to_string(&twitter_response) {
+        let ttl = &state.config.cache.ttl;
+        let mut cache = state.lock_cache().await;
+        for (key, ttl) in [
+            (&cache_key, ttl.twitter_search),
+            (&stale_key, ttl.twitter_search_stale),
+        ] {
+            if let Err(e) = cache.setx(key, &json_data, ttl).await {
+                tracing::error!("Failed to cache {}: {}", key.as_ref(), e);
+            }
+        }
+    }
+
+    Ok(twitter_response)
+}
+
+async fn cached_twitter_search(
+    state: &UseCaseState,
+    key: &CacheKey,
+) -> Option<TwitterSearchResponse> {
+    let cached_data = state.lock_cache().await.get(key).await.ok().flatten()?;
+    serde_json::from_str(&cached_data).ok()
+}
+
+fn is_rate_limited(error: &Error) -> bool {
+    matches!(error, Error::Status(StatusCode::TOO_MANY_REQUESTS))
+}
+
+/// Fetch one page of Twitter advanced search results
+///
+/// Every call spends from the shared request budget; when it's empty, or the
+/// API has asked us to back off, this fails with 429 without calling out.
+async fn fetch_twitter_search(
+    state: &UseCaseState,
+    query: &str,
+    cursor: Option<&str>,
+) -> Result<TwitterSearchResponse> {
+    let api_key = &state.config.api.twitter_api_key;
+    if api_key.is_empty() {
+        tracing::error!("Twitter API key not configured (TWITTERAPIIO_API_KEY)");
+        return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+    }
+
+    let budget = state
+        .twitter
+        .budget
+        .lock()
+        .map(|mut budget| budget.acquire(std::time::Instant::now()))
+        .unwrap_or(Ok(()));
+    if let Err(wait) = budget {
+        tracing::warn!(
+            "Twitter request budget exhausted, next request in {:.1}s",
+            wait.as_secs_f64()
+        );
+        return Err(StatusCode::TOO_MANY_REQUESTS.into());
+    }
+
+    let url = format!(
+        "{}/twitter/tweet/advanced_search",
+        state.config.api.twitter_base_url.trim_end_matches('/')
+    );
+    let mut query_params = vec![("query", query)];
+    if let Some(cursor) = cursor {
+        query_params.push(("cursor", cursor));
+    }
+
+    tracing::info!("Making request to Twitter API: {} (query: {})", url, query);
+
+    let response = metered(
+        &state.usage,
+        Upstream::TwitterApi,
+        state
+            .twitter
+            .http
+            .get(&url)
+            .query(&query_params)
+            .header("X-API-Key", api_key)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "arbitration-app/1.0"),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, chrono::Utc::now()));
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Failed to read error body".to_string());
+        tracing::error!(
+            "Twitter API returned error status: {} - Body: {}",
+            status,
+            error_body
+        );
+
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => {
+                if let Ok(mut budget) = state.twitter.budget.lock() {
+                    let wait = budget.back_off(std::time::Instant::now(), retry_after);
+                    tracing::warn!(
+                        "Twitter API rate limit exceeded, backing off for {}s",
+                        wait.as_secs()
+                    );
+                }
+                return Err(StatusCode::TOO_MANY_REQUESTS.into());
+            }
+            StatusCode::UNAUTHORIZED => {
+                tracing::error!("Twitter API authentication failed - check API key");
+                return Err(StatusCode::UNAUTHORIZED.into());
+            }
+            StatusCode::BAD_REQUEST => {
                 tracing::error!(
                     "Twitter API bad request - check query format: {}",
                     error_body
                 );
-                return Err(StatusCode::BAD_REQUEST.into());
+                return Err(StatusCode::BAD_REQUEST.into());
+            }
+            _ => {
+                tracing::error!("Twitter API unknown error: {} - {}", status, error_body);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+            }
+        }
+    }
+
+    let body = response.text().await.map_err(|e| {
+        tracing::error!("Failed to read response body: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let twitter_response: TwitterSearchResponse =
+        serde_json::from_str(&body).map_err(|e| {
+            tracing::error!("Failed to parse Twitter API response: {}", e);
+            tracing::error!("Response body: {}", body);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!(
+        "Successfully fetched {} tweets from Twitter API",
+        twitter_response.tweets.len()
+    );
+
+    Ok(twitter_response)
+}
+
+pub async fn handle_reddit_search_request(
+    State(state): State<UseCaseState>,
+    headers: HeaderMap,
+    Query(params): Query<RedditSearchQuery>,
+) -> Result<Json<RedditSearchResponse>> {
+    let strictness = moderation_strictness(&state, &headers).await;
+    let mut reddit_response = load_thread_comments(&state, params).await?;
+
+    let mut moderator = Moderator::new(&state.config.moderation, strictness);
+    moderator.reddit(&mut reddit_response);
+    tracing::debug!(
+        "Moderated Reddit comments at {}: {:?}",
+        strictness,
+        moderator.summary
+    );
+
+    Ok(Json(reddit_response))
+}
+
+/// The first `limit` comments of a game's Reddit thread in the requested sort,
+/// with their expanded replies, unmoderated
+async fn load_thread_comments(
+    state: &UseCaseState,
+    params: RedditSearchQuery,
+) -> Result<RedditSearchResponse> {
+    tracing::info!("Reddit search request received with params: {:?}", params);
+
+    let (Some(league), Some(game_id)) = (params.league, params.game_id) else {
+        tracing::error!("Missing required parameters: league and game_id");
+        return Err(StatusCode::BAD_REQUEST.into());
+    };
+    let league = league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", league);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let fetch_limit = 100;
+    let output_limit = params
+        .limit
+        .unwrap_or(state.config.api.reddit_api.default_comment_limit);
+    let sort_kind = params.kind.unwrap_or_else(|| "new".to_string());
+    let bypass_cache = params.cache.unwrap_or(true); // Default to using cache
+
+    let comments_cache_key = CacheKey::reddit_thread_comments(
+        &league,
+        &game_id,
+        params.subreddit.as_deref(),
+        &sort_kind,
+        output_limit,
+    );
+
+    if bypass_cache {
+        if let Ok(Some(cached_data)) =
+            state.lock_cache().await.get(&comments_cache_key).await
+        {
+            if let Ok(reddit_response) =
+                serde_json::from_str::<RedditSearchResponse>(&cached_data)
+            {
+                tracing::debug!("Returning cached Reddit comments result");
+                return Ok(reddit_response);
+            }
+        }
+    } else {
+        tracing::info!("Cache bypass requested - fetching fresh data from Reddit API");
+    }
+
+    let thread =
+        load_game_thread(state, &league, &game_id, params.subreddit.as_deref(), true)
+            .await?;
+    let subreddit_clean = thread.subreddit.as_str();
+    let game_thread_id = thread.thread_id.clone();
+
+    tracing::info!(
+        "Reddit comments for game {} in r/{} (thread {}), fetch_limit: {}, output_limit: {}",
+        game_id,
+        subreddit_clean,
+        game_thread_id,
+        fetch_limit,
+        output_limit
+    );
+
+    let tree = state
+        .reddit
+        .comment_tree(subreddit_clean, &game_thread_id, &sort_kind, fetch_limit)
+        .await;
+    let (post_data, comment_children) = match tree {
+        Ok(tree) => tree,
+        Err(e) if is_rate_limited(&e) => return Err(e),
+        Err(e) => {
+            tracing::warn!(
+                "Reddit comments request failed for thread {} in {}: {}",
+                game_thread_id,
+                subreddit_clean,
+                e
+            );
+            return Ok(RedditSearchResponse {
+                posts: vec![],
+                total_posts: 0,
+                subreddits_searched: vec![subreddit_clean.to_string()],
+                game_id,
+                search_timestamp: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+    };
+
+    let mut reddit_post = RedditPost {
+        id: game_thread_id.clone(),
+        title: post_data["data"]["title"]
+            .as_str()
+            .unwrap_or("Game Thread")
+            .to_string(),
+        author: post_data["data"]["author"]
+            .as_str()
+            .unwrap_or("Unknown")
+            .to_string(),
+        selftext: post_data["data"]["selftext"]
+            .as_str()
+            .unwrap_or("Game thread comments")
+            .to_string(),
+        score: post_data["data"]["score"].as_i64().unwrap_or(0) as i32,
+        num_comments: post_data["data"]["num_comments"].as_i64().unwrap_or(0) as i32,
+        created_utc: post_data["data"]["created_utc"].as_f64().unwrap_or(0.0),
+        permalink: post_data["data"]["permalink"]
+            .as_str()
+            .unwrap_or("")
+            .to_string(),
+        subreddit: subreddit_clean.to_string(),
+        url: post_data["data"]["url"].as_str().map(|s| s.to_string()),
+        is_self: post_data["data"]["is_self"].as_bool().unwrap_or(true),
+        over_18: post_data["data"]["over_18"].as_bool().unwrap_or(false),
+        team: None,
+        comments: Vec::new(),
+    };
+
+    let comments_array: Vec<&RedditChild> = comment_children
+        .iter()
+        .filter(|child| child.kind == "t1")
+        .collect();
+    tracing::info!(
+        "Found {} comments for game thread {}, keeping the first {} by {}",
+        comments_array.len(),
+        game_thread_id,
+        output_limit,
+        sort_kind
+    );
+
+    // Reddit returns the tree in the requested sort, so the first comments are the ones asked for
+    for comment_data in comments_array.into_iter().take(output_limit as usize) {
+        if let Some(body) = &comment_data.data.body {
+            let comment = RedditComment {
+                id: comment_data.data.id.clone().unwrap_or_default(),
+                author: comment_data.data.author.clone().unwrap_or_default(),
+                content: body.clone(),
+                timestamp: if let Some(created_utc) = comment_data.data.created_utc {
+                    chrono::DateTime::from_timestamp(created_utc as i64, 0)
+                        .unwrap_or_else(chrono::Utc::now)
+                        .to_rfc3339()
+                } else {
+                    chrono::Utc::now().to_rfc3339()
+                },
+                score: comment_data.data.score.unwrap_or(0) as i32,
+                permalink: format!(
+                    "https://reddit.com{}",
+                    comment_data.data.permalink.clone().unwrap_or_default()
+                ),
+                subreddit: comment_data.data.subreddit.clone().unwrap_or_default(),
+                team: Some("home".to_string()), // Default team assignment
+                depth: 0,
+                replies: comment_data
+                    .data
+                    .extract_comments(Some("home".to_string()), 1),
+            };
+            reddit_post.comments.push(comment);
+        }
+    }
+
+    let reddit_response = RedditSearchResponse {
+        posts: vec![reddit_post],
+        total_posts: 1,
+        subreddits_searched: vec![subreddit_clean.to_string()],
+        game_id,
+        search_timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if let Ok(json_data) = serde_json::to_string(&reddit_response) {
+        let _ = state
+            .lock_cache()
+            .await
+            .setx(
+                &comments_cache_key,
+                &json_data,
+                state.config.cache.ttl.reddit_thread_comments,
+            )
+            .await;
+    }
+
+    Ok(reddit_response)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RedditThreadQuery {
+    pub league: String,
+    pub game_id: String,
+    /// Subreddit to search instead of the teams' configured ones
+    #[serde(default)]
+    pub subreddit: Option<String>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+}
+
+/// Find the Reddit game thread for one game
+/// GET /api/v1/reddit-thread
+pub async fn handle_reddit_thread_request(
+    State(state): State<UseCaseState>,
+    Query(params): Query<RedditThreadQuery>,
+) -> Result<Json<serde_json::Value>> {
+    tracing::info!("Reddit thread request received with params: {:?}", params);
+
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+    let use_cache = params.cache.unwrap_or(true);
+    if !use_cache {
+        tracing::info!("Cache bypass requested - fetching fresh data from Reddit API");
+    }
+
+    let thread = load_game_thread(
+        &state,
+        &league,
+        &params.game_id,
+        params.subreddit.as_deref(),
+        use_cache,
+    )
+    .await?;
+
+    Ok(Json(serde_json::json!({"success": true, "thread": thread})))
+}
+
+/// Subreddit name without its `r/` prefix, checked against Reddit's naming
+/// rules (2 to 21 letters, digits or underscores) before it goes into a path
+fn subreddit_name(subreddit: &str) -> Result<String> {
+    let name = subreddit.trim().trim_start_matches("r/");
+    if !(2..=21).contains(&name.len())
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        tracing::error!("Invalid subreddit: {}", subreddit);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+    Ok(name.to_string())
+}
+
+/// Title prefix a subreddit's game threads are searched by
+fn game_thread_search_query(league: &League, subreddit: &str) -> &'static str {
+    match league {
+        League::Mlb => match subreddit.to_lowercase().as_str() {
+            "dodgers" | "mariners" | "brewers" => "Game Chat",
+            "chicubs" => "GDT:",
+            _ => "Game Thread",
+        },
+        _ => "Game Thread",
+    }
+}
+
+/// The Reddit game thread for a game, cached by game id
+///
+/// Searches `subreddit` when given, otherwise the home team's configured
+/// subreddit and then the away team's, matching threads on team names, the
+/// scheduled start and flair.
+async fn load_game_thread(
+    state: &UseCaseState,
+    league: &League,
+    game_id: &str,
+    subreddit: Option<&str>,
+    use_cache: bool,
+) -> Result<GameThread> {
+    let subreddit = subreddit.map(subreddit_name).transpose()?;
+    let cache_key = CacheKey::reddit_game_thread(league, game_id);
+    if use_cache {
+        if let Ok(Some(cached_data)) = state.lock_cache().await.get(&cache_key).await {
+            if let Ok(thread) = serde_json::from_str::<GameThread>(&cached_data) {
+                tracing::debug!(
+                    "Found cached game thread {} for game {}",
+                    thread.thread_id,
+                    game_id
+                );
+                return Ok(thread);
             }
-            _ => {
-                tracing::error!("Twitter API unknown error: {} - {}", status, error_body);
-                return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+        }
+    }
+
+    let game = load_schedule_games(state, league, true)
+        .await?
+        .into_iter()
+        .find(|game| game.game_id == game_id)
+        .ok_or_else(|| {
+            tracing::error!("Game {} not found in {} schedule", game_id, league);
+            StatusCode::NOT_FOUND
+        })?;
+    let start = scheduled_start(&game).ok_or_else(|| {
+        tracing::error!("Game {} has no scheduled start", game_id);
+        StatusCode::NOT_FOUND
+    })?;
+
+    let league_name = league.to_string();
+    let team_config = |team: &str| {
+        state.config.api.reddit_api.teams.iter().find(|config| {
+            config.league.eq_ignore_ascii_case(&league_name)
+                && config.team.eq_ignore_ascii_case(team)
+        })
+    };
+    let team_names = |team: &str| {
+        let mut names = vec![team.to_string()];
+        if let Some(config) = team_config(team) {
+            names.extend(config.names.iter().cloned());
+        }
+        names
+    };
+    let target = GameThreadTarget {
+        home_names: team_names(&game.home),
+        away_names: team_names(&game.away),
+        start,
+    };
+
+    let subreddits: Vec<String> = match subreddit {
+        Some(subreddit) => vec![subreddit],
+        None => [&game.home, &game.away]
+            .into_iter()
+            .filter_map(|team| team_config(team))
+            .map(|config| config.subreddit.clone())
+            .collect(),
+    };
+    if subreddits.is_empty() {
+        tracing::error!(
+            "No subreddit configured for {} @ {} ({})",
+            game.away,
+            game.home,
+            league
+        );
+        return Err(StatusCode::NOT_FOUND.into());
+    }
+
+    for subreddit in &subreddits {
+        let query = game_thread_search_query(league, subreddit);
+        let search = state
+            .reddit
+            .get::<RedditListing>(
+                &format!("/r/{}/search", subreddit),
+                &[
+                    ("q", query),
+                    ("restrict_sr", "1"),
+                    ("sort", "new"),
+                    ("limit", "25"),
+                ],
+            )
+            .await;
+        let search_data = match search {
+            Ok(search_data) => search_data,
+            Err(e) if is_rate_limited(&e) => return Err(e),
+            Err(e) => {
+                tracing::warn!("Reddit search failed for subreddit {}: {}", subreddit, e);
+                continue;
             }
+        };
+
+        let Some(post) = find_game_thread(&search_data, &target) else {
+            continue;
+        };
+        tracing::info!(
+            "Found game thread for game {} using query '{}': {} (ID: {})",
+            game_id,
+            query,
+            post.title,
+            post.id
+        );
+
+        let thread = GameThread {
+            league: league_name.clone(),
+            game_id: game_id.to_string(),
+            subreddit: subreddit.clone(),
+            thread_id: post.id.clone(),
+            title: post.title.clone(),
+            permalink: post.permalink.clone(),
+            created_utc: post.created_utc,
+        };
+        if let Ok(json_data) = serde_json::to_string(&thread) {
+            let _ = state
+                .lock_cache()
+                .await
+                .setx(&cache_key, &json_data, state.config.cache.ttl.reddit_thread)
+                .await;
+        }
+        return Ok(thread);
+    }
+
+    tracing::warn!(
+        "No game thread found for game {} in r/{}",
+        game_id,
+        subreddits.join(", r/")
+    );
+    Err(StatusCode::NOT_FOUND.into())
+}
+
+pub async fn handle_odds_by_date_request(
+    Query(params): Query<OddsByDateQuery>,
+    State(use_case_state): State<UseCaseState>,
+) -> Result<Json<OddsByDateResponse>> {
+    let league = params.league.parse::<League>().map_err(|_| {
+        tracing::error!("Invalid league: {}", params.league);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    if params.date.is_empty() {
+        tracing::error!("Date parameter is required");
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    if league != League::Mlb {
+        tracing::error!("Odds not yet supported for league: {}", league);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let api_path = odds_by_date_path(league.clone(), Some(params.date.clone()));
+    let api_url = api_path.to_string();
+
+    let cache_key =
+        CacheKey::odds_by_date(use_case_state.provider(), &league, &params.date);
+    let use_cache = params.cache.unwrap_or(true);
+
+    if use_cache {
+        let mut cache = use_case_state.lock_cache().await;
+        if let Some(cached_data) = cache.get(&cache_key).await? {
+            tracing::debug!("Returning cached odds data for date: {}", params.date);
+            let response: OddsByDateResponse = serde_json::from_str(&cached_data)
+                .map_err(|e| {
+                    tracing::error!("Failed to deserialize cached data: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            return Ok(Json(response));
         }
     }
 
+    let raw_data =
+        fetch_odds_by_date_from_api(&api_url, &league, &use_case_state).await?;
+
+    let game_odds: Vec<GameOdds> = serde_json::from_str(&raw_data).map_err(|e| {
+        tracing::error!("Failed to parse odds JSON: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let response = OddsByDateResponse {
+        date: params.date.clone(),
+        data: game_odds,
+        games_count: 0, // Will be set from data length
+    };
+
+    let response = OddsByDateResponse {
+        games_count: response.data.len(),
+        ..response
+    };
+
+    {
+        let mut cache = use_case_state.lock_cache().await;
+        let serialized = serde_json::to_string(&response).map_err(|e| {
+            tracing::error!("Failed to serialize response: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        cache
+            .setx(
+                &cache_key,
+                &serialized,
+                use_case_state.config.cache.ttl.odds,
+            )
+            .await?;
+    }
+
+    tracing::info!(
+        "Successfully fetched and cached odds for date: {}",
+        params.date
+    );
+    Ok(Json(response))
+}
+
+async fn fetch_odds_by_date_from_api(
+    api_url: &str,
+    league: &League,
+    state: &UseCaseState,
+) -> Result<String> {
+    let api_key = &state.config.api.sportsdata_api_key;
+
+    let client = reqwest::Client::new();
+
+    tracing::info!(
+        "Making real API request to: {} for league: {}",
+        api_url,
+        league
+    );
+
+    let response = metered(
+        &state.usage,
+        Upstream::SportsData,
+        client.get(api_url).query(&[("key", &api_key)]),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        tracing::error!("API request failed with status {}: {}", status, error_text);
+        return Err(StatusCode::from_u16(status.as_u16())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+            .into());
+    }
+
     let body = response.text().await.map_err(|e| {
         tracing::error!("Failed to read response body: {}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    let twitter_response: TwitterSearchResponse =
-        serde_json::from_str(&body).map_err(|e| {
-            tracing::error!("Failed to parse Twitter API response: {}", e);
-            tracing::error!("Response body: {}", body);
-            StatusCode::INTERNAL_SERVER_ERROR
+    tracing::info!("Successfully fetched odds data from API");
+    Ok(body)
+}
+
+/// The client behind a request, added by [`rate_limit`]
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    /// Public id of the API key, `None` for anonymous requests
+    pub key_id: Option<String>,
+    pub tier: ApiTier,
+}
+
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Check a presented API key: the configured admin key or an issued one
+async fn resolve_api_key(state: &UseCaseState, key: &str) -> Result<ApiClient> {
+    let hash = token_hash(key);
+    let admin_key = &state.config.rate_limit.admin_key;
+    if !admin_key.is_empty() && hash == token_hash(admin_key) {
+        return Ok(ApiClient {
+            key_id: Some("admin".to_string()),
+            tier: ApiTier::Admin,
+        });
+    }
+
+    let stored = state
+        .lock_store()
+        .await
+        .get(CacheKey::api_key(&hash))
+        .await?;
+    let api_key: ApiKey = stored
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .ok_or_else(|| {
+            tracing::warn!("Rejected unknown API key");
+            StatusCode::UNAUTHORIZED
         })?;
+    Ok(ApiClient {
+        key_id: Some(api_key.key_id),
+        tier: api_key.tier,
+    })
+}
+
+fn client_ip(trust_forwarded_for: bool, request: &Request) -> String {
+    if trust_forwarded_for {
+        let forwarded = request
+            .headers()
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+            // The proxy appends the address it saw; earlier hops are
+            // whatever the client sent
+            .and_then(|value| value.rsplit(',').next())
+            .map(str::trim)
+            .filter(|ip| !ip.is_empty());
+        if let Some(ip) = forwarded {
+            return ip.to_string();
+        }
+    }
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn rate_limit_headers(headers: &mut HeaderMap, status: &RateLimitStatus) {
+    headers.insert(
+        HeaderName::from_static("ratelimit-limit"),
+        status.limit.into(),
+    );
+    headers.insert(
+        HeaderName::from_static("ratelimit-remaining"),
+        status.remaining.into(),
+    );
+    headers.insert(
+        HeaderName::from_static("ratelimit-reset"),
+        status.reset.into(),
+    );
+}
+
+/// Count a request against `subject`'s quota for the current window, or
+/// `None` when Redis is unreachable and quotas fail open
+async fn count_request(
+    state: &UseCaseState,
+    subject: &str,
+    limit: u64,
+) -> Option<(u64, RateLimitStatus)> {
+    let window_seconds = state.config.rate_limit.window_seconds;
+    let now = chrono::Utc::now().timestamp().max(0) as u64;
+    let counted = state
+        .lock_store()
+        .await
+        .incr(
+            CacheKey::rate_limit(subject, window_start(now, window_seconds)),
+            window_seconds,
+        )
+        .await;
+    match counted {
+        Ok(count) => Some((
+            count,
+            RateLimitStatus::after(count, limit, now, window_seconds),
+        )),
+        Err(e) => {
+            tracing::warn!("Rate limiting unavailable, letting request through: {}", e);
+            None
+        }
+    }
+}
+
+fn too_many_requests(subject: &str, status: &RateLimitStatus) -> Response {
+    tracing::warn!("Rate limited {}", subject);
+    let mut response =
+        (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded").into_response();
+    rate_limit_headers(response.headers_mut(), status);
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, status.reset.into());
+    response
+}
+
+/// Middleware for every route: counts the request against its IP, checks any
+/// `X-API-Key`, then holds the request to its key's quota, or its IP's without
+/// a key
+///
+/// The IP is counted before a key is looked up so that guessing keys spends
+/// the anonymous quota. Quotas fail open when Redis is unreachable; upstream
+/// budgets still apply.
+pub async fn rate_limit(
+    State(state): State<UseCaseState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response> {
+    let config = &state.config.rate_limit;
+    let presented = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string());
+    let exempt = matches!(
+        request.uri().path(),
+        "/health" | "/health/live" | "/health/ready"
+    );
+    let counting = config.enabled && !exempt;
+
+    let ip = format!("ip:{}", client_ip(config.trust_forwarded_for, &request));
+    let by_ip = match counting {
+        true => count_request(&state, &ip, config.anonymous_requests).await,
+        false => None,
+    };
+
+    let resolved = match presented {
+        Some(key) => Some(resolve_api_key(&state, &key).await),
+        None => None,
+    };
+    let (client, subject, counted) = match resolved {
+        Some(Ok(client)) => {
+            let subject = format!("key:{}", client.key_id.as_deref().unwrap_or_default());
+            let counted = match counting {
+                true => count_request(&state, &subject, client.tier.limit(config)).await,
+                false => None,
+            };
+            (client, subject, counted)
+        }
+        Some(Err(e)) => match by_ip {
+            Some((count, status)) if status.exceeded(count) => {
+                return Ok(too_many_requests(&ip, &status))
+            }
+            _ => return Err(e),
+        },
+        None => {
+            let client = ApiClient {
+                key_id: None,
+                tier: ApiTier::Anonymous,
+            };
+            (client, ip, by_ip)
+        }
+    };
+    request.extensions_mut().insert(client);
+
+    let Some((count, status)) = counted else {
+        return Ok(next.run(request).await);
+    };
+    if status.exceeded(count) {
+        return Ok(too_many_requests(&subject, &status));
+    }
+
+    let mut response = next.run(request).await;
+    rate_limit_headers(response.headers_mut(), &status);
+    Ok(response)
+}
+
+fn require_admin(client: &ApiClient) -> Result<()> {
+    if client.tier != ApiTier::Admin {
+        return Err(StatusCode::FORBIDDEN.into());
+    }
+    Ok(())
+}
 
-    tracing::info!(
-        "Successfully fetched {} tweets from Twitter API",
-        twitter_response.tweets.len()
-    );
+/// Issue an API key; the key itself is only ever in this response
+/// POST /api/v1/admin/api-keys
+pub async fn handle_issue_api_key_request(
+    State(state): State<UseCaseState>,
+    Extension(client): Extension<ApiClient>,
+    Json(request): Json<ApiKeyRequest>,
+) -> Result<(StatusCode, Json<IssuedApiKey>)> {
+    require_admin(&client)?;
+    let name = request.name.trim().to_string();
+    if name.is_empty() || request.tier == ApiTier::Anonymous {
+        tracing::warn!("Rejected API key request for {:?}", request.name);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
 
-    if let Ok(json_data) = serde_json::to_string(&twitter_response) {
-        let _ = state
-            .cache
-            .lock()
-            .await
-            .setx(
-                &cache_key,
-                &json_data,
-                state.config.cache.ttl.twitter_search,
-            )
-            .await;
+    let key = generate_api_key();
+    let hash = token_hash(&key);
+    let api_key = ApiKey {
+        key_id: random_token()[..12].to_string(),
+        name,
+        tier: request.tier,
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    // Kept until revoked: an expiring key would break its app without warning
+    {
+        let mut store = state.lock_store().await;
+        store
+            .set(CacheKey::api_key(&hash), &serde_json::to_string(&api_key)?)
+            .await?;
+        store
+            .set(CacheKey::api_key_id(&api_key.key_id), &hash)
+            .await?;
     }
+    tracing::info!(
+        "Issued {} API key {} for {}",
+        api_key.tier,
+        api_key.key_id,
+        api_key.name
+    );
 
-    Ok(Json(twitter_response))
+    Ok((
+        StatusCode::CREATED,
+        Json(IssuedApiKey {
+            key,
+            key_id: api_key.key_id,
+            name: api_key.name,
+            tier: api_key.tier,
+            created_at: api_key.created_at,
+        }),
+    ))
 }
 
-pub async fn handle_reddit_search_request(
+/// Revoke an API key by its public id
+/// DELETE /api/v1/admin/api-keys?key_id=...
+pub async fn handle_revoke_api_key_request(
+    Query(params): Query<RevokeApiKeyQuery>,
     State(state): State<UseCaseState>,
-    Query(params): Query<RedditSearchQuery>,
-) -> Result<Json<RedditSearchResponse>> {
-    tracing::info!("Reddit search request received with params: {:?}", params);
+    Extension(client): Extension<ApiClient>,
+) -> Result<StatusCode> {
+    require_admin(&client)?;
+    let mut cache = state.lock_store().await;
+    let hash = cache
+        .take(CacheKey::api_key_id(&params.key_id))
+        .await?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    cache.delete(CacheKey::api_key(&hash)).await?;
+    tracing::info!("Revoked API key {}", params.key_id);
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    let subreddit = params.subreddit.ok_or_else(|| {
-        tracing::error!("Missing required parameter: subreddit");
-        StatusCode::BAD_REQUEST
-    })?;
+/// The route a request matched, e.g. `/api/v1/scores`
+fn matched_route(request: &Request) -> String {
+    request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string())
+}
 
-    let subreddit_clean = subreddit.trim().trim_start_matches("r/");
-    let game_id = params.game_id.unwrap_or_default();
-    let fetch_limit = 100;
-    let output_limit = params
-        .limit
-        .unwrap_or(state.config.api.reddit_api.default_comment_limit);
-    let sort_kind = params.kind.unwrap_or_else(|| "new".to_string());
-    let bypass_cache = params.cache.unwrap_or(true); // Default to using cache
+/// The request's `league` query parameter if it names a league arb knows,
+/// "other" if it doesn't, or "all" without one; keeps label values bounded
+fn request_league(request: &Request) -> String {
+    request
+        .uri()
+        .query()
+        .and_then(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .find(|(key, _)| key == "league")
+                .map(|(_, value)| {
+                    value
+                        .to_lowercase()
+                        .parse::<League>()
+                        .map(|league| league.to_string())
+                        .unwrap_or_else(|_| "other".to_string())
+                })
+        })
+        .unwrap_or_else(|| "all".to_string())
+}
 
-    tracing::info!(
-        "Reddit search for subreddit: {}, game_id: {}, fetch_limit: {}, output_limit: {}, bypass_cache: {}",
-        subreddit_clean,
-        game_id,
-        fetch_limit,
-        output_limit,
-        !bypass_cache
+/// Middleware for every route: runs the handler in a span named for its
+/// route, with the league and response status as attributes
+pub async fn trace_handler(request: Request, next: Next) -> Response {
+    let route = matched_route(&request);
+    let span = tracing::info_span!(
+        "handler",
+        otel.name = %route,
+        http.route = %route,
+        league = %request_league(&request),
+        http.response.status_code = tracing::field::Empty,
+        otel.status_code = tracing::field::Empty,
     );
 
-    let comments_cache_key = format!("reddit:thread_comments:{}", subreddit_clean);
-
-    if bypass_cache {
-        let comments_cache_key = format!("reddit:thread_comments:{}", subreddit_clean);
-        if let Ok(Some(cached_data)) =
-            state.cache.lock().await.get(&comments_cache_key).await
-        {
-            if let Ok(reddit_response) =
-                serde_json::from_str::<RedditSearchResponse>(&cached_data)
-            {
-                tracing::debug!("Returning cached Reddit comments result");
-                return Ok(Json(reddit_response));
-            }
-        }
-    } else {
-        tracing::info!("Cache bypass requested - fetching fresh data from Reddit API");
+    let response = next.run(request).instrument(span.clone()).await;
+    span.record("http.response.status_code", response.status().as_u16());
+    if response.status().is_server_error() {
+        span.record("otel.status_code", "ERROR");
     }
+    response
+}
 
-    let thread_cache_key = format!("reddit:thread:{}", subreddit_clean);
-    let game_thread_id = if let Ok(Some(cached_thread_id)) =
-        state.cache.lock().await.get(&thread_cache_key).await
-    {
-        if let Ok(thread_id) = serde_json::from_str::<String>(&cached_thread_id) {
-            tracing::debug!("Found cached game thread ID: {}", thread_id);
-            Some(thread_id)
-        } else {
-            None
-        }
-    } else {
-        tracing::info!(
-            "No cached game thread ID found for subreddit: {}",
-            subreddit_clean
-        );
-        None
+/// Middleware for every API route: counts upstream calls and cache lookups
+/// made while serving the request against its route and league, and
+/// writes the counters to Redis every `usage.flush_seconds`
+pub async fn track_usage(
+    State(state): State<UseCaseState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let scope = UsageScope {
+        usage: state.usage.clone(),
+        endpoint: matched_route(&request),
+        league: request_league(&request),
     };
 
-    let game_thread_id = match game_thread_id {
-        Some(id) => id,
-        None => {
-            tracing::error!("No game thread ID found for subreddit: {}", subreddit_clean);
-            return Err(StatusCode::NOT_FOUND.into());
-        }
-    };
+    let response = USAGE_SCOPE.scope(scope, next.run(request)).await;
+    flush_usage(&state, false).await;
+    response
+}
+
+/// Middleware for every route: request counts and latency by matched
+/// route, and requests in flight
+pub async fn track_metrics(
+    State(state): State<UseCaseState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().to_string();
+    let route = matched_route(&request);
+
+    let _in_flight = state.metrics.start_request();
+    let started = Instant::now();
+    let response = next.run(request).await;
+    state.metrics.observe_request(
+        &method,
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
 
-    let client_id = &state.config.api.reddit_oauth.client_id;
-    let client_secret = &state.config.api.reddit_oauth.client_secret;
-    let user_agent = &state.config.api.reddit_api.user_agent;
+/// Prometheus metrics in the text exposition format, for admin keys only
+/// GET /metrics
+pub async fn handle_metrics_request(
+    State(state): State<UseCaseState>,
+    Extension(client): Extension<ApiClient>,
+) -> Result<Response> {
+    require_admin(&client)?;
+    let body = state.metrics.render()?;
+    Ok(([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], body).into_response())
+}
 
-    let token_url = "https://www.reddit.com/api/v1/access_token";
-    let token_params = [("grant_type", "client_credentials")];
+/// Add this instance's pending counters to today's rollup; month totals
+/// are counted in the store as each call is made
+async fn flush_usage(state: &UseCaseState, force: bool) {
+    let now = Instant::now();
+    let every = Duration::from_secs(state.config.usage.flush_seconds);
+    if !force && !state.usage.flush_due(every, now) {
+        return;
+    }
 
-    let token_response = reqwest::Client::new()
-        .post(token_url)
-        .basic_auth(client_id, Some(&client_secret))
-        .form(&token_params)
-        .header("User-Agent", user_agent)
-        .send()
+    let pending = state.usage.take_pending(now);
+    if pending.is_empty() {
+        return;
+    }
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let written = state
+        .lock_store()
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get Reddit token: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .increment_fields(
+            CacheKey::usage_day(&today),
+            &rollup_fields(&pending),
+            state.config.usage.retention_days * 86400,
+        )
+        .await;
 
-    if !token_response.status().is_success() {
-        tracing::error!(
-            "Reddit token request failed with status: {}",
-            token_response.status()
+    if let Err(e) = written {
+        tracing::warn!(
+            "Failed to write upstream usage, keeping it for later: {}",
+            e
         );
-        return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+        state.usage.restore(pending);
     }
+}
 
-    let token_data: serde_json::Value = token_response.json().await.map_err(|e| {
-        tracing::error!("Failed to parse Reddit token response: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+/// Upstream spend this month against the caps, and daily rollups by
+/// upstream, route and league
+/// GET /api/v1/admin/usage?days=...
+pub async fn handle_usage_report_request(
+    Query(params): Query<UsageQuery>,
+    State(state): State<UseCaseState>,
+    Extension(client): Extension<ApiClient>,
+) -> Result<Json<UsageReport>> {
+    require_admin(&client)?;
+    flush_usage(&state, true).await;
+
+    let config = &state.config.usage;
+    let today = chrono::Utc::now().date_naive();
+    let month = today.format("%Y-%m").to_string();
+    let days = params
+        .days
+        .unwrap_or(7)
+        .clamp(1, config.retention_days.max(1) as u32);
+
+    let mut store = state.lock_store().await;
+    let month_totals = store.get_fields(CacheKey::usage_month(&month)).await?;
+    let upstreams = Upstream::ALL
+        .into_iter()
+        .map(|upstream| {
+            let month_calls = month_totals
+                .get(upstream.as_str())
+                .copied()
+                .unwrap_or_default();
+            let monthly_cap = state.usage.monthly_cap(upstream);
+            UpstreamSpend {
+                upstream,
+                month_calls,
+                monthly_cap,
+                remaining: monthly_cap.map(|cap| cap.saturating_sub(month_calls)),
+            }
+        })
+        .collect();
 
-    let access_token = token_data["access_token"].as_str().ok_or_else(|| {
-        tracing::error!("Missing access token in Reddit response");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let mut rollups = Vec::new();
+    for offset in 0..days {
+        let date = (today - chrono::Duration::days(offset as i64))
+            .format("%Y-%m-%d")
+            .to_string();
+        let fields = store.get_fields(CacheKey::usage_day(&date)).await?;
+        rollups.push(UsageDay {
+            date,
+            entries: parse_rollup(fields),
+        });
+    }
 
-    let mut all_posts = Vec::new();
-    let mut total_posts = 0;
+    Ok(Json(UsageReport {
+        month,
+        upstreams,
+        days: rollups,
+    }))
+}
 
-    let search_url = format!(
-        "https://oauth.reddit.com/r/{}/comments/{}.json?sort={}&limit={}",
-        subreddit_clean, game_thread_id, sort_kind, fetch_limit
-    );
+/// Longest device id accepted for a guest account
+const MAX_DEVICE_ID_LEN: usize = 256;
+
+/// The signed-in user behind a request, added by [`require_auth`]
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    /// The email for Apple and Google users, `guest:...` for guests
+    pub user_id: String,
+    pub session_id: String,
+    pub provider: String,
+    /// When the session started, as a Unix timestamp
+    pub signed_in_at: i64,
+}
 
-    let search_response = reqwest::Client::new()
-        .get(&search_url)
-        .bearer_auth(access_token)
-        .header("User-Agent", user_agent)
-        .send()
+async fn load_auth_session(
+    state: &UseCaseState,
+    session_id: &str,
+) -> Option<AuthSession> {
+    let stored = state
+        .lock_store()
         .await
+        .get(CacheKey::auth_session(session_id))
+        .await;
+    match stored {
+        Ok(data) => data.and_then(|data| serde_json::from_str(&data).ok()),
+        Err(e) => {
+            tracing::error!("Failed to load session: {}", e);
+            None
+        }
+    }
+}
+
+/// Check a `Bearer` access token: signature, expiry and that its session
+/// hasn't been revoked
+async fn authenticate(state: &UseCaseState, headers: &HeaderMap) -> Result<AuthUser> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    let claims = JwtUtils::new(&state.config.api.jwt_secret)
+        .verify_token(token.trim())
         .map_err(|e| {
-            tracing::error!(
-                "Failed to search Reddit subreddit {}: {}",
-                subreddit_clean,
-                e
-            );
-            StatusCode::INTERNAL_SERVER_ERROR
+            tracing::debug!("Rejected access token: {}", e);
+            StatusCode::UNAUTHORIZED
         })?;
 
-    if !search_response.status().is_success() {
-        tracing::warn!(
-            "Reddit search failed for subreddit {} with status: {}",
-            subreddit_clean,
-            search_response.status()
-        );
-        return Ok(Json(RedditSearchResponse {
-            posts: vec![],
-            total_posts: 0,
-            subreddits_searched: vec![subreddit_clean.to_string()],
-            game_id,
-            search_timestamp: chrono::Utc::now().to_rfc3339(),
-        }));
+    let session = load_auth_session(state, &claims.sid)
+        .await
+        .filter(|session| session.user_id == claims.sub)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    Ok(AuthUser {
+        user_id: session.user_id,
+        session_id: session.session_id,
+        provider: session.provider,
+        signed_in_at: session.created_at,
+    })
+}
+
+/// Middleware for routes that need a signed-in user: 401 without a valid
+/// access token, otherwise the [`AuthUser`] is available as an extension
+pub async fn require_auth(
+    State(state): State<UseCaseState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response> {
+    let user = authenticate(&state, request.headers()).await?;
+    request.extensions_mut().insert(user);
+    Ok(next.run(request).await)
+}
+
+/// Store a session and its current refresh token, and sign an access token
+async fn save_session(
+    state: &UseCaseState,
+    session: &AuthSession,
+    refresh_token: String,
+) -> Result<TokenPair> {
+    let auth = &state.config.auth;
+    let session_json = serde_json::to_string(session)?;
+    {
+        let mut cache = state.lock_store().await;
+        cache
+            .setx(
+                CacheKey::auth_session(&session.session_id),
+                &session_json,
+                auth.refresh_token_seconds,
+            )
+            .await?;
+        cache
+            .setx(
+                CacheKey::refresh_token(&session.refresh_hash),
+                &session.session_id,
+                auth.refresh_token_seconds,
+            )
+            .await?;
     }
 
-    let comments_data: Vec<serde_json::Value> =
-        search_response.json().await.map_err(|e| {
-            tracing::error!(
-                "Failed to parse Reddit comments response for {}: {}",
-                subreddit_clean,
-                e
-            );
+    let access_token = JwtUtils::new(&state.config.api.jwt_secret)
+        .generate_token(
+            &session.user_id,
+            &session.session_id,
+            auth.access_token_seconds,
+        )
+        .map_err(|e| {
+            tracing::error!("Failed to sign access token: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
+    Ok(TokenPair {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: auth.access_token_seconds,
+        refresh_token,
+        refresh_expires_in: auth.refresh_token_seconds,
+    })
+}
 
-    if comments_data.len() >= 2 {
-        let post_data = &comments_data[0];
-        let comments_listing = &comments_data[1];
-
-        let mut reddit_post = RedditPost {
-            id: game_thread_id.clone(),
-            title: post_data["data"]["title"]
-                .as_str()
-                .unwrap_or("Game Thread")
-                .to_string(),
-            author: post_data["data"]["author"]
-                .as_str()
-                .unwrap_or("Unknown")
-                .to_string(),
-            selftext: post_data["data"]["selftext"]
-                .as_str()
-                .unwrap_or("Game thread comments")
-                .to_string(),
-            score: post_data["data"]["score"].as_i64().unwrap_or(0) as i32,
-            num_comments: post_data["data"]["num_comments"].as_i64().unwrap_or(0) as i32,
-            created_utc: post_data["data"]["created_utc"].as_f64().unwrap_or(0.0),
-            permalink: post_data["data"]["permalink"]
-                .as_str()
-                .unwrap_or("")
-                .to_string(),
-            subreddit: subreddit_clean.to_string(),
-            url: post_data["data"]["url"].as_str().map(|s| s.to_string()),
-            is_self: post_data["data"]["is_self"].as_bool().unwrap_or(true),
-            team: None,
-            comments: Vec::new(),
-        };
-
-        if let Some(comments_array) = comments_listing["data"]["children"].as_array() {
-            tracing::info!(
-                "Found {} comments for game thread {}, sampling {} for output",
-                comments_array.len(),
-                game_thread_id,
-                output_limit
-            );
-            let mut post_comments = Vec::new();
-
-            let sampled_comments = if comments_array.len() <= output_limit as usize {
-                comments_array.iter().collect::<Vec<_>>()
-            } else {
-                use rand::seq::SliceRandom;
-                use rand::thread_rng;
-
-                let mut rng = thread_rng();
-                comments_array
-                    .choose_multiple(&mut rng, output_limit as usize)
-                    .collect::<Vec<_>>()
-            };
+/// Drop a session and its refresh token; access tokens fail from then on
+async fn revoke_session(state: &UseCaseState, session: &AuthSession) -> Result<()> {
+    let mut cache = state.lock_store().await;
+    cache
+        .delete(CacheKey::refresh_token(&session.refresh_hash))
+        .await?;
+    cache
+        .delete(CacheKey::auth_session(&session.session_id))
+        .await
+}
 
-            tracing::info!(
-                "Sampled {} comments from {} total comments",
-                sampled_comments.len(),
-                comments_array.len()
-            );
+/// One-time code a sign-in callback passes to the client, exchanged for
+/// tokens at `/api/v1/auth/token` so tokens never appear in a redirect URL
+async fn issue_auth_code(
+    state: &UseCaseState,
+    user: &User,
+    oauth_session: &OAuthSession,
+) -> Result<String> {
+    let code = random_token();
+    let sign_in = SignInCode {
+        session: AuthSession::start(&user.email, &user.provider).0,
+        code_challenge: oauth_session.client_challenge.clone(),
+    };
+    state
+        .lock_store()
+        .await
+        .setx(
+            CacheKey::auth_code(&code),
+            &serde_json::to_string(&sign_in)?,
+            state.config.cache.ttl.oauth_state,
+        )
+        .await?;
+    Ok(code)
+}
 
-            for comment_child in sampled_comments {
-                if let Ok(comment_data) =
-                    serde_json::from_value::<RedditChild>(comment_child.clone())
-                {
-                    if let Some(body) = &comment_data.data.body {
-                        let comment = RedditComment {
-                            id: comment_data.data.id.clone().unwrap_or_default(),
-                            author: comment_data.data.author.clone().unwrap_or_default(),
-                            content: body.clone(),
-                            timestamp: if let Some(created_utc) =
-                                comment_data.data.created_utc
-                            {
-                                chrono::DateTime::from_timestamp(created_utc as i64, 0)
-                                    .unwrap_or_else(chrono::Utc::now)
-                                    .to_rfc3339()
-                            } else {
-                                chrono::Utc::now().to_rfc3339()
-                            },
-                            score: comment_data.data.score.unwrap_or(0) as i32,
-                            permalink: format!(
-                                "https://reddit.com{}",
-                                comment_data.data.permalink.clone().unwrap_or_default()
-                            ),
-                            subreddit: comment_data
-                                .data
-                                .subreddit
-                                .clone()
-                                .unwrap_or_default(),
-                            team: Some("home".to_string()), // Default team assignment
-                            depth: 0,
-                            replies: comment_data
-                                .data
-                                .extract_comments(Some("home".to_string()), 1),
-                        };
-                        post_comments.push(comment);
-                    }
-                }
+/// Exchange a sign-in callback's one-time code, and the PKCE verifier sign-in
+/// started with, for tokens
+///
+/// Sent with a guest's access token, the guest's favorites and preferences
+/// move to the signed-in account and the guest session ends.
+/// POST /api/v1/auth/token
+pub async fn handle_auth_token_request(
+    State(state): State<UseCaseState>,
+    headers: HeaderMap,
+    Json(request): Json<AuthCodeRequest>,
+) -> Result<Json<TokenPair>> {
+    let stored = state
+        .lock_store()
+        .await
+        .take(CacheKey::auth_code(&request.code))
+        .await?;
+    let mut session = stored
+        .and_then(|data| serde_json::from_str::<SignInCode>(&data).ok())
+        .and_then(|sign_in| sign_in.redeem(&request.code_verifier))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if headers.contains_key(header::AUTHORIZATION) {
+        match authenticate(&state, &headers).await {
+            Ok(guest) if guest.provider == GUEST_PROVIDER => {
+                merge_guest_account(&state, &guest, &session.user_id).await?
             }
-
-            reddit_post.comments = post_comments;
+            Ok(_) => {}
+            Err(_) => tracing::debug!("Ignoring invalid token on sign-in exchange"),
         }
-
-        all_posts.push(reddit_post);
-        total_posts = 1;
     }
 
-    let reddit_response = RedditSearchResponse {
-        posts: all_posts,
-        total_posts,
-        subreddits_searched: vec![subreddit_clean.to_string()],
-        game_id,
-        search_timestamp: chrono::Utc::now().to_rfc3339(),
-    };
+    // The code's placeholder refresh hash was never handed out
+    let refresh_token = session.rotate();
+    Ok(Json(save_session(&state, &session, refresh_token).await?))
+}
 
-    if let Ok(json_data) = serde_json::to_string(&reddit_response) {
-        let _ = state
-            .cache
-            .lock()
+/// Move a guest's favorites and preferences to `user_id` and end the guest
+/// session; conflicts resolve the same way whichever order devices sign in
+async fn merge_guest_account(
+    state: &UseCaseState,
+    guest: &AuthUser,
+    user_id: &str,
+) -> Result<()> {
+    let favorites =
+        load_user_data::<UserFavorites>(state, CacheKey::user_favorites(user_id))
             .await
-            .setx(
-                &comments_cache_key,
-                &json_data,
-                state.config.cache.ttl.reddit_thread_comments,
-            )
-            .await;
-    }
-
-    Ok(Json(reddit_response))
-}
+            .merge_guest(
+                load_user_data(state, CacheKey::user_favorites(&guest.user_id)).await,
+            );
+    let preferences =
+        load_user_data::<UserPreferences>(state, CacheKey::user_preferences(user_id))
+            .await
+            .merge_guest(
+                load_user_data(state, CacheKey::user_preferences(&guest.user_id)).await,
+            );
+    store_user_data(
+        state,
+        CacheKey::user_favorites(user_id),
+        &favorites,
+        state.config.cache.ttl.user_favorites,
+    )
+    .await?;
+    store_user_data(
+        state,
+        CacheKey::user_preferences(user_id),
+        &preferences,
+        state.config.cache.ttl.user_preferences,
+    )
+    .await?;
 
-#[derive(Debug, Deserialize)]
-pub struct RedditThreadQuery {
-    pub subreddit: String,
-    pub league: String,
-    #[serde(default)]
-    pub cache: Option<bool>,
+    {
+        let mut cache = state.lock_store().await;
+        cache
+            .delete(CacheKey::user_favorites(&guest.user_id))
+            .await?;
+        cache
+            .delete(CacheKey::user_preferences(&guest.user_id))
+            .await?;
+    }
+    if let Some(session) = load_auth_session(state, &guest.session_id).await {
+        revoke_session(state, &session).await?;
+    }
+    tracing::info!("Merged guest {} into {}", guest.user_id, user_id);
+    Ok(())
 }
 
-pub async fn handle_reddit_thread_request(
+/// Start an anonymous account bound to the calling device
+/// POST /api/v1/auth/guest
+pub async fn handle_guest_signup_request(
     State(state): State<UseCaseState>,
-    Query(params): Query<RedditThreadQuery>,
-) -> Result<Json<serde_json::Value>> {
-    tracing::info!("Reddit thread request received with params: {:?}", params);
-
-    let subreddit_clean = params.subreddit.trim().trim_start_matches("r/");
-
-    tracing::info!(
-        "Reddit thread search for subreddit: {} (league: {})",
-        subreddit_clean,
-        params.league
-    );
+    Json(request): Json<GuestRequest>,
+) -> std::result::Result<Json<TokenPair>, (StatusCode, String)> {
+    let device_id = request.device_id.trim();
+    if device_id.is_empty() || device_id.len() > MAX_DEVICE_ID_LEN {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("device_id must be 1 to {} bytes", MAX_DEVICE_ID_LEN),
+        ));
+    }
 
-    let cache_key = format!("reddit:thread:{}", subreddit_clean);
-    let use_cache = params.cache.unwrap_or(true);
+    let (session, refresh_token) = AuthSession::guest(device_id);
+    let tokens = save_session(&state, &session, refresh_token)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to start guest session: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to start guest session".to_string(),
+            )
+        })?;
+    Ok(Json(tokens))
+}
 
-    if use_cache {
-        if let Ok(Some(_cached_data)) = state.cache.lock().await.get(&cache_key).await {
-            tracing::debug!("Returning cached Reddit thread result");
-            return Ok(Json(serde_json::json!({"success": true})));
+/// Trade a refresh token for new tokens; each refresh token works once
+///
+/// Presenting one that's already been rotated out means it leaked, so the
+/// whole session is revoked.
+/// POST /api/v1/auth/refresh
+pub async fn handle_auth_refresh_request(
+    State(state): State<UseCaseState>,
+    Json(request): Json<RefreshRequest>,
+) -> Result<Json<TokenPair>> {
+    let hash = token_hash(&request.refresh_token);
+
+    // Check the device before the token is spent, so a stranger holding a
+    // guest's refresh token can't burn it
+    let live = state
+        .lock_store()
+        .await
+        .get(CacheKey::refresh_token(&hash))
+        .await?;
+    if let Some(session_id) = live {
+        let session = load_auth_session(&state, &session_id).await;
+        if session.is_some_and(|session| {
+            !session.allows_device(request.device_id.as_deref().map(str::trim))
+        }) {
+            tracing::warn!("Guest refresh token presented from another device");
+            return Err(StatusCode::UNAUTHORIZED.into());
         }
-    } else {
-        tracing::info!("Cache bypass requested - fetching fresh data from Reddit API");
     }
 
-    let client_id = &state.config.api.reddit_oauth.client_id;
-    let client_secret = &state.config.api.reddit_oauth.client_secret;
-    let user_agent = &state.config.api.reddit_api.user_agent;
-
-    let token_url = "https://www.reddit.com/api/v1/access_token";
-    let token_params = [("grant_type", "client_credentials")];
-
-    let token_response = reqwest::Client::new()
-        .post(token_url)
-        .basic_auth(client_id, Some(&client_secret))
-        .form(&token_params)
-        .header("User-Agent", user_agent)
-        .send()
+    // Spent and marked used in one step, so two concurrent refreshes can't
+    // both rotate the same token
+    let (session_id, reused) = state
+        .lock_store()
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get Reddit token: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .spend(
+            CacheKey::refresh_token(&hash),
+            CacheKey::refresh_token_used(&hash),
+            state.config.auth.refresh_token_seconds,
+        )
+        .await?
+        .ok_or(StatusCode::UNAUTHORIZED)?;
 
-    if !token_response.status().is_success() {
-        tracing::error!(
-            "Reddit token request failed with status: {}",
-            token_response.status()
+    let mut session = load_auth_session(&state, &session_id)
+        .await
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    if reused || session.refresh_hash != hash {
+        tracing::warn!(
+            "Refresh token reused for {}; revoking session",
+            session.user_id
         );
-        return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+        revoke_session(&state, &session).await?;
+        return Err(StatusCode::UNAUTHORIZED.into());
     }
 
-    let token_data: serde_json::Value = token_response.json().await.map_err(|e| {
-        tracing::error!("Failed to parse Reddit token response: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let refresh_token = session.rotate();
+    Ok(Json(save_session(&state, &session, refresh_token).await?))
+}
 
-    let access_token = token_data["access_token"].as_str().ok_or_else(|| {
-        tracing::error!("Missing access token in Reddit response");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+/// Sign out: revoke the session behind the access token
+/// POST /api/v1/auth/logout
+pub async fn handle_logout_request(
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<StatusCode> {
+    if let Some(session) = load_auth_session(&state, &user.session_id).await {
+        revoke_session(&state, &session).await?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    let query = match params.league.to_lowercase().as_str() {
-        "mlb" => match subreddit_clean.to_lowercase().as_str() {
-            "dodgers" | "mariners" | "brewers" => "Game Chat",
-            "chicubs" => "GDT:",
-            _ => "Game Thread",
-        },
-        _ => "Game Thread",
-    };
+/// The signed-in user
+/// GET /api/v1/me
+pub async fn handle_me_request(Extension(user): Extension<AuthUser>) -> Json<MeResponse> {
+    Json(MeResponse {
+        email: (user.provider != GUEST_PROVIDER).then(|| user.user_id.clone()),
+        user_id: user.user_id,
+        provider: user.provider,
+        signed_in_at: chrono::DateTime::from_timestamp(user.signed_in_at, 0)
+            .map(|signed_in_at| signed_in_at.to_rfc3339())
+            .unwrap_or_default(),
+    })
+}
 
-    let search_url = format!(
-        "https://oauth.reddit.com/r/{}/search.json?q={}&restrict_sr=1&sort=new&limit=10",
-        subreddit_clean,
-        urlencoding::encode(query)
-    );
+/// A user's stored preferences or favorites, or the default
+async fn load_user_data<T>(state: &UseCaseState, cache_key: CacheKey) -> T
+where
+    T: serde::de::DeserializeOwned + Default,
+{
+    let cached = state.lock_store().await.get(&cache_key).await;
+    match cached {
+        Ok(Some(data)) => serde_json::from_str(&data).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring unreadable {}: {}", cache_key.as_ref(), e);
+            T::default()
+        }),
+        Ok(None) => T::default(),
+        Err(e) => {
+            tracing::warn!("Failed to load {}: {}", cache_key.as_ref(), e);
+            T::default()
+        }
+    }
+}
 
-    let search_response = reqwest::Client::new()
-        .get(&search_url)
-        .bearer_auth(access_token)
-        .header("User-Agent", user_agent)
-        .send()
+/// Store a user's preferences or favorites for `ttl`, restarted on every
+/// update so they're kept as long as they're in use
+async fn store_user_data<T: Serialize>(
+    state: &UseCaseState,
+    cache_key: CacheKey,
+    data: &T,
+    ttl: u64,
+) -> Result<()> {
+    let json_data = serde_json::to_string(data)?;
+    state
+        .lock_store()
+        .await
+        .setx(&cache_key, &json_data, ttl)
         .await
         .map_err(|e| {
-            tracing::error!(
-                "Failed to search Reddit subreddit {}: {}",
-                subreddit_clean,
-                e
-            );
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+            tracing::error!("Failed to store {}: {}", cache_key.as_ref(), e);
+            StatusCode::INTERNAL_SERVER_ERROR.into()
+        })
+}
 
-    if !search_response.status().is_success() {
-        tracing::warn!(
-            "Reddit search failed for subreddit {} with status: {}",
-            subreddit_clean,
-            search_response.status()
-        );
-        return Err(StatusCode::NOT_FOUND.into());
-    }
+/// Moderation strictness for the requesting user, or the configured default
+/// for anonymous requests and users who haven't picked one
+async fn moderation_strictness(state: &UseCaseState, headers: &HeaderMap) -> Strictness {
+    let default = state.config.moderation.default_strictness;
+    let Ok(user) = authenticate(state, headers).await else {
+        return default;
+    };
+    load_user_data::<UserPreferences>(state, CacheKey::user_preferences(&user.user_id))
+        .await
+        .moderation
+        .unwrap_or(default)
+}
 
-    let search_data: RedditListing = search_response.json().await.map_err(|e| {
-        tracing::error!(
-            "Failed to parse Reddit search response for {}: {}",
-            subreddit_clean,
-            e
-        );
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+/// The signed-in user's preferences
+/// GET /api/v1/preferences
+pub async fn handle_get_preferences_request(
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+) -> Json<UserPreferences> {
+    Json(load_user_data(&state, CacheKey::user_preferences(&user.user_id)).await)
+}
 
-    if let Some(game_thread) = find_live_game_thread(&search_data) {
-        tracing::info!(
-            "Found game thread using query '{}': {} (ID: {})",
-            query,
-            game_thread.title,
-            game_thread.id
-        );
+/// Replace the signed-in user's preferences
+/// PUT /api/v1/preferences
+pub async fn handle_put_preferences_request(
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+    Json(preferences): Json<UserPreferences>,
+) -> Result<Json<UserPreferences>> {
+    let preferences = valid_preferences(preferences)?;
+    store_user_data(
+        &state,
+        CacheKey::user_preferences(&user.user_id),
+        &preferences,
+        state.config.cache.ttl.user_preferences,
+    )
+    .await?;
+    Ok(Json(preferences))
+}
 
-        if let Ok(json_data) = serde_json::to_string(&game_thread.id) {
-            let _ = state
-                .cache
-                .lock()
-                .await
-                .setx(&cache_key, &json_data, state.config.cache.ttl.reddit_thread)
-                .await;
-        }
+fn valid_preferences(preferences: UserPreferences) -> Result<UserPreferences> {
+    preferences.validate().map_err(|e| {
+        tracing::warn!("Rejected preferences: {}", e);
+        StatusCode::BAD_REQUEST.into()
+    })
+}
 
-        Ok(Json(serde_json::json!({"success": true})))
-    } else {
-        tracing::warn!("No game thread found for subreddit: {}", subreddit_clean);
-        Err(StatusCode::NOT_FOUND.into())
-    }
+/// Change only the preferences present in the body
+/// PATCH /api/v1/preferences
+pub async fn handle_patch_preferences_request(
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+    Json(changes): Json<UserPreferences>,
+) -> Result<Json<UserPreferences>> {
+    let cache_key = CacheKey::user_preferences(&user.user_id);
+    let stored: UserPreferences = load_user_data(&state, cache_key.clone()).await;
+    let preferences = valid_preferences(stored.update(changes))?;
+    store_user_data(
+        &state,
+        cache_key,
+        &preferences,
+        state.config.cache.ttl.user_preferences,
+    )
+    .await?;
+    Ok(Json(preferences))
 }
 
-pub async fn handle_odds_by_date_request(
-    Query(params): Query<OddsByDateQuery>,
-    State(use_case_state): State<UseCaseState>,
-) -> Result<Json<OddsByDateResponse>> {
-    let league = params.league.parse::<League>().map_err(|_| {
-        tracing::error!("Invalid league: {}", params.league);
-        StatusCode::BAD_REQUEST
-    })?;
+/// Reset the signed-in user's preferences to the defaults
+/// DELETE /api/v1/preferences
+pub async fn handle_delete_preferences_request(
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<StatusCode> {
+    state
+        .lock_store()
+        .await
+        .delete(CacheKey::user_preferences(&user.user_id))
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    if params.date.is_empty() {
-        tracing::error!("Date parameter is required");
+/// The signed-in user's favorite teams
+/// GET /api/v1/favorites
+pub async fn handle_get_favorites_request(
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+) -> Json<UserFavorites> {
+    Json(load_user_data(&state, CacheKey::user_favorites(&user.user_id)).await)
+}
+
+/// Replace the signed-in user's favorite teams; teams already on the list
+/// keep when they were added
+/// PUT /api/v1/favorites
+pub async fn handle_put_favorites_request(
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<FavoritesRequest>,
+) -> Result<Json<UserFavorites>> {
+    let current: UserFavorites =
+        load_user_data(&state, CacheKey::user_favorites(&user.user_id)).await;
+    let now = chrono::Utc::now().timestamp();
+    let teams = request
+        .teams
+        .into_iter()
+        .map(|team| {
+            let mut favorite = favorite_team(team, now)?;
+            if let Some(existing) = current.teams.iter().find(|existing| {
+                existing.league == favorite.league && existing.team == favorite.team
+            }) {
+                favorite.added_at = existing.added_at;
+            }
+            Ok(favorite)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // Merging with nothing dedupes and sorts
+    let favorites = UserFavorites { teams }.merge_guest(UserFavorites::default());
+    store_user_data(
+        &state,
+        CacheKey::user_favorites(&user.user_id),
+        &favorites,
+        state.config.cache.ttl.user_favorites,
+    )
+    .await?;
+    Ok(Json(favorites))
+}
+
+/// A favorite from a request: a known league and a team key, normalized
+fn favorite_team(team: FavoriteTeamRequest, added_at: i64) -> Result<FavoriteTeam> {
+    let league = team.league.trim().to_lowercase();
+    league
+        .parse::<League>()
+        .map_err(|_| Error::InvalidLeague(team.league.clone()))?;
+    let team = team.team.trim().to_uppercase();
+    if team.is_empty() {
+        tracing::warn!("Rejected favorite without a team");
         return Err(StatusCode::BAD_REQUEST.into());
     }
+    Ok(FavoriteTeam {
+        league,
+        team,
+        added_at,
+    })
+}
 
-    if league != League::Mlb {
-        tracing::error!("Odds not yet supported for league: {}", league);
-        return Err(StatusCode::BAD_REQUEST.into());
+/// Follow one more team; adding a team that's already a favorite is a no-op
+/// POST /api/v1/favorites
+pub async fn handle_add_favorite_request(
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<FavoriteTeamRequest>,
+) -> Result<Json<UserFavorites>> {
+    let favorite = favorite_team(request, chrono::Utc::now().timestamp())?;
+    let cache_key = CacheKey::user_favorites(&user.user_id);
+    let mut favorites: UserFavorites = load_user_data(&state, cache_key.clone()).await;
+    if favorites.add(favorite) {
+        store_user_data(
+            &state,
+            cache_key,
+            &favorites,
+            state.config.cache.ttl.user_favorites,
+        )
+        .await?;
     }
+    Ok(Json(favorites))
+}
 
-    let api_path = odds_by_date_path(league.clone(), Some(params.date.clone()));
-    let api_url = api_path.to_string();
+/// Stop following a team
+/// DELETE /api/v1/favorites?league=mlb&team=CIN
+pub async fn handle_remove_favorite_request(
+    Query(params): Query<FavoriteTeamRequest>,
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<UserFavorites>> {
+    let cache_key = CacheKey::user_favorites(&user.user_id);
+    let mut favorites: UserFavorites = load_user_data(&state, cache_key.clone()).await;
+    if !favorites.remove(params.league.trim(), params.team.trim()) {
+        return Err(StatusCode::NOT_FOUND.into());
+    }
+    store_user_data(
+        &state,
+        cache_key,
+        &favorites,
+        state.config.cache.ttl.user_favorites,
+    )
+    .await?;
+    Ok(Json(favorites))
+}
 
-    let cache_key =
-        CacheKey::odds_by_date(use_case_state.provider(), &league, &params.date);
-    let use_cache = params.cache.unwrap_or(true);
+/// Days `/me/feed` covers when the client doesn't say
+const DEFAULT_FEED_DAYS: u32 = 7;
+/// Most days `/me/feed` covers; each is a schedule lookup per league
+const MAX_FEED_DAYS: u32 = 14;
 
-    if use_cache {
-        let mut cache = use_case_state.cache.lock().await;
-        if let Some(cached_data) = cache.get(&cache_key).await? {
-            tracing::debug!("Returning cached odds data for date: {}", params.date);
-            let response: OddsByDateResponse = serde_json::from_str(&cached_data)
-                .map_err(|e| {
-                    tracing::error!("Failed to deserialize cached data: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
-            return Ok(Json(response));
+/// Today's and upcoming games for the user's favorite teams, grouped into
+/// days by the user's time zone
+/// GET /api/v1/me/feed
+pub async fn handle_me_feed_request(
+    Query(params): Query<MeFeedQuery>,
+    State(state): State<UseCaseState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<MeFeedResponse>> {
+    let preferences: UserPreferences =
+        load_user_data(&state, CacheKey::user_preferences(&user.user_id)).await;
+    let favorites: UserFavorites =
+        load_user_data(&state, CacheKey::user_favorites(&user.user_id)).await;
+
+    let tz = preferences.tz();
+    let days = params
+        .days
+        .unwrap_or(DEFAULT_FEED_DAYS)
+        .clamp(1, MAX_FEED_DAYS);
+    let from = chrono::Utc::now().with_timezone(&tz).date_naive();
+    let to = from + chrono::Duration::days(days as i64 - 1);
+
+    let mut leagues: Vec<League> = Vec::new();
+    for favorite in &favorites.teams {
+        match favorite.league.parse::<League>() {
+            // The leagues current games supports
+            Ok(league @ (League::Mlb | League::Nfl | League::Nba)) => {
+                if !leagues.contains(&league) {
+                    leagues.push(league);
+                }
+            }
+            _ => tracing::debug!("No feed for {} favorites", favorite.league),
         }
     }
 
-    let raw_data =
-        fetch_odds_by_date_from_api(&api_url, &league, &use_case_state.config).await?;
+    let mut games = Vec::new();
+    for league in leagues {
+        let league_name = league.to_string();
+        // Upstream game days are US Eastern; a day either side covers any
+        // time zone's calendar
+        let raw = match fetch_games_between(
+            &state,
+            &league,
+            from.pred_opt().unwrap_or(from),
+            to.succ_opt().unwrap_or(to),
+            true,
+        )
+        .await
+        {
+            Ok(raw) => raw,
+            Err(e) => {
+                tracing::warn!("Skipping {} games in feed: {}", league_name, e);
+                continue;
+            }
+        };
+        games.extend(feed_games(
+            &league_name,
+            &raw,
+            &favorites.teams_in(&league_name),
+            tz,
+            from,
+            to,
+        ));
+    }
+    games.sort_by_key(|game| chrono::DateTime::parse_from_rfc3339(&game.starts_at).ok());
 
-    let game_odds: Vec<GameOdds> = serde_json::from_str(&raw_data).map_err(|e| {
-        tracing::error!("Failed to parse odds JSON: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    Ok(Json(MeFeedResponse {
+        time_zone: tz.name().to_string(),
+        from: from.format("%Y-%m-%d").to_string(),
+        to: to.format("%Y-%m-%d").to_string(),
+        games,
+    }))
+}
 
-    let response = OddsByDateResponse {
-        date: params.date.clone(),
-        data: game_odds,
-        games_count: 0, // Will be set from data length
-    };
+#[derive(Debug, Deserialize)]
+pub struct SignInQuery {
+    /// Where to land after sign-in; must be the client URL or a configured
+    /// return URL such as an iOS deep link
+    #[serde(default)]
+    pub return_to: Option<String>,
+    /// The client's PKCE `S256` challenge; `/auth/token` needs its verifier
+    #[serde(default)]
+    pub code_challenge: Option<String>,
+    #[serde(default)]
+    pub code_challenge_method: Option<String>,
+}
 
-    let response = OddsByDateResponse {
-        games_count: response.data.len(),
-        ..response
+/// `Set-Cookie` for the signed sign-in state, or clearing it when `None`
+///
+/// `SameSite=None` because Apple's callback is a cross-site form post; the
+/// path keeps the cookie off every other request.
+fn oauth_state_cookie(use_case_state: &UseCaseState, state: Option<&str>) -> String {
+    let (value, max_age) = match state {
+        Some(state) => (
+            sign_value(state, &use_case_state.config.api.jwt_secret),
+            use_case_state.config.cache.ttl.oauth_state,
+        ),
+        None => (String::new(), 0),
     };
+    format!(
+        "{}={}; Max-Age={}; Path=/api/v1/signin; HttpOnly; Secure; SameSite=None",
+        OAUTH_STATE_COOKIE, value, max_age
+    )
+}
 
-    {
-        let mut cache = use_case_state.cache.lock().await;
-        let serialized = serde_json::to_string(&response).map_err(|e| {
-            tracing::error!("Failed to serialize response: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-        cache
-            .setx(
-                &cache_key,
-                &serialized,
-                use_case_state.config.cache.ttl.odds,
-            )
-            .await?;
-    }
-
-    tracing::info!(
-        "Successfully fetched and cached odds for date: {}",
-        params.date
-    );
-    Ok(Json(response))
+/// Whether the browser's signed state cookie carries `state`, so a callback
+/// can't be replayed into another browser
+fn oauth_state_matches(
+    use_case_state: &UseCaseState,
+    headers: &HeaderMap,
+    state: &str,
+) -> bool {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .filter(|(name, _)| *name == OAUTH_STATE_COOKIE)
+        .any(|(_, value)| {
+            verify_signed_value(value, &use_case_state.config.api.jwt_secret)
+                == Some(state)
+        })
 }
 
-async fn fetch_odds_by_date_from_api(
-    api_url: &str,
-    league: &League,
-    config: &ArbConfig,
-) -> Result<String> {
-    let api_key = &config.api.sportsdata_api_key;
+/// `base` with `query` appended, whether or not it already has a query string
+fn signin_redirect(base: &str, query: &str) -> Redirect {
+    let separator = if base.contains('?') { '&' } else { '?' };
+    Redirect::to(&format!("{}{}{}", base, separator, query))
+}
 
-    let client = reqwest::Client::new();
+/// Start a sign-in: check the return URL and client challenge and store a
+/// fresh state, nonce and PKCE verifier until the callback
+///
+/// Returns the session and the state cookie to set.
+async fn start_oauth_session(
+    use_case_state: &UseCaseState,
+    params: SignInQuery,
+    pkce: bool,
+) -> std::result::Result<(OAuthSession, String), (StatusCode, String)> {
+    let SignInQuery {
+        return_to,
+        code_challenge,
+        code_challenge_method,
+    } = params;
+    let code_challenge = code_challenge
+        .filter(|challenge| is_pkce_challenge(challenge))
+        .filter(|_| code_challenge_method.as_deref().is_none_or(|m| m == "S256"))
+        .ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                "code_challenge must be an S256 PKCE challenge".to_string(),
+            )
+        })?;
 
-    tracing::info!(
-        "Making real API request to: {} for league: {}",
-        api_url,
-        league
-    );
+    let server = &use_case_state.config.server;
+    if let Some(return_to) = return_to.as_deref() {
+        let allowed: Vec<&str> = std::iter::once(server.client_url.as_str())
+            .chain(server.return_urls.iter().map(String::as_str))
+            .collect();
+        if !is_allowed_return_url(return_to, &allowed) {
+            tracing::warn!("Rejected sign-in return URL: {}", return_to);
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "return_to is not an allowed return URL".to_string(),
+            ));
+        }
+    }
 
-    let response = client
-        .get(api_url)
-        .query(&[("key", &api_key)])
-        .send()
+    let session = OAuthSession::new(pkce, return_to, code_challenge);
+    let session_json = serde_json::to_string(&session)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let mut cache = use_case_state.lock_store().await;
+    cache
+        .setx(
+            CacheKey::oauth_session(&session.state),
+            &session_json,
+            use_case_state.config.cache.ttl.oauth_state,
+        )
         .await
         .map_err(|e| {
-            tracing::error!("Failed to make HTTP request: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            tracing::error!("Failed to store sign-in session: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to start sign-in".to_string(),
+            )
         })?;
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        tracing::error!("API request failed with status {}: {}", status, error_text);
-        return Err(StatusCode::from_u16(status.as_u16())
-            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
-            .into());
-    }
-
-    let body = response.text().await.map_err(|e| {
-        tracing::error!("Failed to read response body: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let cookie = oauth_state_cookie(use_case_state, Some(&session.state));
+    Ok((session, cookie))
+}
 
-    tracing::info!("Successfully fetched odds data from API");
-    Ok(body)
+/// The stored sign-in a callback's `state` refers to, if this browser started
+/// it; each can be used once
+async fn take_oauth_session(
+    use_case_state: &UseCaseState,
+    headers: &HeaderMap,
+    state: Option<&String>,
+) -> Option<OAuthSession> {
+    let state = state?;
+    if !oauth_state_matches(use_case_state, headers, state) {
+        tracing::warn!("Sign-in callback without a matching state cookie");
+        return None;
+    }
+    let stored = use_case_state
+        .lock_store()
+        .await
+        .take(CacheKey::oauth_session(state))
+        .await;
+    match stored {
+        Ok(Some(data)) => serde_json::from_str(&data).ok(),
+        Ok(None) => None,
+        Err(e) => {
+            tracing::error!("Failed to load sign-in session: {}", e);
+            None
+        }
+    }
 }
 
 /// Google OAuth redirect endpoint
 /// GET /api/v1/signin/google
 pub async fn handle_google_auth_redirect(
+    Query(params): Query<SignInQuery>,
     State(use_case_state): State<UseCaseState>,
-) -> std::result::Result<Redirect, (StatusCode, String)> {
+) -> std::result::Result<impl IntoResponse, (StatusCode, String)> {
+    let (session, cookie) = start_oauth_session(&use_case_state, params, true).await?;
     let google_oauth = GoogleOAuth::new(
         use_case_state.config.api.google_oauth.client_id.clone(),
         use_case_state.config.api.google_oauth.client_secret.clone(),
         use_case_state.config.api.google_oauth.redirect_uri.clone(),
-        use_case_state.config.api.jwt_secret.clone(),
     );
 
-    let auth_url = google_oauth.get_auth_url();
-    Ok(Redirect::to(&auth_url))
+    let auth_url = google_oauth.get_auth_url(&session);
+    Ok(([(header::SET_COOKIE, cookie)], Redirect::to(&auth_url)))
 }
 
 /// Google OAuth callback endpoint
@@ -3605,236 +7711,308 @@ pub async fn handle_google_auth_redirect(
 pub async fn handle_google_auth_callback(
     Query(params): Query<HashMap<String, String>>,
     State(use_case_state): State<UseCaseState>,
-) -> std::result::Result<Redirect, (StatusCode, String)> {
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let redirect = google_sign_in(&use_case_state, &params, &headers).await;
+    (
+        [(
+            header::SET_COOKIE,
+            oauth_state_cookie(&use_case_state, None),
+        )],
+        redirect,
+    )
+}
+
+/// Finish a Google sign-in, redirecting with a one-time code or an error
+async fn google_sign_in(
+    use_case_state: &UseCaseState,
+    params: &HashMap<String, String>,
+    headers: &HeaderMap,
+) -> Redirect {
+    let client_url = use_case_state.config.server.client_url.clone();
+    let Some(session) =
+        take_oauth_session(use_case_state, headers, params.get("state")).await
+    else {
+        tracing::warn!("Google callback with unknown or reused state");
+        return signin_redirect(&client_url, "error=state");
+    };
+    let return_to = session.return_to.clone().unwrap_or(client_url);
+
     let code = match params.get("code") {
         Some(code) => code,
-        None => {
-            let error_url =
-                format!("{}?error=code", use_case_state.config.server.client_url);
-            return Ok(Redirect::to(&error_url));
-        }
+        None => return signin_redirect(&return_to, "error=code"),
     };
 
     let google_oauth = GoogleOAuth::new(
         use_case_state.config.api.google_oauth.client_id.clone(),
         use_case_state.config.api.google_oauth.client_secret.clone(),
         use_case_state.config.api.google_oauth.redirect_uri.clone(),
-        use_case_state.config.api.jwt_secret.clone(),
     );
 
-    let token_response = match google_oauth.exchange_code_for_token(code).await {
+    let token_response = match google_oauth
+        .exchange_code_for_token(code, session.code_verifier.as_deref())
+        .await
+    {
         Ok(token) => token,
         Err(e) => {
-            let error_url = format!(
-                "{}?error=token&message={}",
-                use_case_state.config.server.client_url,
+            let query = format!(
+                "error=token&message={}",
                 urlencoding::encode(&e.to_string())
             );
-            return Ok(Redirect::to(&error_url));
+            return signin_redirect(&return_to, &query);
         }
     };
 
-    let user_info = match google_oauth
-        .get_user_info(&token_response.access_token)
+    let id_token = match token_response.id_token {
+        Some(token) => token,
+        None => return signin_redirect(&return_to, "error=missing_id_token"),
+    };
+
+    let claims = match use_case_state
+        .id_tokens
+        .verify(
+            IdTokenProvider::Google,
+            &id_token,
+            &[use_case_state.config.api.google_oauth.client_id.as_str()],
+            Some(&session.nonce),
+        )
         .await
     {
-        Ok(info) => info,
+        Ok(claims) => claims,
         Err(e) => {
-            let error_url = format!(
-                "{}?error=userinfo&message={}",
-                use_case_state.config.server.client_url,
+            tracing::warn!("Rejected Google ID token: {}", e);
+            let query = format!(
+                "error=id_token&message={}",
                 urlencoding::encode(&e.to_string())
             );
-            return Ok(Redirect::to(&error_url));
+            return signin_redirect(&return_to, &query);
         }
     };
+    let Some(email) = claims.verified_email() else {
+        return signin_redirect(&return_to, "error=unverified_email");
+    };
 
     let user = match google_oauth
-        .create_or_update_user(&user_info.email, &user_info.name)
+        .create_or_update_user(email, claims.name.as_deref().unwrap_or_default())
         .await
     {
         Ok(user) => user,
         Err(e) => {
-            let error_url = format!(
-                "{}?error=user&message={}",
-                use_case_state.config.server.client_url,
-                urlencoding::encode(&e.to_string())
-            );
-            return Ok(Redirect::to(&error_url));
+            let query =
+                format!("error=user&message={}", urlencoding::encode(&e.to_string()));
+            return signin_redirect(&return_to, &query);
         }
     };
 
-    let cache_key = CacheKey::user(use_case_state.provider(), &user.email);
-    let user_json = match serde_json::to_string(&user) {
-        Ok(json) => json,
+    if let Err(query) = store_signed_in_user(use_case_state, &user).await {
+        return signin_redirect(&return_to, &query);
+    }
+    let code = match issue_auth_code(use_case_state, &user, &session).await {
+        Ok(code) => code,
         Err(e) => {
-            let error_url = format!(
-                "{}?error=serialize&message={}",
-                use_case_state.config.server.client_url,
-                urlencoding::encode(&e.to_string())
-            );
-            return Ok(Redirect::to(&error_url));
+            tracing::error!("Failed to issue sign-in code: {}", e);
+            return signin_redirect(&return_to, "error=session");
         }
     };
 
+    signin_redirect(&return_to, &format!("a=ok&s=g&code={}", code))
+}
+
+/// Cache a signed-in user, or the error query to redirect with
+async fn store_signed_in_user(
+    use_case_state: &UseCaseState,
+    user: &User,
+) -> std::result::Result<(), String> {
+    let cache_key = CacheKey::user(use_case_state.provider(), &user.email);
+    let user_json = serde_json::to_string(user).map_err(|e| {
+        format!(
+            "error=serialize&message={}",
+            urlencoding::encode(&e.to_string())
+        )
+    })?;
+
+    let mut cache = use_case_state.lock_cache().await;
+    if let Err(e) = cache
+        .setx(
+            &cache_key,
+            &user_json,
+            use_case_state.config.cache.ttl.user_auth,
+        )
+        .await
     {
-        let mut cache = use_case_state.cache.lock().await;
-        if let Err(e) = cache
-            .setx(
-                &cache_key,
-                &user_json,
-                use_case_state.config.cache.ttl.user_auth,
-            )
-            .await
-        {
-            tracing::warn!("Failed to cache user: {}", e);
-        }
+        tracing::warn!("Failed to cache user: {}", e);
     }
+    Ok(())
+}
 
-    let success_url = format!(
-        "{}?a=ok&s=g&e={}",
-        use_case_state.config.server.client_url,
-        urlencoding::encode(&user.email)
-    );
-    Ok(Redirect::to(&success_url))
+fn apple_oauth(use_case_state: &UseCaseState) -> AppleOAuth {
+    let apple = &use_case_state.config.api.apple_oauth;
+    AppleOAuth::new(
+        apple.client_id.clone(),
+        apple.redirect_uri.clone(),
+        apple.team_id.clone(),
+        apple.key_id.clone(),
+        apple.secret_key_path.clone(),
+        apple.jwt_expire_seconds,
+    )
 }
 
 /// Apple OAuth redirect endpoint
 /// GET /api/v1/signin/apple
 pub async fn handle_apple_auth_redirect(
+    Query(params): Query<SignInQuery>,
     State(use_case_state): State<UseCaseState>,
-) -> std::result::Result<Redirect, (StatusCode, String)> {
-    let apple_oauth = AppleOAuth::new(
-        use_case_state.config.api.apple_oauth.client_id.clone(),
-        use_case_state.config.api.apple_oauth.redirect_uri.clone(),
-        use_case_state.config.api.apple_oauth.team_id.clone(),
-        use_case_state.config.api.apple_oauth.key_id.clone(),
-        use_case_state
-            .config
-            .api
-            .apple_oauth
-            .secret_key_path
-            .clone(),
-        use_case_state.config.api.apple_oauth.jwt_expire_seconds,
-        use_case_state.config.api.jwt_secret.clone(),
-    );
-
-    let auth_url = apple_oauth.get_auth_url();
-    Ok(Redirect::to(&auth_url))
+) -> std::result::Result<impl IntoResponse, (StatusCode, String)> {
+    // Apple doesn't support PKCE; the client secret and nonce cover the exchange
+    let (session, cookie) = start_oauth_session(&use_case_state, params, false).await?;
+    let auth_url = apple_oauth(&use_case_state).get_auth_url(&session);
+    Ok(([(header::SET_COOKIE, cookie)], Redirect::to(&auth_url)))
 }
 
 /// Apple OAuth callback endpoint
 /// POST /api/v1/signin/apple/callback
 pub async fn handle_apple_auth_callback(
     State(use_case_state): State<UseCaseState>,
+    headers: HeaderMap,
     body: String,
-) -> std::result::Result<Redirect, (StatusCode, String)> {
+) -> impl IntoResponse {
     let form_data: HashMap<String, String> = url::form_urlencoded::parse(body.as_bytes())
         .into_owned()
         .collect();
+    let redirect = apple_sign_in(&use_case_state, &form_data, &headers).await;
+    (
+        [(
+            header::SET_COOKIE,
+            oauth_state_cookie(&use_case_state, None),
+        )],
+        redirect,
+    )
+}
+
+/// Finish an Apple sign-in, redirecting with a one-time code or an error
+async fn apple_sign_in(
+    use_case_state: &UseCaseState,
+    form_data: &HashMap<String, String>,
+    headers: &HeaderMap,
+) -> Redirect {
+    let client_url = use_case_state.config.server.client_url.clone();
+    let Some(session) =
+        take_oauth_session(use_case_state, headers, form_data.get("state")).await
+    else {
+        tracing::warn!("Apple callback with unknown or reused state");
+        return signin_redirect(&client_url, "error=state");
+    };
+    let return_to = session.return_to.clone().unwrap_or(client_url);
 
     let code = match form_data.get("code") {
         Some(code) => code,
-        None => {
-            let error_url =
-                format!("{}?error=code", use_case_state.config.server.client_url);
-            return Ok(Redirect::to(&error_url));
-        }
-    };
-
-    let apple_oauth = AppleOAuth::new(
-        use_case_state.config.api.apple_oauth.client_id.clone(),
-        use_case_state.config.api.apple_oauth.redirect_uri.clone(),
-        use_case_state.config.api.apple_oauth.team_id.clone(),
-        use_case_state.config.api.apple_oauth.key_id.clone(),
-        use_case_state
-            .config
-            .api
-            .apple_oauth
-            .secret_key_path
-            .clone(),
-        use_case_state.config.api.apple_oauth.jwt_expire_seconds,
-        use_case_state.config.api.jwt_secret.clone(),
-    );
+        None => return signin_redirect(&return_to, "error=code"),
+    };
 
+    let apple_oauth = apple_oauth(use_case_state);
     let token_response = match apple_oauth.exchange_code_for_token(code).await {
         Ok(token) => token,
         Err(e) => {
-            let error_url = format!(
-                "{}?error=token&message={}",
-                use_case_state.config.server.client_url,
+            let query = format!(
+                "error=token&message={}",
                 urlencoding::encode(&e.to_string())
             );
-            return Ok(Redirect::to(&error_url));
+            return signin_redirect(&return_to, &query);
         }
     };
 
     let id_token = match token_response.id_token {
         Some(token) => token,
-        None => {
-            let error_url = format!(
-                "{}?error=missing_id_token",
-                use_case_state.config.server.client_url
-            );
-            return Ok(Redirect::to(&error_url));
-        }
+        None => return signin_redirect(&return_to, "error=missing_id_token"),
     };
 
-    let user = match apple_oauth
-        .create_or_update_user_from_token(&id_token)
+    let claims = match use_case_state
+        .id_tokens
+        .verify(
+            IdTokenProvider::Apple,
+            &id_token,
+            &[use_case_state.config.api.apple_oauth.client_id.as_str()],
+            Some(&session.nonce),
+        )
         .await
     {
-        Ok(user) => user,
+        Ok(claims) => claims,
         Err(e) => {
-            let error_url = format!(
-                "{}?error=user&message={}",
-                use_case_state.config.server.client_url,
+            tracing::warn!("Rejected Apple ID token: {}", e);
+            let query = format!(
+                "error=id_token&message={}",
                 urlencoding::encode(&e.to_string())
             );
-            return Ok(Redirect::to(&error_url));
+            return signin_redirect(&return_to, &query);
         }
     };
 
-    let cache_key = CacheKey::user(use_case_state.provider(), &user.email);
-    let user_json = match serde_json::to_string(&user) {
-        Ok(json) => json,
+    let user = match apple_oauth.create_or_update_user_from_claims(&claims).await {
+        Ok(user) => user,
         Err(e) => {
-            let error_url = format!(
-                "{}?error=serialize&message={}",
-                use_case_state.config.server.client_url,
-                urlencoding::encode(&e.to_string())
-            );
-            return Ok(Redirect::to(&error_url));
+            let query =
+                format!("error=user&message={}", urlencoding::encode(&e.to_string()));
+            return signin_redirect(&return_to, &query);
         }
     };
 
-    {
-        let mut cache = use_case_state.cache.lock().await;
-        if let Err(e) = cache
-            .setx(
-                &cache_key,
-                &user_json,
-                use_case_state.config.cache.ttl.user_auth,
-            )
-            .await
-        {
-            tracing::warn!("Failed to cache user: {}", e);
-        }
+    if let Err(query) = store_signed_in_user(use_case_state, &user).await {
+        return signin_redirect(&return_to, &query);
     }
+    let code = match issue_auth_code(use_case_state, &user, &session).await {
+        Ok(code) => code,
+        Err(e) => {
+            tracing::error!("Failed to issue sign-in code: {}", e);
+            return signin_redirect(&return_to, "error=session");
+        }
+    };
 
-    let success_url = format!(
-        "{}?a=ok&s=a&e={}",
-        use_case_state.config.server.client_url,
-        urlencoding::encode(&user.email)
-    );
-    Ok(Redirect::to(&success_url))
+    signin_redirect(&return_to, &format!("a=ok&s=a&code={}", code))
 }
 
+pub use handle_add_favorite_request as add_favorite;
+pub use handle_auth_refresh_request as auth_refresh;
+pub use handle_auth_token_request as auth_token;
 pub use handle_box_score_request as box_score;
+pub use handle_delete_preferences_request as delete_preferences;
+pub use handle_depth_charts_request as depth_charts;
 pub use handle_game_by_date_request as game_by_date;
+pub use handle_game_sentiment_request as game_sentiment;
+pub use handle_game_social_request as game_social;
+pub use handle_game_timeline_request as game_timeline;
+pub use handle_get_favorites_request as get_favorites;
+pub use handle_get_preferences_request as get_preferences;
+pub use handle_guest_signup_request as guest_signup;
+pub use handle_injuries_request as injuries;
+pub use handle_issue_api_key_request as issue_api_key;
+pub use handle_leaderboard_request as leaderboard;
+pub use handle_lineups_request as lineups;
+pub use handle_logout_request as logout;
+pub use handle_me_feed_request as me_feed;
+pub use handle_me_request as me;
+pub use handle_metrics_request as metrics;
+pub use handle_news_request as news;
 pub use handle_odds_by_date_request as odds_by_date;
+pub use handle_patch_preferences_request as patch_preferences;
+pub use handle_player_career_request as player_career;
+pub use handle_player_game_log_request as player_game_log;
+pub use handle_player_profile_request as player_profile;
+pub use handle_player_splits_request as player_splits;
+pub use handle_playoff_odds_request as playoff_odds;
+pub use handle_probable_pitchers_request as probable_pitchers;
+pub use handle_put_favorites_request as put_favorites;
+pub use handle_put_preferences_request as put_preferences;
+pub use handle_rating_history_request as rating_history;
+pub use handle_ratings_request as ratings;
 pub use handle_reddit_search_request as reddit_search;
 pub use handle_reddit_thread_request as reddit_thread;
+pub use handle_remove_favorite_request as remove_favorite;
+pub use handle_revoke_api_key_request as revoke_api_key;
+pub use handle_season_stats_request as season_stats;
 pub use handle_stadiums_request as stadiums;
+pub use handle_standings_analytics_request as standings_analytics;
 pub use handle_standings_request as standings;
 pub use handle_twitter_search_request as twitter_search;
+pub use handle_usage_report_request as usage_report;
+pub use handle_videos_request as videos;
+pub use handle_win_probability_request as win_probability;
@@ -4,15 +4,14 @@ use std::env;
 
 use arb_rs::cache::Cache;
 use arb_rs::config::{ArbConfig, CacheConfig, CacheMode, CacheTtlConfig};
+use arb_rs::metrics::Metrics;
 use arb_rs::server::Server;
+use arb_rs::services::auth::Claims;
 
-async fn setup_test_server() -> TestServer {
-    env::set_var("SPORTDATAIO_API_KEY", "test_api_key");
-    env::set_var("REDIS_URL", "redis://127.0.0.1:6379");
-
+fn test_cache_config() -> CacheConfig {
     let redis_url =
         env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
-    let cache_config = CacheConfig {
+    CacheConfig {
         enabled: true,
         mode: CacheMode::TtlBased,
         redis_url,
@@ -27,14 +26,63 @@ async fn setup_test_server() -> TestServer {
             stadiums: 86400,             // 24 hours
             standings: 21600,            // 6 hours
             twitter_search: 60,          // 1 minute
+            twitter_search_stale: 86400, // 1 day
             reddit_thread: 600,          // 10 minutes
             reddit_thread_comments: 120, // 2 minutes
             odds: 86400,                 // 24 hours
             user_auth: 604800,           // 1 week
+            oauth_state: 600,            // 10 minutes
+            user_preferences: 31536000,  // 1 year
+            user_favorites: 63072000,    // 2 years
+            playoff_odds: 21600,         // 6 hours
+            ratings: 31536000,           // 1 year
+            season_stats: 3600,          // 1 hour
+            players: 3600,               // 1 hour
+            career_seasons: 31536000,    // 1 year
+            injuries: 300,               // 5 minutes
+            depth_charts: 3600,          // 1 hour
+            lineups: 300,                // 5 minutes
+            starter_alerts: 86400,       // 1 day
+            news: 900,                   // 15 minutes
+            videos: 604800,              // 1 week
+            health_probe: 300,           // 5 minutes
         },
-    };
-    let cache = match Cache::new(cache_config).await {
-        Ok(cache) => std::sync::Arc::new(async_std::sync::Mutex::new(cache)),
+    }
+}
+
+/// The real server with `config`, or `None` when Redis isn't running
+async fn setup_redis_server(config: ArbConfig) -> Option<TestServer> {
+    let metrics = Metrics::new().unwrap();
+    let cache = Cache::new(test_cache_config(), metrics.clone())
+        .await
+        .ok()?;
+    let store = std::sync::Arc::new(async_std::sync::Mutex::new(cache.store()));
+    let cache = std::sync::Arc::new(async_std::sync::Mutex::new(cache));
+    let server = Server::new(cache, store, config, metrics);
+    Some(TestServer::new(server.build()).unwrap())
+}
+
+/// A client address no earlier run has used, so its quota starts empty
+fn fresh_ip() -> String {
+    format!(
+        "test-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    )
+}
+
+async fn setup_test_server() -> TestServer {
+    env::set_var("SPORTDATAIO_API_KEY", "test_api_key");
+    env::set_var("REDIS_URL", "redis://127.0.0.1:6379");
+
+    let metrics = Metrics::new().unwrap();
+    let (cache, store) = match Cache::new(test_cache_config(), metrics.clone()).await {
+        Ok(cache) => (
+            std::sync::Arc::new(async_std::sync::Mutex::new(cache.clone())),
+            std::sync::Arc::new(async_std::sync::Mutex::new(cache.store())),
+        ),
         Err(_) => {
             use axum::{extract::Query, response::Json, routing::get, Router};
             use std::collections::HashMap;
@@ -147,7 +195,7 @@ async fn setup_test_server() -> TestServer {
     };
 
     let test_config = ArbConfig::default();
-    let server = Server::new(cache, test_config);
+    let server = Server::new(cache, store, test_config, metrics);
     let app = server.build();
 
     TestServer::new(app).unwrap()
@@ -445,3 +493,129 @@ async fn test_scores_with_days_back_parameter() {
         assert!(body.get("data").is_some());
     }
 }
+
+#[tokio::test]
+async fn test_rate_limited_response_carries_headers() {
+    let mut config = ArbConfig::default();
+    config.rate_limit.anonymous_requests = 2;
+    config.rate_limit.trust_forwarded_for = true;
+    let Some(server) = setup_redis_server(config).await else {
+        return;
+    };
+    let ip = fresh_ip();
+
+    let request = || {
+        server
+            .get("/api/v1/team-profile")
+            .add_query_param("league", "invalid")
+            .add_header("X-Forwarded-For", ip.as_str())
+    };
+    let first = request().await;
+    assert_eq!(first.status_code(), 400);
+    assert_eq!(first.header("ratelimit-limit"), "2");
+    assert_eq!(first.header("ratelimit-remaining"), "1");
+
+    request().await;
+    let limited = request().await;
+    assert_eq!(limited.status_code(), 429);
+    assert_eq!(limited.header("ratelimit-limit"), "2");
+    assert_eq!(limited.header("ratelimit-remaining"), "0");
+    let reset: u64 = limited
+        .header("ratelimit-reset")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=60).contains(&reset));
+    assert_eq!(
+        limited.header("retry-after"),
+        limited.header("ratelimit-reset")
+    );
+}
+
+#[tokio::test]
+async fn test_api_key_tier_sets_the_quota() {
+    let admin_key = format!("admin-{}", fresh_ip());
+    let mut config = ArbConfig::default();
+    config.rate_limit.anonymous_requests = 1;
+    config.rate_limit.app_requests = 5;
+    config.rate_limit.admin_requests = 50;
+    config.rate_limit.trust_forwarded_for = true;
+    config.rate_limit.admin_key = admin_key.clone();
+    let Some(server) = setup_redis_server(config).await else {
+        return;
+    };
+
+    let issued = server
+        .post("/api/v1/admin/api-keys")
+        .add_header("X-API-Key", admin_key.as_str())
+        .add_header("X-Forwarded-For", fresh_ip().as_str())
+        .json(&serde_json::json!({"name": "integration-test", "tier": "app"}))
+        .await;
+    assert_eq!(issued.status_code(), 201);
+    assert_eq!(issued.header("ratelimit-limit"), "50");
+    let body: Value = issued.json();
+    assert_eq!(body["tier"], "app");
+    let key = body["key"].as_str().unwrap().to_string();
+
+    // The IP's anonymous quota of one doesn't apply to requests with a key
+    let ip = fresh_ip();
+    for remaining in ["4", "3"] {
+        let response = server
+            .get("/api/v1/team-profile")
+            .add_query_param("league", "invalid")
+            .add_header("X-API-Key", key.as_str())
+            .add_header("X-Forwarded-For", ip.as_str())
+            .await;
+        assert_eq!(response.status_code(), 400);
+        assert_eq!(response.header("ratelimit-limit"), "5");
+        assert_eq!(response.header("ratelimit-remaining"), remaining);
+    }
+
+    // App keys can't issue keys
+    let forbidden = server
+        .post("/api/v1/admin/api-keys")
+        .add_header("X-API-Key", key.as_str())
+        .json(&serde_json::json!({"name": "nested"}))
+        .await;
+    assert_eq!(forbidden.status_code(), 403);
+}
+
+#[tokio::test]
+async fn test_me_requires_a_current_bearer_token() {
+    let mut config = ArbConfig::default();
+    config.api.jwt_secret = "integration-test-secret".to_string();
+    config.rate_limit.enabled = false;
+    let Some(server) = setup_redis_server(config).await else {
+        return;
+    };
+
+    let missing = server.get("/api/v1/me").await;
+    assert_eq!(missing.status_code(), 401);
+
+    // Signed with the right secret, but expired well past any leeway
+    let now = chrono::Utc::now().timestamp();
+    let claims = Claims {
+        sub: "fan@example.com".to_string(),
+        sid: "expired-session".to_string(),
+        exp: now - 3600,
+        iat: now - 7200,
+    };
+    let expired = jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(b"integration-test-secret"),
+    )
+    .unwrap();
+    let response = server
+        .get("/api/v1/me")
+        .add_header("Authorization", format!("Bearer {}", expired).as_str())
+        .await;
+    assert_eq!(response.status_code(), 401);
+
+    let malformed = server
+        .get("/api/v1/me")
+        .add_header("Authorization", "Bearer not-a-token")
+        .await;
+    assert_eq!(malformed.status_code(), 401);
+}
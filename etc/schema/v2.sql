@@ -0,0 +1,18 @@
+-- Sports Hub App Database Schema, v2 (apply after v1.sql)
+
+-- Social posts collected for provider games, which have no row in `games`
+ALTER TABLE social_posts ADD COLUMN provider_game_id VARCHAR(50);
+-- Teams the post was collected for, kept even when `teams` has no row for them
+ALTER TABLE social_posts ADD COLUMN team_abbreviations TEXT[];
+
+CREATE INDEX idx_social_posts_provider_game ON social_posts(league_id, provider_game_id, posted_at);
+
+-- Catalogued videos: one row per canonical link, the id the feed or provider
+-- gave it, and the same provider game and team fields as social posts
+ALTER TABLE videos ADD COLUMN url_key TEXT;
+ALTER TABLE videos ADD COLUMN external_id TEXT;
+ALTER TABLE videos ADD COLUMN provider_game_id VARCHAR(50);
+ALTER TABLE videos ADD COLUMN team_abbreviations TEXT[];
+ALTER TABLE videos ADD COLUMN source TEXT;
+
+CREATE UNIQUE INDEX idx_videos_url_key ON videos(url_key);